@@ -3,9 +3,9 @@ use std::sync::mpsc;
 use itsuki::zero_indexed_enum;
 use ratatui::{
     backend::Backend,
-    crossterm::event::{Event, KeyCode},
+    crossterm::event::{Event, KeyEvent},
     layout::{Margin, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::Line,
     widgets::Paragraph,
     Frame, Terminal,
@@ -13,9 +13,15 @@ use ratatui::{
 use ratatui_macros::{horizontal, vertical};
 
 use crate::{
-    key_code, key_code_char,
+    clipboard,
+    config::{Config, FocusTarget, SessionConfig},
+    event::AppEvent,
+    keymap::{Action, Keymap},
     msg::Msg,
+    palette::{Command, Palette},
     panes::{list::ListPane, pane::Pane, tool::ToolPane},
+    session::{Session, SessionError},
+    theme::Theme,
     util::group_strs_to_fit_width,
 };
 
@@ -36,31 +42,57 @@ pub struct App {
     quit: bool,
     focused: PaneType,
     notification: Notification,
+    keymap: Keymap,
+    theme: Theme,
+    palette: Option<Palette>,
     list_pane: ListPane,
     tool_pane: ToolPane,
+    session_config: SessionConfig,
+    /// Error saving the session on quit, reported after the terminal is restored.
+    session_error: Option<SessionError>,
 }
 
 impl App {
-    pub fn new() -> App {
+    pub fn new(config: &Config) -> App {
+        let mut notification = Notification::None;
+        let mut session = Session::default();
+        if config.session.enabled {
+            match Session::load() {
+                Ok(loaded) => session = loaded,
+                Err(e) => notification = Notification::Warn(e.to_string()),
+            }
+            session.exclude(&config.session.exclude);
+        }
+        let initial_page = session.page.unwrap_or(config.global.initial_page);
+        let (focused, list_focused) = match config.global.initial_focus {
+            FocusTarget::List => (PaneType::List, true),
+            FocusTarget::Tool => (PaneType::Tool, false),
+        };
+        clipboard::init(&config.clipboard);
         App {
             quit: false,
-            focused: PaneType::List,
-            notification: Notification::None,
-            list_pane: ListPane::new(true),
-            tool_pane: ToolPane::new(false),
+            focused,
+            notification,
+            keymap: Keymap::new(&config.keybindings),
+            theme: Theme::load(&config.theme),
+            palette: None,
+            list_pane: ListPane::new(list_focused, initial_page),
+            tool_pane: ToolPane::new(!list_focused, initial_page, config, session),
+            session_config: config.session.clone(),
+            session_error: None,
         }
     }
 
     pub fn start<B: Backend>(
         &mut self,
         terminal: &mut Terminal<B>,
-        rx: mpsc::Receiver<Event>,
+        rx: mpsc::Receiver<AppEvent>,
     ) -> std::io::Result<()> {
         while !self.quit {
             terminal.draw(|f| self.render(f))?;
 
             match rx.recv().unwrap() {
-                Event::Key(key) => {
+                AppEvent::Terminal(Event::Key(key)) => {
                     self.notification = Notification::None;
 
                     let mut current_msg = self.handle_key(key);
@@ -68,21 +100,42 @@ impl App {
                         current_msg = self.update(msg);
                     }
                 }
-                Event::Resize(w, h) => self.resize(w, h),
-                _ => {}
+                AppEvent::Terminal(Event::Resize(w, h)) => self.resize(w, h),
+                AppEvent::Terminal(_) => {}
+                AppEvent::Msg(msg) => {
+                    let mut current_msg = Some(msg);
+                    while let Some(msg) = current_msg {
+                        current_msg = self.update(msg);
+                    }
+                }
             }
         }
-        Ok(())
+        match self.session_error.take() {
+            Some(e) => Err(std::io::Error::other(e)),
+            None => Ok(()),
+        }
     }
 
-    fn handle_key(&self, key: ratatui::crossterm::event::KeyEvent) -> Option<Msg> {
-        match key {
-            key_code_char!('c', Ctrl) => Some(Msg::Quit),
-            key_code!(KeyCode::Tab) => Some(Msg::SwitchPane),
-            _ => match self.focused {
-                PaneType::List => self.list_pane.handle_key(key),
-                PaneType::Tool => self.tool_pane.handle_key(key),
-            },
+    fn handle_key(&self, key: KeyEvent) -> Option<Msg> {
+        let global_action = self.keymap.resolve_global(key);
+        if let Some(Action::Quit) = global_action {
+            return Some(Msg::Quit);
+        }
+        if let Some(palette) = &self.palette {
+            return palette.handle_key(key, &self.keymap);
+        }
+        if let Some(Action::SwitchPane) = global_action {
+            return Some(Msg::SwitchPane);
+        }
+
+        let msg = match self.focused {
+            PaneType::List => self.list_pane.handle_key(key, &self.keymap),
+            PaneType::Tool => self.tool_pane.handle_key(key, &self.keymap),
+        };
+        // the palette opens only if the key is not consumed by the pane, e.g. as text input
+        match (msg, self.keymap.resolve(key)) {
+            (None, Some(Action::CommandPalette)) => Some(Msg::PaletteOpen),
+            (msg, _) => msg,
         }
     }
 
@@ -103,6 +156,29 @@ impl App {
             Msg::NotifyError(msg) => {
                 self.notification = Notification::Error(msg);
             }
+            Msg::PaletteOpen => {
+                self.open_palette();
+            }
+            Msg::PaletteClose => {
+                self.palette = None;
+            }
+            Msg::PaletteExecute => {
+                let msgs = self.palette.take().and_then(|p| p.selected_msgs());
+                return msgs.map(Msg::Batch);
+            }
+            Msg::PaletteSelectNext | Msg::PaletteSelectPrev | Msg::PaletteKeyEvent(_) => {
+                if let Some(palette) = &mut self.palette {
+                    palette.update(msg);
+                }
+            }
+            Msg::Batch(msgs) => {
+                for msg in msgs {
+                    let mut current_msg = Some(msg);
+                    while let Some(msg) = current_msg {
+                        current_msg = self.update(msg);
+                    }
+                }
+            }
             Msg::Page(page_msg) => {
                 let tool_msg = self.tool_pane.update(Msg::Page(page_msg));
                 return tool_msg;
@@ -118,6 +194,11 @@ impl App {
 
     fn quit_app(&mut self) {
         self.quit = true;
+        if self.session_config.enabled {
+            let mut session = self.tool_pane.session();
+            session.exclude(&self.session_config.exclude);
+            self.session_error = session.save().err();
+        }
     }
 
     fn switch_pane(&mut self) {
@@ -136,6 +217,14 @@ impl App {
         }
     }
 
+    fn open_palette(&mut self) {
+        let mut commands = self.tool_pane.commands();
+        commands.extend(self.list_pane.commands());
+        commands.push(Command::new("Switch pane", vec![Msg::SwitchPane]));
+        commands.push(Command::new("Quit", vec![Msg::Quit]));
+        self.palette = Some(Palette::new(commands));
+    }
+
     fn render(&mut self, f: &mut Frame) {
         let area = f.area();
 
@@ -151,13 +240,17 @@ impl App {
         } else {
             self.render_notification(f, chunks[1]);
         }
+
+        if let Some(palette) = &self.palette {
+            palette.render(f, chunks[0], &self.theme);
+        }
     }
 
     fn render_panes(&mut self, f: &mut Frame, area: Rect) {
         let chunks = horizontal![==20, >=0].split(area);
 
-        self.list_pane.render(f, chunks[0]);
-        self.tool_pane.render(f, chunks[1]);
+        self.list_pane.render(f, chunks[0], &self.theme);
+        self.tool_pane.render(f, chunks[1], &self.theme);
     }
 
     fn render_notification(&self, f: &mut Frame, area: Rect) {
@@ -165,13 +258,13 @@ impl App {
         let style = Style::default().add_modifier(Modifier::BOLD);
         match &self.notification {
             Notification::Info(msg) => {
-                f.render_widget(Line::styled(msg, style.fg(Color::Green)), area);
+                f.render_widget(Line::styled(msg, self.theme.info.patch(style)), area);
             }
             Notification::Warn(msg) => {
-                f.render_widget(Line::styled(msg, style.fg(Color::Yellow)), area);
+                f.render_widget(Line::styled(msg, self.theme.warn.patch(style)), area);
             }
             Notification::Error(msg) => {
-                f.render_widget(Line::styled(msg, style.fg(Color::Red)), area);
+                f.render_widget(Line::styled(msg, self.theme.error.patch(style)), area);
             }
             Notification::None => {}
         };
@@ -180,7 +273,7 @@ impl App {
     fn render_help(&self, f: &mut Frame, area: Rect, help_lines: Vec<String>) {
         let help_lines: Vec<Line> = help_lines
             .iter()
-            .map(|line| Line::styled(line, Style::default().fg(Color::DarkGray)))
+            .map(|line| Line::styled(line, self.theme.help))
             .collect();
         let help = Paragraph::new(help_lines);
         f.render_widget(help, area.inner(Margin::new(1, 0)));
@@ -194,10 +287,15 @@ impl App {
 impl App {
     fn help_lines(&self, width: u16) -> Vec<String> {
         let delimiter = ", ";
-        let helps = match self.focused {
-            PaneType::List => self.list_pane.helps(),
-            PaneType::Tool => self.tool_pane.helps(),
-        };
+        let helps: Vec<String> = match (&self.palette, self.focused) {
+            (Some(palette), _) => palette.helps(),
+            (None, PaneType::List) => self.list_pane.helps(),
+            (None, PaneType::Tool) => self.tool_pane.helps(),
+        }
+        .iter()
+        .map(|help| self.keymap.help_str(help))
+        .collect();
+        let helps: Vec<&str> = helps.iter().map(String::as_str).collect();
         group_strs_to_fit_width(&helps, width as usize, delimiter)
             .iter()
             .map(|helps| helps.join(delimiter))
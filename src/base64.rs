@@ -0,0 +1,337 @@
+use std::fmt;
+
+use base64::{
+    alphabet,
+    engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine as _,
+};
+
+/// Line length of MIME (RFC 2045) encoded text.
+const MIME_LINE_LEN: usize = 76;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Encode,
+    Decode,
+}
+
+/// Alphabet and line wrapping of Base64.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// RFC 4648 with `+` and `/`.
+    #[default]
+    Standard,
+    /// RFC 4648 with `-` and `_`, as used by JWT and signed URLs.
+    UrlSafe,
+    /// The standard alphabet wrapped at 76 columns.
+    Mime,
+    /// `./A-Za-z0-9`, as used by bcrypt hashes.
+    Bcrypt,
+    /// `./0-9A-Za-z`, as used by crypt(3) hashes.
+    Crypt,
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Variant::Standard => "standard",
+            Variant::UrlSafe => "URL-safe",
+            Variant::Mime => "MIME",
+            Variant::Bcrypt => "bcrypt",
+            Variant::Crypt => "crypt",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl Variant {
+    fn alphabet(&self) -> &'static alphabet::Alphabet {
+        match self {
+            Variant::Standard | Variant::Mime => &alphabet::STANDARD,
+            Variant::UrlSafe => &alphabet::URL_SAFE,
+            Variant::Bcrypt => &alphabet::BCRYPT,
+            Variant::Crypt => &alphabet::CRYPT,
+        }
+    }
+
+    /// Returns the variant the input is most likely encoded in.
+    /// Inputs of letters and digits only are valid in every variant and detected as standard,
+    /// and `.` is detected as bcrypt since it is shared with crypt, see [`Variant::ambiguous_with`].
+    pub fn detect(input: &str) -> Variant {
+        let has = |chars: &[char]| input.contains(chars);
+        if has(&['-', '_']) {
+            Variant::UrlSafe
+        } else if has(&['.']) {
+            Variant::Bcrypt
+        } else if is_mime_wrapped(input) {
+            Variant::Mime
+        } else {
+            Variant::Standard
+        }
+    }
+
+    /// Returns the variant that can not be told apart from this one when detected,
+    /// i.e. crypt for bcrypt since they share the characters but not their order.
+    pub fn ambiguous_with(&self) -> Option<Variant> {
+        match self {
+            Variant::Bcrypt => Some(Variant::Crypt),
+            _ => None,
+        }
+    }
+}
+
+/// Returns whether the input has multiple lines and all but the last are 76 characters long.
+fn is_mime_wrapped(input: &str) -> bool {
+    let lines: Vec<&str> = input.trim_end().lines().map(str::trim_end).collect();
+    match lines.split_last() {
+        Some((last, rest)) if !rest.is_empty() => {
+            rest.iter().all(|line| line.len() == MIME_LINE_LEN) && last.len() <= MIME_LINE_LEN
+        }
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Base64Error {
+    InvalidInput,
+}
+
+impl fmt::Display for Base64Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Base64Error::InvalidInput => write!(f, "invalid base64 input"),
+        }
+    }
+}
+
+impl std::error::Error for Base64Error {}
+
+#[derive(Debug, Default, Clone)]
+pub struct Base64Request<'a> {
+    pub input: &'a str,
+    pub mode: Mode,
+    /// The variant to encode or decode with, detected from the input on decode if `None`.
+    pub variant: Option<Variant>,
+    /// Omits the trailing `=` on encode. Decode accepts input with or without padding.
+    pub unpadded: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Base64Response {
+    /// The output as text, decoded bytes that are not valid UTF-8 are replaced with U+FFFD.
+    pub output: String,
+    /// The output as is, which is the decoded data on decode.
+    pub bytes: Vec<u8>,
+    /// The variant used, which is the detected one if the request did not specify it.
+    pub variant: Variant,
+    /// Whether the output on encode, or the input on decode, is padded.
+    pub padded: bool,
+}
+
+pub fn calculate(req: &Base64Request) -> Result<Base64Response, Base64Error> {
+    match req.mode {
+        Mode::Encode => Ok(encode(
+            req.input.as_bytes(),
+            req.variant.unwrap_or_default(),
+            req.unpadded,
+        )),
+        Mode::Decode => {
+            let variant = req.variant.unwrap_or_else(|| Variant::detect(req.input));
+            let input: String = req
+                .input
+                .chars()
+                .filter(|c| !c.is_ascii_whitespace())
+                .collect();
+            let decoded = engine(variant, true)
+                .decode(&input)
+                .map_err(|_| Base64Error::InvalidInput)?;
+            Ok(Base64Response {
+                output: String::from_utf8_lossy(&decoded).to_string(),
+                bytes: decoded,
+                variant,
+                padded: input.ends_with('='),
+            })
+        }
+    }
+}
+
+/// Encodes bytes that are not necessarily text, e.g. the contents of a file.
+pub fn encode(bytes: &[u8], variant: Variant, unpadded: bool) -> Base64Response {
+    let padded = !unpadded;
+    let output = engine(variant, padded).encode(bytes);
+    let output = match variant {
+        Variant::Mime => wrap_lines(&output, MIME_LINE_LEN),
+        _ => output,
+    };
+    Base64Response {
+        bytes: output.clone().into_bytes(),
+        output,
+        variant,
+        padded,
+    }
+}
+
+impl Base64Response {
+    pub fn is_utf8(&self) -> bool {
+        std::str::from_utf8(&self.bytes).is_ok()
+    }
+}
+
+fn engine(variant: Variant, padded: bool) -> GeneralPurpose {
+    let config = GeneralPurposeConfig::new()
+        .with_encode_padding(padded)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent);
+    GeneralPurpose::new(variant.alphabet(), config)
+}
+
+/// Wraps the lines with CRLF, as required by MIME.
+fn wrap_lines(s: &str, width: usize) -> String {
+    // the encoded string is ASCII, so the chunks are always on character boundaries
+    s.as_bytes()
+        .chunks(width)
+        .map(|line| std::str::from_utf8(line).unwrap())
+        .collect::<Vec<_>>()
+        .join("\r\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case("hello", Mode::Encode, Ok("aGVsbG8="))]
+    #[case("", Mode::Encode, Ok(""))]
+    #[case("aGVsbG8=", Mode::Decode, Ok("hello"))]
+    #[case("aGVsbG8*", Mode::Decode, Err(Base64Error::InvalidInput))]
+    fn test_calculate(
+        #[case] input: &str,
+        #[case] mode: Mode,
+        #[case] expected: Result<&str, Base64Error>,
+    ) {
+        let req = Base64Request {
+            input,
+            mode,
+            ..Default::default()
+        };
+        let actual = calculate(&req).map(|res| res.output);
+        assert_eq!(actual, expected.map(String::from));
+    }
+
+    #[rstest]
+    #[case(Variant::Standard, false, "PDw/Pg==")]
+    #[case(Variant::Standard, true, "PDw/Pg")]
+    #[case(Variant::UrlSafe, false, "PDw_Pg==")]
+    #[case(Variant::UrlSafe, true, "PDw_Pg")]
+    #[case(Variant::Bcrypt, true, "NBu9Ne")]
+    #[case(Variant::Crypt, true, "D1kzDU")]
+    fn test_calculate_encode_variant(
+        #[case] variant: Variant,
+        #[case] unpadded: bool,
+        #[case] expected: &str,
+    ) {
+        let req = Base64Request {
+            input: "<<?>",
+            mode: Mode::Encode,
+            variant: Some(variant),
+            unpadded,
+        };
+        let res = calculate(&req).unwrap();
+        assert_eq!(res.output, expected);
+        assert_eq!(res.padded, !unpadded);
+    }
+
+    #[test]
+    fn test_calculate_encode_mime() {
+        let input = "a".repeat(100);
+        let req = Base64Request {
+            input: &input,
+            mode: Mode::Encode,
+            variant: Some(Variant::Mime),
+            ..Default::default()
+        };
+        let output = calculate(&req).unwrap().output;
+        let lines: Vec<&str> = output.split("\r\n").collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].len(), MIME_LINE_LEN);
+        assert!(lines[1].ends_with('='));
+    }
+
+    #[rstest]
+    #[case("aGVsbG8=", "hello", Variant::Standard, true)]
+    #[case("aGVsbG8", "hello", Variant::Standard, false)]
+    #[case(" aGVs\nbG8=\n", "hello", Variant::Standard, true)]
+    #[case(
+        "eyJhbGciOiJIUzI1NiJ9",
+        "{\"alg\":\"HS256\"}",
+        Variant::Standard,
+        false
+    )]
+    #[case("PDw_Pz4-", "<<??>>", Variant::UrlSafe, false)]
+    #[case("PDw/Pz4+", "<<??>>", Variant::Standard, false)]
+    fn test_calculate_decode_detect(
+        #[case] input: &str,
+        #[case] expected: &str,
+        #[case] variant: Variant,
+        #[case] padded: bool,
+    ) {
+        let req = Base64Request {
+            input,
+            mode: Mode::Decode,
+            ..Default::default()
+        };
+        let res = calculate(&req).unwrap();
+        assert_eq!(res.output, expected);
+        assert_eq!(res.variant, variant);
+        assert_eq!(res.padded, padded);
+    }
+
+    #[test]
+    fn test_calculate_decode_binary() {
+        let req = Base64Request {
+            input: "AP8=",
+            mode: Mode::Decode,
+            ..Default::default()
+        };
+        let res = calculate(&req).unwrap();
+        assert_eq!(res.bytes, vec![0x00, 0xff]);
+        assert_eq!(res.output, "\0\u{fffd}");
+        assert!(!res.is_utf8());
+    }
+
+    #[rstest]
+    #[case("aGVsbG8=", Variant::Standard)]
+    #[case("PDw_Pg", Variant::UrlSafe)]
+    #[case("$2b$12$R9h/cIPz0gi.URNNX3kh2O", Variant::Bcrypt)]
+    #[case("YWJj\nZGVm", Variant::Standard)]
+    fn test_detect_variant(#[case] input: &str, #[case] expected: Variant) {
+        assert_eq!(Variant::detect(input), expected);
+    }
+
+    #[test]
+    fn test_ambiguous_with() {
+        assert_eq!(Variant::Bcrypt.ambiguous_with(), Some(Variant::Crypt));
+        assert_eq!(Variant::UrlSafe.ambiguous_with(), None);
+    }
+
+    #[test]
+    fn test_calculate_decode_mime() {
+        let input = "a".repeat(100);
+        let encode = Base64Request {
+            input: &input,
+            mode: Mode::Encode,
+            variant: Some(Variant::Mime),
+            ..Default::default()
+        };
+        let encoded = calculate(&encode).unwrap().output;
+        let decode = Base64Request {
+            input: &encoded,
+            mode: Mode::Decode,
+            ..Default::default()
+        };
+        let res = calculate(&decode).unwrap();
+        assert_eq!(res.output, input);
+        assert_eq!(res.variant, Variant::Mime);
+    }
+}
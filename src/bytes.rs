@@ -0,0 +1,105 @@
+//! Text representations of binary data, for outputs that are not valid UTF-8.
+
+const DUMP_LINE_LEN: usize = 16;
+const ARRAY_LINE_LEN: usize = 12;
+
+/// Formats the bytes like `hexdump -C`: offset, 16 bytes in hex and their printable ASCII.
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let mut lines: Vec<String> = bytes
+        .chunks(DUMP_LINE_LEN)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let mut hex = String::new();
+            for j in 0..DUMP_LINE_LEN {
+                if j == DUMP_LINE_LEN / 2 {
+                    hex.push(' ');
+                }
+                match chunk.get(j) {
+                    Some(b) => hex.push_str(&format!("{b:02x} ")),
+                    None => hex.push_str("   "),
+                }
+            }
+            let ascii: String = chunk
+                .iter()
+                .map(|b| {
+                    if b.is_ascii_graphic() || *b == b' ' {
+                        *b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!("{:08x}  {hex} |{ascii}|", i * DUMP_LINE_LEN)
+        })
+        .collect();
+    lines.push(format!("{:08x}", bytes.len()));
+    lines.join("\n")
+}
+
+pub fn hex_string(bytes: &[u8]) -> String {
+    base16ct::lower::encode_string(bytes)
+}
+
+/// Formats the bytes as a C array definition named `data`.
+pub fn c_array(bytes: &[u8]) -> String {
+    format!(
+        "unsigned char data[{}] = {{\n{}\n}};",
+        bytes.len(),
+        array_items(bytes)
+    )
+}
+
+/// Formats the bytes as a Rust array constant named `DATA`.
+pub fn rust_array(bytes: &[u8]) -> String {
+    format!(
+        "const DATA: [u8; {}] = [\n{}\n];",
+        bytes.len(),
+        array_items(bytes)
+    )
+}
+
+fn array_items(bytes: &[u8]) -> String {
+    bytes
+        .chunks(ARRAY_LINE_LEN)
+        .map(|chunk| {
+            let items: Vec<String> = chunk.iter().map(|b| format!("0x{b:02x},")).collect();
+            format!("    {}", items.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_dump() {
+        let bytes = b"hello, world\n\x00\xff binary";
+        let expected = [
+            "00000000  68 65 6c 6c 6f 2c 20 77  6f 72 6c 64 0a 00 ff 20  |hello, world... |",
+            "00000010  62 69 6e 61 72 79                                 |binary|",
+            "00000016",
+        ];
+        assert_eq!(hex_dump(bytes), expected.join("\n"));
+        assert_eq!(hex_dump(b""), "00000000");
+    }
+
+    #[test]
+    fn test_hex_string() {
+        assert_eq!(hex_string(&[0x00, 0xab, 0xff]), "00abff");
+    }
+
+    #[test]
+    fn test_arrays() {
+        let bytes: Vec<u8> = (0..14).collect();
+        assert_eq!(
+            c_array(&bytes),
+            "unsigned char data[14] = {\n    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b,\n    0x0c, 0x0d,\n};"
+        );
+        assert_eq!(
+            rust_array(&[0xff]),
+            "const DATA: [u8; 1] = [\n    0xff,\n];"
+        );
+    }
+}
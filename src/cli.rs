@@ -0,0 +1,565 @@
+use std::{
+    io::{self, Read, Write},
+    process::ExitCode,
+};
+
+use btox::{
+    base64::{self, Base64Request},
+    hash::{self, HashAlgorithm, Hasher},
+    number::{self, Case, NumberRequest, Radix},
+    ulid::{self, UlidFormat, UlidRequest},
+    unixtime::{self, UnixTimeRequest, DEFAULT_OUTPUT_FORMAT},
+    url::{self, Charset, UrlRequest},
+    uuid::{self, UuidFormat, UuidRequest, UuidVersion},
+};
+use clap::{
+    builder::{PossibleValuesParser, RangedU64ValueParser, TypedValueParser},
+    Args, Parser, Subcommand, ValueEnum,
+};
+
+#[derive(Parser)]
+#[command(version, about = "my personal toolbox app")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Do not restore the previous session or save the session on quit
+    #[arg(long)]
+    pub no_session: bool,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Generate UUIDs
+    Uuid(UuidArgs),
+    /// Generate ULIDs
+    Ulid(UlidArgs),
+    /// Encode or decode Base64
+    Base64(Base64Args),
+    /// Encode or decode URL (percent-encoding)
+    Url(UrlArgs),
+    /// Calculate hash digest
+    Hash(HashArgs),
+    /// Convert between Unix timestamp and datetime
+    Unixtime(UnixTimeArgs),
+    /// Convert number base
+    Number(NumberArgs),
+}
+
+#[derive(Args)]
+pub struct UuidArgs {
+    /// Number of UUIDs to generate
+    #[arg(short = 'n', long, default_value_t = 1)]
+    count: usize,
+    /// Output in uppercase
+    #[arg(long)]
+    upper: bool,
+    /// Output without hyphens
+    #[arg(long)]
+    no_hyphen: bool,
+}
+
+#[derive(Args)]
+pub struct UlidArgs {
+    /// Number of ULIDs to generate
+    #[arg(short = 'n', long, default_value_t = 1)]
+    count: usize,
+    /// Output in lowercase
+    #[arg(long)]
+    lower: bool,
+    /// Generate strictly increasing ULIDs within the same millisecond
+    #[arg(long)]
+    monotonic: bool,
+}
+
+#[derive(Args)]
+pub struct Base64Args {
+    #[arg(value_enum, default_value_t = Mode::Encode)]
+    mode: Mode,
+    /// Alphabet and line wrapping (auto detects the variant on decode and is standard on encode)
+    #[arg(long, value_enum, default_value_t = Base64Variant::Auto)]
+    variant: Base64Variant,
+    /// Encode without the trailing `=`
+    #[arg(long)]
+    no_padding: bool,
+    /// Input string (read from stdin if omitted)
+    input: Option<String>,
+}
+
+#[derive(Args)]
+pub struct UrlArgs {
+    #[arg(value_enum, default_value_t = Mode::Encode)]
+    mode: Mode,
+    /// Input string (read from stdin if omitted)
+    input: Option<String>,
+}
+
+#[derive(Args)]
+pub struct HashArgs {
+    #[arg(value_parser = hash_algorithm_parser(), ignore_case = true)]
+    algorithm: HashAlgorithm,
+    /// Output length in bytes of shake128 and shake256 (1 - 65536)
+    #[arg(long, value_parser = RangedU64ValueParser::<usize>::new().range(1..=hash::XOF_OUTPUT_LEN_MAX as u64))]
+    length: Option<usize>,
+    /// HMAC key (compute HMAC instead of the plain digest if set)
+    #[arg(long)]
+    key: Option<String>,
+    /// Encoding of the HMAC key
+    #[arg(long, value_enum, default_value_t = KeyEncoding::Utf8)]
+    key_encoding: KeyEncoding,
+    /// How the input is turned into the bytes to hash
+    #[arg(long, value_enum, default_value_t = InputEncoding::Utf8)]
+    encoding: InputEncoding,
+    /// Remove a trailing newline of the input, e.g. of `echo`
+    #[arg(long)]
+    strip_newline: bool,
+    /// Output format of the digest
+    #[arg(long, value_enum, default_value_t = OutputFormat::Hex)]
+    format: OutputFormat,
+    /// Input string (read from stdin if omitted)
+    input: Option<String>,
+}
+
+#[derive(Args)]
+pub struct UnixTimeArgs {
+    /// Unix timestamp or RFC 3339 datetime (read from stdin if omitted)
+    input: Option<String>,
+    /// Time zone of the output datetime
+    #[arg(long, value_enum, default_value_t = TimeZone::Utc)]
+    tz: TimeZone,
+    /// Output datetime format (strftime)
+    #[arg(long, default_value = DEFAULT_OUTPUT_FORMAT)]
+    format: String,
+}
+
+#[derive(Args)]
+pub struct NumberArgs {
+    /// Base of the input number
+    #[arg(long, value_enum, default_value_t = Base::Dec)]
+    from: Base,
+    /// Base of the output number (print all bases if omitted)
+    #[arg(long, value_enum)]
+    to: Option<Base>,
+    /// Output hexadecimal in uppercase
+    #[arg(long)]
+    upper: bool,
+    /// Input number (read from stdin if omitted)
+    input: Option<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Mode {
+    Encode,
+    Decode,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Base64Variant {
+    Auto,
+    Standard,
+    UrlSafe,
+    Mime,
+    Bcrypt,
+    Crypt,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum InputEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+    Hex,
+    Base64,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Hex,
+    HexUpper,
+    Base64,
+    Base64Url,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum KeyEncoding {
+    Utf8,
+    Hex,
+    Base64,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum TimeZone {
+    Utc,
+    Local,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Base {
+    Bin,
+    Oct,
+    Dec,
+    Hex,
+}
+
+impl From<Mode> for base64::Mode {
+    fn from(mode: Mode) -> Self {
+        match mode {
+            Mode::Encode => base64::Mode::Encode,
+            Mode::Decode => base64::Mode::Decode,
+        }
+    }
+}
+
+impl From<Base64Variant> for Option<base64::Variant> {
+    fn from(variant: Base64Variant) -> Self {
+        match variant {
+            Base64Variant::Auto => None,
+            Base64Variant::Standard => Some(base64::Variant::Standard),
+            Base64Variant::UrlSafe => Some(base64::Variant::UrlSafe),
+            Base64Variant::Mime => Some(base64::Variant::Mime),
+            Base64Variant::Bcrypt => Some(base64::Variant::Bcrypt),
+            Base64Variant::Crypt => Some(base64::Variant::Crypt),
+        }
+    }
+}
+
+impl From<Mode> for url::Mode {
+    fn from(mode: Mode) -> Self {
+        match mode {
+            Mode::Encode => url::Mode::Encode,
+            Mode::Decode => url::Mode::Decode,
+        }
+    }
+}
+
+impl From<InputEncoding> for hash::InputEncoding {
+    fn from(encoding: InputEncoding) -> Self {
+        match encoding {
+            InputEncoding::Utf8 => hash::InputEncoding::Utf8,
+            InputEncoding::Utf16Le => hash::InputEncoding::Utf16Le,
+            InputEncoding::Utf16Be => hash::InputEncoding::Utf16Be,
+            InputEncoding::Latin1 => hash::InputEncoding::Latin1,
+            InputEncoding::Hex => hash::InputEncoding::Hex,
+            InputEncoding::Base64 => hash::InputEncoding::Base64,
+        }
+    }
+}
+
+impl From<OutputFormat> for hash::OutputFormat {
+    fn from(format: OutputFormat) -> Self {
+        match format {
+            OutputFormat::Hex => hash::OutputFormat::HexLower,
+            OutputFormat::HexUpper => hash::OutputFormat::HexUpper,
+            OutputFormat::Base64 => hash::OutputFormat::Base64,
+            OutputFormat::Base64Url => hash::OutputFormat::Base64Url,
+        }
+    }
+}
+
+impl From<KeyEncoding> for hash::KeyEncoding {
+    fn from(encoding: KeyEncoding) -> Self {
+        match encoding {
+            KeyEncoding::Utf8 => hash::KeyEncoding::Utf8,
+            KeyEncoding::Hex => hash::KeyEncoding::Hex,
+            KeyEncoding::Base64 => hash::KeyEncoding::Base64,
+        }
+    }
+}
+
+impl From<TimeZone> for unixtime::TimeZone {
+    fn from(tz: TimeZone) -> Self {
+        match tz {
+            TimeZone::Utc => unixtime::TimeZone::Utc,
+            TimeZone::Local => unixtime::TimeZone::Local,
+        }
+    }
+}
+
+impl From<Base> for Radix {
+    fn from(base: Base) -> Self {
+        match base {
+            Base::Bin => Radix::Binary,
+            Base::Oct => Radix::Octal,
+            Base::Dec => Radix::Decimal,
+            Base::Hex => Radix::Hexadecimal,
+        }
+    }
+}
+
+fn hash_algorithm_parser() -> impl TypedValueParser<Value = HashAlgorithm> {
+    PossibleValuesParser::new(HashAlgorithm::ALL.map(|algo| algo.name()))
+        .map(|s| s.parse::<HashAlgorithm>().unwrap())
+}
+
+pub fn run(command: Command) -> ExitCode {
+    match execute(command, &mut io::stdin()) {
+        // written as bytes since decoded output may not be UTF-8
+        Ok(output) => {
+            let mut stdout = io::stdout().lock();
+            match stdout.write_all(&output).and_then(|_| stdout.flush()) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(e) => {
+                    eprintln!("btox: failed to write stdout: {e}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Err(e) => {
+            eprintln!("btox: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn execute(command: Command, stdin: &mut impl Read) -> Result<Vec<u8>, String> {
+    match command {
+        Command::Uuid(args) => {
+            let req = UuidRequest {
+                version: UuidVersion::V4,
+                count: args.count,
+                ..Default::default()
+            };
+            let format = UuidFormat {
+                hyphenated: !args.no_hyphen,
+                uppercase: args.upper,
+            };
+            let ids = uuid::generate(&req);
+            Ok(lines(ids.iter().map(|id| uuid::format(id, format))))
+        }
+        Command::Ulid(args) => {
+            let req = UlidRequest {
+                count: args.count,
+                monotonic: args.monotonic,
+                ..Default::default()
+            };
+            let format = UlidFormat {
+                uppercase: !args.lower,
+            };
+            let ids = ulid::generate(&req).map_err(|e| e.to_string())?;
+            Ok(lines(ids.iter().map(|id| ulid::format(id, format))))
+        }
+        Command::Base64(args) => {
+            let input = bytes_or_stdin(args.input, stdin)?;
+            let variant: Option<base64::Variant> = args.variant.into();
+            let res = match args.mode {
+                // the bytes are encoded as is, so that binary data can be piped
+                Mode::Encode => {
+                    base64::encode(&input, variant.unwrap_or_default(), args.no_padding)
+                }
+                Mode::Decode => {
+                    // bytes that are not UTF-8 are not base64 either, and fail to decode
+                    let input = String::from_utf8_lossy(&input);
+                    let req = Base64Request {
+                        input: &input,
+                        mode: args.mode.into(),
+                        variant,
+                        unpadded: args.no_padding,
+                    };
+                    base64::calculate(&req).map_err(|e| e.to_string())?
+                }
+            };
+            Ok(with_newline(res.bytes, args.mode))
+        }
+        Command::Url(args) => {
+            let input = input_or_stdin(args.input, stdin)?;
+            let req = UrlRequest {
+                input: &input,
+                mode: args.mode.into(),
+                charset: Charset::Utf8,
+            };
+            let res = url::calculate(&req).map_err(|e| e.to_string())?;
+            Ok(with_newline(res.output.into_bytes(), args.mode))
+        }
+        Command::Hash(args) => {
+            let key = args
+                .key
+                .map(|key| hash::decode_key(&key, args.key_encoding.into()))
+                .transpose()
+                .map_err(|e| e.to_string())?;
+            let mut hasher = match &key {
+                Some(key) => Hasher::new_hmac(args.algorithm, args.length, key),
+                None => Hasher::new(args.algorithm, args.length),
+            };
+            let read_error = |e: io::Error| format!("failed to read stdin: {e}");
+            let digest = match (args.input, hash::InputEncoding::from(args.encoding)) {
+                // stdin is streamed into the hasher like files, other encodings need the whole text
+                (None, hash::InputEncoding::Utf8) if args.strip_newline => {
+                    hash::digest_reader_strip_newline(hasher, stdin).map_err(read_error)?
+                }
+                (None, hash::InputEncoding::Utf8) => {
+                    hash::digest_reader(hasher, stdin).map_err(read_error)?
+                }
+                (input, encoding) => {
+                    let input = bytes_or_stdin(input, stdin)?;
+                    let bytes = hash::encode_input_bytes(&input, encoding, args.strip_newline)
+                        .map_err(|e| e.to_string())?;
+                    hasher.update(&bytes);
+                    hasher.finalize()
+                }
+            };
+            Ok(lines([hash::format_digest(&digest, args.format.into())]))
+        }
+        Command::Unixtime(args) => {
+            let input = input_or_stdin(args.input, stdin)?;
+            let req = UnixTimeRequest {
+                input: input.trim(),
+                time_zone: args.tz.into(),
+                output_format: &args.format,
+            };
+            let res = unixtime::convert(&req).map_err(|e| e.to_string())?;
+            Ok(lines([res.output]))
+        }
+        Command::Number(args) => {
+            let input = input_or_stdin(args.input, stdin)?;
+            let req = NumberRequest {
+                input: input.trim(),
+                radix: args.from.into(),
+            };
+            let res = number::parse(&req).map_err(|e| e.to_string())?;
+            let case = if args.upper {
+                Case::Uppercase
+            } else {
+                Case::Lowercase
+            };
+            match args.to {
+                Some(base) => Ok(lines([res.format(base.into(), case)])),
+                None => Ok(lines([
+                    format!("bin: {}", res.format(Radix::Binary, case)),
+                    format!("oct: {}", res.format(Radix::Octal, case)),
+                    format!("dec: {}", res.format(Radix::Decimal, case)),
+                    format!("hex: {}", res.format(Radix::Hexadecimal, case)),
+                ])),
+            }
+        }
+    }
+}
+
+fn input_or_stdin(input: Option<String>, stdin: &mut impl Read) -> Result<String, String> {
+    match input {
+        Some(input) => Ok(input),
+        None => {
+            let mut buf = String::new();
+            stdin
+                .read_to_string(&mut buf)
+                .map_err(|e| format!("failed to read stdin: {e}"))?;
+            Ok(buf)
+        }
+    }
+}
+
+/// Reads the input as bytes, which may not be UTF-8 if read from stdin.
+fn bytes_or_stdin(input: Option<String>, stdin: &mut impl Read) -> Result<Vec<u8>, String> {
+    match input {
+        Some(input) => Ok(input.into_bytes()),
+        None => {
+            let mut buf = Vec::new();
+            stdin
+                .read_to_end(&mut buf)
+                .map_err(|e| format!("failed to read stdin: {e}"))?;
+            Ok(buf)
+        }
+    }
+}
+
+fn lines<I: IntoIterator<Item = String>>(lines: I) -> Vec<u8> {
+    lines
+        .into_iter()
+        .map(|line| line + "\n")
+        .collect::<String>()
+        .into_bytes()
+}
+
+// decoded output is written as is, so that it can be piped without modification
+fn with_newline(mut output: Vec<u8>, mode: Mode) -> Vec<u8> {
+    match mode {
+        Mode::Encode => {
+            output.push(b'\n');
+            output
+        }
+        Mode::Decode => output,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn execute_bytes(args: &[&str], stdin: &[u8]) -> Result<Vec<u8>, String> {
+        let cli = Cli::try_parse_from(args).unwrap();
+        execute(cli.command.unwrap(), &mut &stdin[..])
+    }
+
+    fn execute_args(args: &[&str], stdin: &str) -> Result<String, String> {
+        execute_bytes(args, stdin.as_bytes()).map(|output| String::from_utf8(output).unwrap())
+    }
+
+    #[rstest]
+    #[case(&["btox", "base64", "encode", "hello"], "", Ok("aGVsbG8=\n"))]
+    #[case(&["btox", "base64", "decode"], "aGVsbG8=", Ok("hello"))]
+    #[case(&["btox", "base64", "decode", "!"], "", Err("invalid base64 input"))]
+    #[case(&["btox", "base64", "encode", "--variant", "url-safe", "--no-padding", "<<?>"], "", Ok("PDw_Pg\n"))]
+    #[case(&["btox", "base64", "decode"], "PDw_Pg\n", Ok("<<?>"))]
+    #[case(&["btox", "url", "encode", "a b"], "", Ok("a%20b\n"))]
+    #[case(&["btox", "hash", "md5"], "", Ok("d41d8cd98f00b204e9800998ecf8427e\n"))]
+    #[case(&["btox", "hash", "shake128", "--length", "4", "abc"], "", Ok("5881092d\n"))]
+    #[case(&["btox", "hash", "sha256", "--key", "Jefe", "what do ya want for nothing?"], "", Ok("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843\n"))]
+    #[case(&["btox", "hash", "md5", "--key", "4a656665", "--key-encoding", "hex"], "what do ya want for nothing?", Ok("750c783e6ab0b503eaa86e310a5db738\n"))]
+    #[case(&["btox", "hash", "md5", "--key", "zz", "--key-encoding", "hex", "a"], "", Err("invalid HMAC key: invalid hex character 'z' at position 1"))]
+    #[case(&["btox", "hash", "md5", "--strip-newline"], "abc\n", Ok("900150983cd24fb0d6963f7d28e17f72\n"))]
+    #[case(&["btox", "hash", "md5", "--strip-newline"], "abc\r\n", Ok("900150983cd24fb0d6963f7d28e17f72\n"))]
+    #[case(&["btox", "hash", "md5", "--strip-newline", "abc\n"], "", Ok("900150983cd24fb0d6963f7d28e17f72\n"))]
+    #[case(&["btox", "hash", "md5", "--encoding", "hex", "--format", "base64"], "616263", Ok("kAFQmDzST7DWlj99KOF/cg==\n"))]
+    #[case(&["btox", "hash", "md5", "--encoding", "base64", "!"], "", Err("invalid input: invalid base64 input"))]
+    #[case(&["btox", "unixtime", "1634567890"], "", Ok("2021-10-18T14:38:10+00:00\n"))]
+    #[case(&["btox", "unixtime", "--format", "%Y"], "1634567890\n", Ok("2021\n"))]
+    #[case(&["btox", "unixtime", "2021-10-18T14:38:10Z"], "", Ok("1634567890\n"))]
+    #[case(&["btox", "number", "--from", "hex", "--to", "dec", "ff"], "", Ok("255\n"))]
+    #[case(&["btox", "number", "--from", "bin", "2"], "", Err("invalid binary number"))]
+    fn test_execute(
+        #[case] args: &[&str],
+        #[case] stdin: &str,
+        #[case] expected: Result<&str, &str>,
+    ) {
+        let actual = execute_args(args, stdin);
+        let expected = expected.map(String::from).map_err(String::from);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(&["btox", "base64", "encode"], b"\x00\xff\xfe".as_slice(), Ok(b"AP/+\n".as_slice()))]
+    #[case(&["btox", "base64", "decode", "AP8="], b"".as_slice(), Ok(b"\x00\xff".as_slice()))]
+    #[case(&["btox", "base64", "decode"], b"\xff".as_slice(), Err("invalid base64 input"))]
+    #[case(&["btox", "hash", "md5"], b"\xff".as_slice(), Ok(b"00594fd4f42ba43fc1ca0427a0576295\n".as_slice()))]
+    #[case(&["btox", "hash", "md5", "--strip-newline"], b"\xff\n".as_slice(), Ok(b"00594fd4f42ba43fc1ca0427a0576295\n".as_slice()))]
+    #[case(&["btox", "hash", "md5", "--encoding", "hex"], b"\xff".as_slice(), Err("invalid input: invalid utf-8 sequence of 1 bytes from index 0"))]
+    fn test_execute_binary(
+        #[case] args: &[&str],
+        #[case] stdin: &[u8],
+        #[case] expected: Result<&[u8], &str>,
+    ) {
+        let actual = execute_bytes(args, stdin);
+        let expected = expected.map(Vec::from).map_err(String::from);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("0", false)]
+    #[case("1", true)]
+    #[case("65536", true)]
+    #[case("65537", false)]
+    #[case("99999999999", false)]
+    fn test_parse_hash_length(#[case] length: &str, #[case] valid: bool) {
+        let args = ["btox", "hash", "shake128", "--length", length];
+        assert_eq!(Cli::try_parse_from(args).is_ok(), valid);
+    }
+
+    #[test]
+    fn test_execute_uuid() {
+        let output = execute_args(&["btox", "uuid", "-n", "3", "--upper"], "").unwrap();
+        let ids: Vec<&str> = output.lines().collect();
+        assert_eq!(ids.len(), 3);
+        assert!(ids.iter().all(|id| ::uuid::Uuid::parse_str(id).is_ok()));
+        assert!(ids.iter().all(|id| *id == id.to_uppercase()));
+    }
+}
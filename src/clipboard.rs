@@ -0,0 +1,247 @@
+use std::{
+    fmt,
+    io::{Read, Write},
+    process::{Command, Stdio},
+    sync::{Mutex, OnceLock},
+};
+
+use base64::Engine;
+use serde::Deserialize;
+
+use crate::config::ClipboardConfig;
+
+static CLIPBOARD: OnceLock<Clipboard> = OnceLock::new();
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ClipboardBackend {
+    /// System clipboard, falling back to OSC 52 and the internal register when it is not available.
+    #[default]
+    Auto,
+    Arboard,
+    /// Copy with the OSC 52 escape sequence, paste from the internal register.
+    Osc52,
+    Internal,
+    Command,
+}
+
+#[derive(Debug)]
+pub enum ClipboardError {
+    Arboard(arboard::Error),
+    Io(std::io::Error),
+    Command(String),
+    Empty,
+}
+
+impl fmt::Display for ClipboardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClipboardError::Arboard(e) => write!(f, "{e}"),
+            ClipboardError::Io(e) => write!(f, "{e}"),
+            ClipboardError::Command(msg) => write!(f, "{msg}"),
+            ClipboardError::Empty => write!(f, "clipboard is empty"),
+        }
+    }
+}
+
+impl std::error::Error for ClipboardError {}
+
+impl From<arboard::Error> for ClipboardError {
+    fn from(e: arboard::Error) -> Self {
+        ClipboardError::Arboard(e)
+    }
+}
+
+impl From<std::io::Error> for ClipboardError {
+    fn from(e: std::io::Error) -> Self {
+        ClipboardError::Io(e)
+    }
+}
+
+pub struct Clipboard {
+    backend: ClipboardBackend,
+    copy_command: Vec<String>,
+    paste_command: Vec<String>,
+    /// The last copied text, used when the system clipboard cannot be read.
+    register: Mutex<Option<String>>,
+}
+
+/// Sets up the clipboard used by `get`. Only the first call takes effect.
+pub fn init(config: &ClipboardConfig) {
+    let _ = CLIPBOARD.set(Clipboard::new(config));
+}
+
+pub fn get() -> &'static Clipboard {
+    CLIPBOARD.get_or_init(|| Clipboard::new(&ClipboardConfig::default()))
+}
+
+impl Clipboard {
+    fn new(config: &ClipboardConfig) -> Clipboard {
+        Clipboard {
+            backend: config.backend,
+            copy_command: config.copy_command.clone(),
+            paste_command: config.paste_command.clone(),
+            register: Mutex::new(None),
+        }
+    }
+
+    pub fn copy(&self, text: &str) -> Result<(), ClipboardError> {
+        *self.register.lock().unwrap() = Some(text.into());
+        match self.backend {
+            ClipboardBackend::Auto => copy_arboard(text).or_else(|_| copy_osc52(text)),
+            ClipboardBackend::Arboard => copy_arboard(text),
+            ClipboardBackend::Osc52 => copy_osc52(text),
+            ClipboardBackend::Internal => Ok(()),
+            ClipboardBackend::Command => copy_command(&self.copy_command, text),
+        }
+    }
+
+    pub fn paste(&self) -> Result<String, ClipboardError> {
+        match self.backend {
+            ClipboardBackend::Auto => paste_arboard().or_else(|_| self.paste_register()),
+            ClipboardBackend::Arboard => paste_arboard(),
+            ClipboardBackend::Osc52 | ClipboardBackend::Internal => self.paste_register(),
+            ClipboardBackend::Command => paste_command(&self.paste_command),
+        }
+    }
+
+    fn paste_register(&self) -> Result<String, ClipboardError> {
+        self.register
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or(ClipboardError::Empty)
+    }
+}
+
+fn copy_arboard(text: &str) -> Result<(), ClipboardError> {
+    arboard::Clipboard::new()?.set_text(text)?;
+    Ok(())
+}
+
+fn paste_arboard() -> Result<String, ClipboardError> {
+    let text = arboard::Clipboard::new()?.get_text()?;
+    Ok(text)
+}
+
+fn copy_osc52(text: &str) -> Result<(), ClipboardError> {
+    let mut stdout = std::io::stdout();
+    stdout.write_all(osc52_sequence(text).as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
+
+fn osc52_sequence(text: &str) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    format!("\x1b]52;c;{encoded}\x07")
+}
+
+fn copy_command(command: &[String], text: &str) -> Result<(), ClipboardError> {
+    // the output is not read, so it must not fill a pipe and block the program
+    let mut child = spawn(command, Stdio::piped(), Stdio::null())?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(ClipboardError::Command(format!(
+            "{} exited with {status}",
+            command[0]
+        )));
+    }
+    Ok(())
+}
+
+fn paste_command(command: &[String]) -> Result<String, ClipboardError> {
+    // nothing is written, so the program reading the input gets EOF instead of waiting
+    let mut child = spawn(command, Stdio::null(), Stdio::piped())?;
+    let mut buf = Vec::new();
+    if let Some(mut stdout) = child.stdout.take() {
+        stdout.read_to_end(&mut buf)?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(ClipboardError::Command(format!(
+            "{} exited with {status}",
+            command[0]
+        )));
+    }
+    String::from_utf8(buf)
+        .map_err(|_| ClipboardError::Command(format!("{} returned invalid UTF-8", command[0])))
+}
+
+fn spawn(
+    command: &[String],
+    stdin: Stdio,
+    stdout: Stdio,
+) -> Result<std::process::Child, ClipboardError> {
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| ClipboardError::Command("command is not configured".into()))?;
+    Command::new(program)
+        .args(args)
+        .stdin(stdin)
+        .stdout(stdout)
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| ClipboardError::Command(format!("failed to run {program}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_clipboard(backend: ClipboardBackend, copy: &[&str], paste: &[&str]) -> Clipboard {
+        let config = ClipboardConfig {
+            backend,
+            copy_command: copy.iter().map(|s| s.to_string()).collect(),
+            paste_command: paste.iter().map(|s| s.to_string()).collect(),
+        };
+        Clipboard::new(&config)
+    }
+
+    #[test]
+    fn test_internal() {
+        let clipboard = new_clipboard(ClipboardBackend::Internal, &[], &[]);
+        assert!(matches!(clipboard.paste(), Err(ClipboardError::Empty)));
+        clipboard.copy("foo").unwrap();
+        assert_eq!(clipboard.paste().unwrap(), "foo");
+    }
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("hello"), "\x1b]52;c;aGVsbG8=\x07");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command() {
+        let clipboard = new_clipboard(
+            ClipboardBackend::Command,
+            &["sh", "-c", "cat > /dev/null"],
+            &["printf", "bar"],
+        );
+        assert!(clipboard.copy("foo").is_ok());
+        assert_eq!(clipboard.paste().unwrap(), "bar");
+
+        // programs writing more than a pipe buffer on copy, or reading the input on paste
+        let clipboard = new_clipboard(
+            ClipboardBackend::Command,
+            &["sh", "-c", "cat > /dev/null; head -c 1000000 /dev/zero"],
+            &["sh", "-c", "cat; printf bar"],
+        );
+        assert!(clipboard.copy("foo").is_ok());
+        assert_eq!(clipboard.paste().unwrap(), "bar");
+
+        let clipboard = new_clipboard(
+            ClipboardBackend::Command,
+            &["sh", "-c", "cat > /dev/null; exit 1"],
+            &[],
+        );
+        assert!(matches!(
+            clipboard.copy("foo"),
+            Err(ClipboardError::Command(_))
+        ));
+        assert!(matches!(clipboard.paste(), Err(ClipboardError::Command(_))));
+    }
+}
@@ -0,0 +1,429 @@
+use std::{collections::HashMap, fmt, path::PathBuf};
+
+use btox::{
+    other_id::{nanoid_alphabet, NANOID_DEFAULT_LENGTH, NANOID_LENGTH_MAX, SNOWFLAKE_NODE_MAX},
+    unixtime::{is_valid_datetime_format, DEFAULT_OUTPUT_FORMAT},
+};
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use crate::{
+    clipboard::ClipboardBackend,
+    keymap::{Action, KeyChord, Keymap},
+    pages::{base64, encoding, hash, number, other_id, ulid, unixtime, url, uuid},
+    panes::list::PageType,
+    theme::ThemeName,
+};
+
+const APP_DIR_NAME: &str = "btox";
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub global: GlobalConfig,
+    pub uuid: UuidConfig,
+    pub ulid: UlidConfig,
+    pub base64: Base64Config,
+    pub encoding: EncodingConfig,
+    pub url: UrlConfig,
+    pub hash: HashConfig,
+    pub unixtime: UnixTimeConfig,
+    pub number: NumberConfig,
+    pub other_id: OtherIdConfig,
+    pub keybindings: HashMap<Action, Vec<KeyChord>>,
+    pub theme: ThemeConfig,
+    pub clipboard: ClipboardConfig,
+    pub session: SessionConfig,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GlobalConfig {
+    pub initial_page: PageType,
+    pub initial_focus: FocusTarget,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FocusTarget {
+    #[default]
+    List,
+    Tool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UuidConfig {
+    pub hyphen: uuid::HyphenItemSelect,
+    pub case: uuid::CaseItemSelect,
+    pub version: uuid::VersionItemSelect,
+    pub count: usize,
+}
+
+impl Default for UuidConfig {
+    fn default() -> Self {
+        UuidConfig {
+            hyphen: Default::default(),
+            case: Default::default(),
+            version: Default::default(),
+            count: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UlidConfig {
+    pub case: ulid::CaseItemSelect,
+    pub count: usize,
+    pub generation: ulid::GenerationItemSelect,
+}
+
+impl Default for UlidConfig {
+    fn default() -> Self {
+        UlidConfig {
+            case: Default::default(),
+            count: 1,
+            generation: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Base64Config {
+    pub mode: base64::EncodeOrDecodeSelect,
+    pub variant: base64::VariantSelect,
+    pub padding: base64::PaddingSelect,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EncodingConfig {
+    pub mode: encoding::EncodeOrDecodeSelect,
+    pub codec: encoding::CodecSelect,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UrlConfig {
+    pub mode: url::EncodeOrDecodeSelect,
+    pub charset: url::CharsetSelect,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HashConfig {
+    pub algorithm: hash::AlgoItemSelect,
+    pub encoding: hash::EncodeItemSelect,
+    pub format: hash::FormatItemSelect,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UnixTimeConfig {
+    pub time_zone: unixtime::TimeZoneItemSelect,
+    pub output_format: String,
+}
+
+impl Default for UnixTimeConfig {
+    fn default() -> Self {
+        UnixTimeConfig {
+            time_zone: Default::default(),
+            output_format: DEFAULT_OUTPUT_FORMAT.into(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NumberConfig {
+    pub case: number::CaseItemSelect,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OtherIdConfig {
+    pub kind: other_id::KindItemSelect,
+    pub count: usize,
+    pub nanoid_length: usize,
+    /// Characters of NanoIDs, the default URL-safe alphabet if empty.
+    pub nanoid_alphabet: String,
+    /// Snowflake epoch in milliseconds since the Unix epoch, the Twitter epoch if not set.
+    pub snowflake_epoch: Option<i64>,
+    pub snowflake_datacenter: u8,
+    pub snowflake_worker: u8,
+}
+
+impl Default for OtherIdConfig {
+    fn default() -> Self {
+        OtherIdConfig {
+            kind: Default::default(),
+            count: 1,
+            nanoid_length: NANOID_DEFAULT_LENGTH,
+            nanoid_alphabet: String::new(),
+            snowflake_epoch: None,
+            snowflake_datacenter: 0,
+            snowflake_worker: 0,
+        }
+    }
+}
+
+/// Built-in theme and colors overriding it, see `Theme`.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    pub name: ThemeName,
+    pub text: Option<Color>,
+    pub focused: Option<Color>,
+    pub disabled: Option<Color>,
+    pub selected: Option<Color>,
+    pub highlight: Option<Color>,
+    pub help: Option<Color>,
+    pub info: Option<Color>,
+    pub warn: Option<Color>,
+    pub error: Option<Color>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClipboardConfig {
+    pub backend: ClipboardBackend,
+    /// Program and arguments that read the text to copy from stdin, used by the `command` backend.
+    pub copy_command: Vec<String>,
+    /// Program and arguments that write the pasted text to stdout, used by the `command` backend.
+    pub paste_command: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SessionConfig {
+    pub enabled: bool,
+    /// Pages whose inputs and selections are not saved, e.g. `hash` for sensitive inputs.
+    pub exclude: Vec<PageType>,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        SessionConfig {
+            enabled: true,
+            exclude: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Read(PathBuf, std::io::Error),
+    Parse(PathBuf, toml::de::Error),
+    Invalid(PathBuf, String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Read(path, e) => {
+                write!(f, "failed to read config file {}: {e}", path.display())
+            }
+            ConfigError::Parse(path, e) => {
+                write!(f, "failed to parse config file {}: {e}", path.display())
+            }
+            ConfigError::Invalid(path, msg) => {
+                write!(f, "invalid config file {}: {msg}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Loads the config file from the XDG config directory.
+    /// Returns the default config if the file does not exist.
+    pub fn load() -> Result<Config, ConfigError> {
+        let Some(path) = config_file_path() else {
+            return Ok(Config::default());
+        };
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+        let content =
+            std::fs::read_to_string(&path).map_err(|e| ConfigError::Read(path.clone(), e))?;
+        let config: Config =
+            toml::from_str(&content).map_err(|e| ConfigError::Parse(path.clone(), e))?;
+        config
+            .validate()
+            .map_err(|msg| ConfigError::Invalid(path, msg))?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if !(1..=uuid::COUNT_MAX).contains(&self.uuid.count) {
+            let max = uuid::COUNT_MAX;
+            return Err(format!("uuid.count must be between 1 and {max}"));
+        }
+        if !(1..=ulid::COUNT_MAX).contains(&self.ulid.count) {
+            let max = ulid::COUNT_MAX;
+            return Err(format!("ulid.count must be between 1 and {max}"));
+        }
+        if !(1..=other_id::COUNT_MAX).contains(&self.other_id.count) {
+            let max = other_id::COUNT_MAX;
+            return Err(format!("other_id.count must be between 1 and {max}"));
+        }
+        if !(1..=NANOID_LENGTH_MAX).contains(&self.other_id.nanoid_length) {
+            return Err(format!(
+                "other_id.nanoid_length must be between 1 and {NANOID_LENGTH_MAX}"
+            ));
+        }
+        let alphabet = &self.other_id.nanoid_alphabet;
+        if !alphabet.is_empty() {
+            nanoid_alphabet(alphabet).map_err(|e| format!("other_id.nanoid_alphabet: {e}"))?;
+        }
+        if self.other_id.snowflake_datacenter > SNOWFLAKE_NODE_MAX
+            || self.other_id.snowflake_worker > SNOWFLAKE_NODE_MAX
+        {
+            return Err(format!(
+                "other_id.snowflake_datacenter and other_id.snowflake_worker must be between 0 and {SNOWFLAKE_NODE_MAX}"
+            ));
+        }
+        if !is_valid_datetime_format(&self.unixtime.output_format) {
+            return Err("unixtime.output_format is not a valid strftime format".into());
+        }
+        if self.clipboard.backend == ClipboardBackend::Command
+            && (self.clipboard.copy_command.is_empty() || self.clipboard.paste_command.is_empty())
+        {
+            return Err(
+                "clipboard.copy_command and clipboard.paste_command are required for the command backend"
+                    .into(),
+            );
+        }
+        Keymap::new(&self.keybindings)
+            .validate()
+            .map_err(|msg| format!("keybindings: {msg}"))?;
+        Ok(())
+    }
+}
+
+fn config_file_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))?;
+    Some(config_dir.join(APP_DIR_NAME).join(CONFIG_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let content = r#"
+            [global]
+            initial_page = "hash"
+            initial_focus = "tool"
+
+            [uuid]
+            case = "uppercase"
+            count = 10
+
+            [encoding]
+            codec = "base32-hex"
+
+            [hash]
+            algorithm = "sha512-256"
+
+            [unixtime]
+            time_zone = "local"
+            output_format = "%Y-%m-%d"
+
+            [other_id]
+            kind = "snowflake"
+            snowflake_epoch = 1420070400000
+            snowflake_worker = 3
+
+            [session]
+            exclude = ["hash"]
+        "#;
+        let config: Config = toml::from_str(content).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.global.initial_page, PageType::Hash);
+        assert_eq!(config.global.initial_focus, FocusTarget::Tool);
+        assert_eq!(config.uuid.case, uuid::CaseItemSelect::Uppercase);
+        assert_eq!(config.uuid.hyphen, uuid::HyphenItemSelect::WithHyphen);
+        assert_eq!(config.uuid.count, 10);
+        assert_eq!(config.ulid.count, 1);
+        assert_eq!(config.encoding.codec, encoding::CodecSelect::Base32Hex);
+        assert_eq!(config.hash.algorithm, hash::AlgoItemSelect::Sha512_256);
+        assert_eq!(
+            config.unixtime.time_zone,
+            unixtime::TimeZoneItemSelect::Local
+        );
+        assert_eq!(config.unixtime.output_format, "%Y-%m-%d");
+        assert_eq!(config.other_id.kind, other_id::KindItemSelect::Snowflake);
+        assert_eq!(config.other_id.snowflake_epoch, Some(1_420_070_400_000));
+        assert_eq!(config.other_id.snowflake_worker, 3);
+        assert_eq!(config.other_id.nanoid_length, 21);
+        assert!(config.keybindings.is_empty());
+        assert!(config.session.enabled);
+        assert_eq!(config.session.exclude, vec![PageType::Hash]);
+    }
+
+    #[test]
+    fn test_parse_keybindings() {
+        let content = r#"
+            [keybindings]
+            select_next_item = ["ctrl-n", "down"]
+            select_prev_item = ["ctrl-p", "up"]
+            command_palette = [":"]
+            cancel = ["ctrl-g"]
+        "#;
+        let config: Config = toml::from_str(content).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.keybindings[&Action::SelectNextItem],
+            vec!["ctrl-n".parse().unwrap(), "down".parse().unwrap()]
+        );
+
+        let unknown_action = "[keybindings]\nfoo = [\"x\"]";
+        assert!(toml::from_str::<Config>(unknown_action).is_err());
+
+        let invalid_key = "[keybindings]\ncopy = [\"ctrl-\"]";
+        assert!(toml::from_str::<Config>(invalid_key).is_err());
+
+        let conflict: Config = toml::from_str("[keybindings]\ncopy = [\"j\"]").unwrap();
+        assert!(conflict.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_config() {
+        let unknown_field = "[uuid]\nupper = true";
+        assert!(toml::from_str::<Config>(unknown_field).is_err());
+
+        let unknown_value = "[hash]\nalgorithm = \"sha3\"";
+        assert!(toml::from_str::<Config>(unknown_value).is_err());
+
+        let count_out_of_range: Config = toml::from_str("[ulid]\ncount = 0").unwrap();
+        assert!(count_out_of_range.validate().is_err());
+
+        let duplicate_alphabet: Config =
+            toml::from_str("[other_id]\nnanoid_alphabet = \"abca\"").unwrap();
+        assert_eq!(
+            duplicate_alphabet.validate(),
+            Err("other_id.nanoid_alphabet: alphabet must have 2 to 256 distinct characters".into())
+        );
+        let short_alphabet: Config = toml::from_str("[other_id]\nnanoid_alphabet = \"a\"").unwrap();
+        assert!(short_alphabet.validate().is_err());
+
+        let worker_out_of_range: Config =
+            toml::from_str("[other_id]\nsnowflake_worker = 32").unwrap();
+        assert!(worker_out_of_range.validate().is_err());
+
+        let missing_command: Config = toml::from_str("[clipboard]\nbackend = \"command\"").unwrap();
+        assert!(missing_command.validate().is_err());
+
+        let invalid_format: Config = toml::from_str("[unixtime]\noutput_format = \"%\"").unwrap();
+        assert!(invalid_format.validate().is_err());
+    }
+}
@@ -0,0 +1,506 @@
+//! Binary-to-text codecs other than Base64.
+
+use std::fmt;
+
+use crate::other_id::encode_base;
+
+const BASE32_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE32HEX_ALPHABET: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUV";
+const CROCKFORD_ALPHABET: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const BASE58_BITCOIN_ALPHABET: &[u8] =
+    b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE58_FLICKR_ALPHABET: &[u8] = b"123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ";
+const Z85_ALPHABET: &[u8] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Encode,
+    Decode,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    #[default]
+    HexLower,
+    HexUpper,
+    /// RFC 4648 Base32, as used by TOTP secrets.
+    Base32,
+    /// RFC 4648 Base32 with the extended hex alphabet.
+    Base32Hex,
+    /// Douglas Crockford's Base32, which has no padding and decodes `O`, `I` and `L` as digits.
+    Crockford,
+    Base58Bitcoin,
+    Base58Flickr,
+    /// ZeroMQ's Base85, which encodes data in multiples of 4 bytes.
+    Z85,
+    /// Adobe's Base85, decoded with or without the `<~` and `~>` delimiters.
+    Ascii85,
+}
+
+impl fmt::Display for Codec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Codec::HexLower | Codec::HexUpper => "hex",
+            Codec::Base32 => "Base32",
+            Codec::Base32Hex => "base32hex",
+            Codec::Crockford => "Crockford Base32",
+            Codec::Base58Bitcoin | Codec::Base58Flickr => "Base58",
+            Codec::Z85 => "Z85",
+            Codec::Ascii85 => "Ascii85",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodingError {
+    /// The character is not in the alphabet, `position` is 1-based and counts whitespace.
+    InvalidCharacter {
+        codec: Codec,
+        c: char,
+        position: usize,
+    },
+    InvalidLength(Codec),
+    /// A group of Base85 characters that exceeds 32 bits.
+    InvalidGroup(Codec, String),
+    /// Z85 can only encode data in multiples of 4 bytes.
+    UnalignedData(usize),
+}
+
+impl fmt::Display for EncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodingError::InvalidCharacter { codec, c, position } => {
+                write!(f, "invalid {codec} character {c:?} at position {position}")
+            }
+            EncodingError::InvalidLength(codec) => write!(f, "invalid {codec} input length"),
+            EncodingError::InvalidGroup(codec, group) => {
+                write!(f, "invalid {codec} group {group:?}")
+            }
+            EncodingError::UnalignedData(len) => {
+                write!(f, "Z85 input must be a multiple of 4 bytes, got {len}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EncodingError {}
+
+#[derive(Debug, Default, Clone)]
+pub struct EncodingRequest<'a> {
+    pub input: &'a str,
+    pub mode: Mode,
+    pub codec: Codec,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodingResponse {
+    /// The output as text, decoded bytes that are not valid UTF-8 are replaced with U+FFFD.
+    pub output: String,
+    /// The output as is, which is the decoded data on decode.
+    pub bytes: Vec<u8>,
+}
+
+impl EncodingResponse {
+    pub fn is_utf8(&self) -> bool {
+        std::str::from_utf8(&self.bytes).is_ok()
+    }
+}
+
+pub fn calculate(req: &EncodingRequest) -> Result<EncodingResponse, EncodingError> {
+    match req.mode {
+        Mode::Encode => {
+            let output = encode(req.input.as_bytes(), req.codec)?;
+            Ok(EncodingResponse {
+                bytes: output.clone().into_bytes(),
+                output,
+            })
+        }
+        Mode::Decode => {
+            let bytes = decode(req.input, req.codec)?;
+            Ok(EncodingResponse {
+                output: String::from_utf8_lossy(&bytes).into_owned(),
+                bytes,
+            })
+        }
+    }
+}
+
+pub fn encode(bytes: &[u8], codec: Codec) -> Result<String, EncodingError> {
+    let output = match codec {
+        Codec::HexLower => base16ct::lower::encode_string(bytes),
+        Codec::HexUpper => base16ct::upper::encode_string(bytes),
+        Codec::Base32 => encode_base32(bytes, BASE32_ALPHABET, true),
+        Codec::Base32Hex => encode_base32(bytes, BASE32HEX_ALPHABET, true),
+        Codec::Crockford => encode_base32(bytes, CROCKFORD_ALPHABET, false),
+        Codec::Base58Bitcoin => encode_base58(bytes, BASE58_BITCOIN_ALPHABET),
+        Codec::Base58Flickr => encode_base58(bytes, BASE58_FLICKR_ALPHABET),
+        Codec::Z85 => encode_z85(bytes)?,
+        Codec::Ascii85 => encode_ascii85(bytes),
+    };
+    Ok(output)
+}
+
+/// Decodes the input, ignoring whitespace.
+pub fn decode(input: &str, codec: Codec) -> Result<Vec<u8>, EncodingError> {
+    let chars: Vec<(usize, char)> = input
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| !c.is_whitespace())
+        .map(|(i, c)| (i + 1, c))
+        .collect();
+    match codec {
+        Codec::HexLower | Codec::HexUpper => decode_hex(&chars),
+        Codec::Base32 => decode_base32(&chars, codec, BASE32_ALPHABET),
+        Codec::Base32Hex => decode_base32(&chars, codec, BASE32HEX_ALPHABET),
+        Codec::Crockford => decode_base32(&chars, codec, CROCKFORD_ALPHABET),
+        Codec::Base58Bitcoin => decode_base58(&chars, codec, BASE58_BITCOIN_ALPHABET),
+        Codec::Base58Flickr => decode_base58(&chars, codec, BASE58_FLICKR_ALPHABET),
+        Codec::Z85 => decode_z85(&chars),
+        Codec::Ascii85 => decode_ascii85(&chars),
+    }
+}
+
+/// Returns the value of the character in the alphabet, or an error pointing at it.
+fn digit(
+    codec: Codec,
+    alphabet: &[u8],
+    (position, c): (usize, char),
+) -> Result<u32, EncodingError> {
+    alphabet
+        .iter()
+        .position(|a| *a as char == c)
+        .map(|d| d as u32)
+        .ok_or(EncodingError::InvalidCharacter { codec, c, position })
+}
+
+fn decode_hex(chars: &[(usize, char)]) -> Result<Vec<u8>, EncodingError> {
+    if !chars.len().is_multiple_of(2) {
+        return Err(EncodingError::InvalidLength(Codec::HexLower));
+    }
+    let digits = chars
+        .iter()
+        .map(|(position, c)| {
+            c.to_digit(16).ok_or(EncodingError::InvalidCharacter {
+                codec: Codec::HexLower,
+                c: *c,
+                position: *position,
+            })
+        })
+        .collect::<Result<Vec<u32>, _>>()?;
+    Ok(digits
+        .chunks(2)
+        .map(|pair| (pair[0] << 4 | pair[1]) as u8)
+        .collect())
+}
+
+fn encode_base32(bytes: &[u8], alphabet: &[u8], padded: bool) -> String {
+    let mut output = String::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for b in bytes {
+        buffer = buffer << 8 | *b as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            output.push(alphabet[(buffer >> bits & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        output.push(alphabet[(buffer << (5 - bits) & 0x1f) as usize] as char);
+    }
+    if padded {
+        while !output.len().is_multiple_of(8) {
+            output.push('=');
+        }
+    }
+    output
+}
+
+/// Decodes Base32 with or without padding, letters in any case.
+/// Crockford also ignores `-` and reads `O` as `0` and `I` and `L` as `1`.
+fn decode_base32(
+    chars: &[(usize, char)],
+    codec: Codec,
+    alphabet: &[u8],
+) -> Result<Vec<u8>, EncodingError> {
+    let mut chars: Vec<(usize, char)> = chars.to_vec();
+    if codec == Codec::Crockford {
+        chars.retain(|(_, c)| *c != '-');
+    } else {
+        while chars.last().is_some_and(|(_, c)| *c == '=') {
+            chars.pop();
+        }
+    }
+    // 1, 3 and 6 characters can not be the end of a whole number of bytes
+    if matches!(chars.len() % 8, 1 | 3 | 6) {
+        return Err(EncodingError::InvalidLength(codec));
+    }
+
+    let mut output = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for (position, c) in chars {
+        let c = match (codec, c.to_ascii_uppercase()) {
+            (Codec::Crockford, 'O') => '0',
+            (Codec::Crockford, 'I' | 'L') => '1',
+            (_, c) => c,
+        };
+        buffer = buffer << 5 | digit(codec, alphabet, (position, c))?;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(output)
+}
+
+/// Encodes each leading zero byte as the first character and the rest as a big number.
+fn encode_base58(bytes: &[u8], alphabet: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|b| **b == 0).count();
+    let mut output = (alphabet[0] as char).to_string().repeat(zeros);
+    if zeros < bytes.len() {
+        output.push_str(&encode_base(&bytes[zeros..], alphabet));
+    }
+    output
+}
+
+fn decode_base58(
+    chars: &[(usize, char)],
+    codec: Codec,
+    alphabet: &[u8],
+) -> Result<Vec<u8>, EncodingError> {
+    let zeros = chars
+        .iter()
+        .take_while(|(_, c)| *c == alphabet[0] as char)
+        .count();
+    // little-endian bytes of the number
+    let mut number: Vec<u8> = Vec::new();
+    for c in &chars[zeros..] {
+        let mut carry = digit(codec, alphabet, *c)?;
+        for b in number.iter_mut() {
+            let acc = *b as u32 * 58 + carry;
+            *b = acc as u8;
+            carry = acc >> 8;
+        }
+        while carry > 0 {
+            number.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    let mut output = vec![0; zeros];
+    output.extend(number.iter().rev());
+    Ok(output)
+}
+
+/// Encodes a 32-bit big-endian group as 5 digits of base 85.
+fn encode_group(value: u32) -> [u8; 5] {
+    let mut digits = [0u8; 5];
+    let mut value = value;
+    for d in digits.iter_mut().rev() {
+        *d = (value % 85) as u8;
+        value /= 85;
+    }
+    digits
+}
+
+/// Decodes 5 digits of base 85, `None` if they exceed 32 bits.
+fn decode_group(digits: &[u32]) -> Option<u32> {
+    let value = digits.iter().fold(0u64, |acc, d| acc * 85 + *d as u64);
+    u32::try_from(value).ok()
+}
+
+fn encode_z85(bytes: &[u8]) -> Result<String, EncodingError> {
+    if !bytes.len().is_multiple_of(4) {
+        return Err(EncodingError::UnalignedData(bytes.len()));
+    }
+    Ok(bytes
+        .chunks(4)
+        .flat_map(|chunk| encode_group(u32::from_be_bytes(chunk.try_into().unwrap())))
+        .map(|d| Z85_ALPHABET[d as usize] as char)
+        .collect())
+}
+
+fn decode_z85(chars: &[(usize, char)]) -> Result<Vec<u8>, EncodingError> {
+    if !chars.len().is_multiple_of(5) {
+        return Err(EncodingError::InvalidLength(Codec::Z85));
+    }
+    let mut output = Vec::new();
+    for group in chars.chunks(5) {
+        let digits = group
+            .iter()
+            .map(|c| digit(Codec::Z85, Z85_ALPHABET, *c))
+            .collect::<Result<Vec<u32>, _>>()?;
+        let value = decode_group(&digits).ok_or_else(|| {
+            EncodingError::InvalidGroup(Codec::Z85, group.iter().map(|(_, c)| c).collect())
+        })?;
+        output.extend(value.to_be_bytes());
+    }
+    Ok(output)
+}
+
+/// Encodes without the delimiters, with `z` for groups of zeros as Adobe does.
+fn encode_ascii85(bytes: &[u8]) -> String {
+    let mut output = String::new();
+    for chunk in bytes.chunks(4) {
+        if chunk == [0; 4] {
+            output.push('z');
+            continue;
+        }
+        // a partial group is padded with zeros and output without the padding
+        let mut group = [0u8; 4];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let digits = encode_group(u32::from_be_bytes(group));
+        output.extend(digits[..chunk.len() + 1].iter().map(|d| (d + b'!') as char));
+    }
+    output
+}
+
+fn decode_ascii85(chars: &[(usize, char)]) -> Result<Vec<u8>, EncodingError> {
+    let mut chars = chars;
+    if let [(_, '<'), (_, '~'), rest @ ..] = chars {
+        chars = rest;
+    }
+    if let [rest @ .., (_, '~'), (_, '>')] = chars {
+        chars = rest;
+    }
+
+    let mut output = Vec::new();
+    let mut group: Vec<(usize, char)> = Vec::with_capacity(5);
+    let decode = |group: &[(usize, char)], output: &mut Vec<u8>| {
+        // a partial group is padded with the largest digit and output without the padding
+        let mut digits: Vec<u32> = group.iter().map(|(_, c)| *c as u32 - '!' as u32).collect();
+        digits.resize(5, 84);
+        let value = decode_group(&digits).ok_or_else(|| {
+            EncodingError::InvalidGroup(Codec::Ascii85, group.iter().map(|(_, c)| c).collect())
+        })?;
+        output.extend(&value.to_be_bytes()[..group.len() - 1]);
+        Ok(())
+    };
+    for (position, c) in chars {
+        match c {
+            'z' if group.is_empty() => output.extend([0; 4]),
+            '!'..='u' => {
+                group.push((*position, *c));
+                if group.len() == 5 {
+                    decode(&group, &mut output)?;
+                    group.clear();
+                }
+            }
+            _ => {
+                return Err(EncodingError::InvalidCharacter {
+                    codec: Codec::Ascii85,
+                    c: *c,
+                    position: *position,
+                })
+            }
+        }
+    }
+    match group.len() {
+        0 => {}
+        1 => return Err(EncodingError::InvalidLength(Codec::Ascii85)),
+        _ => decode(&group, &mut output)?,
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(Codec::HexLower, "hello", "68656c6c6f")]
+    #[case(Codec::HexUpper, "\u{ff}", "C3BF")]
+    #[case(Codec::Base32, "foobar", "MZXW6YTBOI======")]
+    #[case(Codec::Base32, "hello", "NBSWY3DP")]
+    #[case(Codec::Base32Hex, "foobar", "CPNMUOJ1E8======")]
+    #[case(Codec::Crockford, "foobar", "CSQPYRK1E8")]
+    #[case(Codec::Base58Bitcoin, "hello world", "StV1DL6CwTryKyV")]
+    #[case(Codec::Base58Flickr, "hello world", "rTu1dk6cWsRYjYu")]
+    #[case(Codec::Z85, "", "")]
+    #[case(Codec::Ascii85, "hello world", "BOu!rD]j7BEbo7")]
+    #[case(Codec::Ascii85, "\0\0\0\0a", "z@/")]
+    fn test_calculate_encode(#[case] codec: Codec, #[case] input: &str, #[case] expected: &str) {
+        let req = EncodingRequest {
+            input,
+            mode: Mode::Encode,
+            codec,
+        };
+        let actual = calculate(&req).map(|res| res.output);
+        assert_eq!(actual, Ok(expected.into()));
+    }
+
+    #[rstest]
+    #[case(Codec::HexLower, "68 65 6C 6c 6f", b"hello".to_vec())]
+    #[case(Codec::Base32, "MZXW6YTBOI======", b"foobar".to_vec())]
+    #[case(Codec::Base32, "mzxw6ytboi", b"foobar".to_vec())]
+    #[case(Codec::Base32Hex, "CPNMUOJ1E8", b"foobar".to_vec())]
+    #[case(Codec::Crockford, "csqp-yrki-e8", b"foobar".to_vec())]
+    #[case(Codec::Base58Bitcoin, "StV1DL6CwTryKyV", b"hello world".to_vec())]
+    #[case(Codec::Base58Bitcoin, "11", vec![0, 0])]
+    #[case(Codec::Base58Flickr, "rTu1dk6cWsRYjYu", b"hello world".to_vec())]
+    #[case(Codec::Z85, "HelloWorld", vec![0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b])]
+    #[case(Codec::Ascii85, "<~BOu!rD]j7BEbo7~>", b"hello world".to_vec())]
+    #[case(Codec::Ascii85, "z@/", b"\0\0\0\0a".to_vec())]
+    fn test_calculate_decode(#[case] codec: Codec, #[case] input: &str, #[case] expected: Vec<u8>) {
+        let req = EncodingRequest {
+            input,
+            mode: Mode::Decode,
+            codec,
+        };
+        let actual = calculate(&req).map(|res| res.bytes);
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[rstest]
+    #[case(Codec::HexLower, "abc", EncodingError::InvalidLength(Codec::HexLower))]
+    #[case(Codec::HexLower, "ag", EncodingError::InvalidCharacter { codec: Codec::HexLower, c: 'g', position: 2 })]
+    #[case(Codec::Base32, "MZX", EncodingError::InvalidLength(Codec::Base32))]
+    #[case(Codec::Base32, "MZ1W", EncodingError::InvalidCharacter { codec: Codec::Base32, c: '1', position: 3 })]
+    #[case(Codec::Crockford, "CU", EncodingError::InvalidCharacter { codec: Codec::Crockford, c: 'U', position: 2 })]
+    #[case(Codec::Base58Bitcoin, "1 0", EncodingError::InvalidCharacter { codec: Codec::Base58Bitcoin, c: '0', position: 3 })]
+    #[case(Codec::Z85, "Hell", EncodingError::InvalidLength(Codec::Z85))]
+    #[case(Codec::Z85, "#####", EncodingError::InvalidGroup(Codec::Z85, "#####".into()))]
+    #[case(Codec::Ascii85, "B", EncodingError::InvalidLength(Codec::Ascii85))]
+    #[case(Codec::Ascii85, "Bz", EncodingError::InvalidCharacter { codec: Codec::Ascii85, c: 'z', position: 2 })]
+    fn test_calculate_decode_error(
+        #[case] codec: Codec,
+        #[case] input: &str,
+        #[case] expected: EncodingError,
+    ) {
+        let req = EncodingRequest {
+            input,
+            mode: Mode::Decode,
+            codec,
+        };
+        let actual = calculate(&req).map(|res| res.bytes);
+        assert_eq!(actual, Err(expected));
+    }
+
+    #[test]
+    fn test_calculate_z85_unaligned() {
+        let req = EncodingRequest {
+            input: "hello",
+            mode: Mode::Encode,
+            codec: Codec::Z85,
+        };
+        let actual = calculate(&req).map(|res| res.output);
+        assert_eq!(actual, Err(EncodingError::UnalignedData(5)));
+    }
+
+    #[rstest]
+    #[case(Codec::Base32)]
+    #[case(Codec::Crockford)]
+    #[case(Codec::Base58Flickr)]
+    #[case(Codec::Z85)]
+    #[case(Codec::Ascii85)]
+    fn test_round_trip(#[case] codec: Codec) {
+        let bytes: Vec<u8> = (0..=255).rev().chain(0..4).collect();
+        let encoded = encode(&bytes, codec).unwrap();
+        assert_eq!(decode(&encoded, codec), Ok(bytes));
+    }
+}
@@ -1,15 +1,36 @@
-use std::{sync::mpsc, thread};
+use std::{
+    sync::{mpsc, OnceLock},
+    thread,
+};
 
 use ratatui::crossterm::event::Event;
 
-pub fn new() -> (mpsc::Sender<Event>, mpsc::Receiver<Event>) {
+use crate::msg::Msg;
+
+static SENDER: OnceLock<mpsc::Sender<AppEvent>> = OnceLock::new();
+
+pub enum AppEvent {
+    Terminal(Event),
+    /// A message from a background task, e.g. the progress of loading a file.
+    Msg(Msg),
+}
+
+pub fn new() -> (mpsc::Sender<AppEvent>, mpsc::Receiver<AppEvent>) {
     let (tx, rx) = mpsc::channel();
+    let _ = SENDER.set(tx.clone());
 
     let event_tx = tx.clone();
     thread::spawn(move || loop {
         let e = ratatui::crossterm::event::read().unwrap();
-        event_tx.send(e).unwrap();
+        event_tx.send(AppEvent::Terminal(e)).unwrap();
     });
 
     (tx, rx)
 }
+
+/// Sends the message to the app from another thread, it is dropped if the app is not running.
+pub fn send(msg: Msg) {
+    if let Some(tx) = SENDER.get() {
+        let _ = tx.send(AppEvent::Msg(msg));
+    }
+}
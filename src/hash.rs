@@ -0,0 +1,901 @@
+use std::{fmt, io::Read, str::FromStr};
+
+use blake2::{Blake2b512, Blake2s256};
+use crc::{Crc, CRC_32_ISCSI, CRC_32_ISO_HDLC, CRC_64_XZ};
+use md5::{Digest, Md5};
+use ripemd::Ripemd160;
+use sha1::Sha1;
+use sha2::{digest::DynDigest, Sha224, Sha256, Sha384, Sha512, Sha512_224, Sha512_256};
+use sha3::{
+    digest::{ExtendableOutput, Update},
+    Keccak256, Sha3_224, Sha3_256, Sha3_384, Sha3_512, Shake128, Shake256,
+};
+use xxhash_rust::{xxh3::Xxh3, xxh32::Xxh32, xxh64::Xxh64};
+
+use crate::{
+    base64::{self, Base64Request, Variant},
+    encoding::{self, Codec},
+    manifest,
+};
+
+/// Size of the chunks read from a reader at once.
+const READ_CHUNK_LEN: usize = 64 * 1024;
+
+/// Maximum output length in bytes of SHAKE128 and SHAKE256.
+pub const XOF_OUTPUT_LEN_MAX: usize = 64 * 1024;
+
+static CRC32: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+static CRC32C: Crc<u32> = Crc::<u32>::new(&CRC_32_ISCSI);
+static CRC64: Crc<u64> = Crc::<u64>::new(&CRC_64_XZ);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512_224,
+    Sha512_256,
+    Sha512,
+    Sha3_224,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    Keccak256,
+    Shake128,
+    Shake256,
+    Blake2b,
+    Blake2s,
+    Blake3,
+    Ripemd160,
+    Crc32,
+    Crc32c,
+    Crc64,
+    Adler32,
+    Xxh32,
+    Xxh64,
+    Xxh3,
+}
+
+impl HashAlgorithm {
+    pub const ALL: [HashAlgorithm; 26] = [
+        HashAlgorithm::Md5,
+        HashAlgorithm::Sha1,
+        HashAlgorithm::Sha224,
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Sha384,
+        HashAlgorithm::Sha512_224,
+        HashAlgorithm::Sha512_256,
+        HashAlgorithm::Sha512,
+        HashAlgorithm::Sha3_224,
+        HashAlgorithm::Sha3_256,
+        HashAlgorithm::Sha3_384,
+        HashAlgorithm::Sha3_512,
+        HashAlgorithm::Keccak256,
+        HashAlgorithm::Shake128,
+        HashAlgorithm::Shake256,
+        HashAlgorithm::Blake2b,
+        HashAlgorithm::Blake2s,
+        HashAlgorithm::Blake3,
+        HashAlgorithm::Ripemd160,
+        HashAlgorithm::Crc32,
+        HashAlgorithm::Crc32c,
+        HashAlgorithm::Crc64,
+        HashAlgorithm::Adler32,
+        HashAlgorithm::Xxh32,
+        HashAlgorithm::Xxh64,
+        HashAlgorithm::Xxh3,
+    ];
+
+    /// Returns the identifier used to select the algorithm by name, e.g. `sha512-256`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Md5 => "md5",
+            HashAlgorithm::Sha1 => "sha1",
+            HashAlgorithm::Sha224 => "sha224",
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Sha384 => "sha384",
+            HashAlgorithm::Sha512_224 => "sha512-224",
+            HashAlgorithm::Sha512_256 => "sha512-256",
+            HashAlgorithm::Sha512 => "sha512",
+            HashAlgorithm::Sha3_224 => "sha3-224",
+            HashAlgorithm::Sha3_256 => "sha3-256",
+            HashAlgorithm::Sha3_384 => "sha3-384",
+            HashAlgorithm::Sha3_512 => "sha3-512",
+            HashAlgorithm::Keccak256 => "keccak256",
+            HashAlgorithm::Shake128 => "shake128",
+            HashAlgorithm::Shake256 => "shake256",
+            HashAlgorithm::Blake2b => "blake2b",
+            HashAlgorithm::Blake2s => "blake2s",
+            HashAlgorithm::Blake3 => "blake3",
+            HashAlgorithm::Ripemd160 => "ripemd160",
+            HashAlgorithm::Crc32 => "crc32",
+            HashAlgorithm::Crc32c => "crc32c",
+            HashAlgorithm::Crc64 => "crc64",
+            HashAlgorithm::Adler32 => "adler32",
+            HashAlgorithm::Xxh32 => "xxh32",
+            HashAlgorithm::Xxh64 => "xxh64",
+            HashAlgorithm::Xxh3 => "xxh3",
+        }
+    }
+
+    /// Returns whether the output length can be chosen, i.e. SHAKE128 and SHAKE256.
+    pub fn is_xof(&self) -> bool {
+        matches!(self, HashAlgorithm::Shake128 | HashAlgorithm::Shake256)
+    }
+
+    /// Returns the output length in bytes, the default one for extendable-output functions.
+    pub fn output_len(&self) -> usize {
+        match self {
+            HashAlgorithm::Crc32
+            | HashAlgorithm::Crc32c
+            | HashAlgorithm::Adler32
+            | HashAlgorithm::Xxh32 => 4,
+            HashAlgorithm::Crc64 | HashAlgorithm::Xxh64 | HashAlgorithm::Xxh3 => 8,
+            HashAlgorithm::Md5 => 16,
+            HashAlgorithm::Sha1 | HashAlgorithm::Ripemd160 => 20,
+            HashAlgorithm::Sha224 | HashAlgorithm::Sha512_224 | HashAlgorithm::Sha3_224 => 28,
+            HashAlgorithm::Sha256
+            | HashAlgorithm::Sha512_256
+            | HashAlgorithm::Sha3_256
+            | HashAlgorithm::Keccak256
+            | HashAlgorithm::Shake128
+            | HashAlgorithm::Blake2s
+            | HashAlgorithm::Blake3 => 32,
+            HashAlgorithm::Sha384 | HashAlgorithm::Sha3_384 => 48,
+            HashAlgorithm::Sha512
+            | HashAlgorithm::Sha3_512
+            | HashAlgorithm::Shake256
+            | HashAlgorithm::Blake2b => 64,
+        }
+    }
+
+    /// Returns the block size in bytes that HMAC pads the key to, which is the rate for SHA-3.
+    /// The checksums have no blocks, so HMAC uses 64 bytes for them.
+    pub fn block_len(&self) -> usize {
+        match self {
+            HashAlgorithm::Sha384
+            | HashAlgorithm::Sha512_224
+            | HashAlgorithm::Sha512_256
+            | HashAlgorithm::Sha512
+            | HashAlgorithm::Blake2b => 128,
+            HashAlgorithm::Sha3_224 => 144,
+            HashAlgorithm::Sha3_256 | HashAlgorithm::Keccak256 | HashAlgorithm::Shake256 => 136,
+            HashAlgorithm::Sha3_384 => 104,
+            HashAlgorithm::Sha3_512 => 72,
+            HashAlgorithm::Shake128 => 168,
+            HashAlgorithm::Md5
+            | HashAlgorithm::Sha1
+            | HashAlgorithm::Sha224
+            | HashAlgorithm::Sha256
+            | HashAlgorithm::Blake2s
+            | HashAlgorithm::Blake3
+            | HashAlgorithm::Ripemd160
+            | HashAlgorithm::Crc32
+            | HashAlgorithm::Crc32c
+            | HashAlgorithm::Crc64
+            | HashAlgorithm::Adler32
+            | HashAlgorithm::Xxh32
+            | HashAlgorithm::Xxh64
+            | HashAlgorithm::Xxh3 => 64,
+        }
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = HashError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        HashAlgorithm::ALL
+            .into_iter()
+            .find(|algo| algo.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| HashError::UnknownAlgorithm(s.into()))
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InputEncoding {
+    #[default]
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// ISO-8859-1, characters above U+00FF can not be encoded.
+    Latin1,
+    /// The bytes decoded from hex, whitespace is ignored.
+    Hex,
+    /// The bytes decoded from base64 of any variant, whitespace is ignored.
+    Base64,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    HexLower,
+    HexUpper,
+    Base64,
+    /// URL-safe base64 without padding, as used by JWT.
+    Base64Url,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum KeyEncoding {
+    #[default]
+    Utf8,
+    Hex,
+    Base64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HashError {
+    UnknownAlgorithm(String),
+    /// The HMAC key could not be decoded, with the reason.
+    InvalidKey(String),
+    /// The expected digest is neither hex nor a `sha256sum` line.
+    InvalidExpected,
+    /// The input could not be encoded or decoded, with the reason.
+    InvalidInput(String),
+}
+
+impl fmt::Display for HashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashError::UnknownAlgorithm(name) => write!(f, "unknown hash algorithm: {name}"),
+            HashError::InvalidKey(reason) => write!(f, "invalid HMAC key: {reason}"),
+            HashError::InvalidExpected => write!(f, "expected digest is not hex"),
+            HashError::InvalidInput(reason) => write!(f, "invalid input: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for HashError {}
+
+#[derive(Debug, Clone)]
+pub struct HashRequest<'a> {
+    pub input: &'a str,
+    pub algorithm: HashAlgorithm,
+    pub encoding: InputEncoding,
+    /// Removes a trailing `\n` or `\r\n` of the input before it is encoded.
+    pub strip_newline: bool,
+    /// Output length in bytes of extendable-output functions, their default if `None`.
+    pub output_len: Option<usize>,
+    /// Computes HMAC with the key instead of the plain digest if set.
+    pub key: Option<&'a [u8]>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashResponse {
+    pub digest: Vec<u8>,
+}
+
+impl HashResponse {
+    pub fn to_hex(&self) -> String {
+        let mut buf = vec![0u8; self.digest.len() * 2];
+        base16ct::lower::encode_str(&self.digest, &mut buf)
+            .unwrap()
+            .to_string()
+    }
+
+    pub fn format(&self, format: OutputFormat) -> String {
+        format_digest(&self.digest, format)
+    }
+}
+
+pub fn calculate(req: &HashRequest) -> Result<HashResponse, HashError> {
+    let input_bytes = encode_input(req.input, req.encoding, req.strip_newline)?;
+    let mut hasher = match req.key {
+        Some(key) => Hasher::new_hmac(req.algorithm, req.output_len, key),
+        None => Hasher::new(req.algorithm, req.output_len),
+    };
+    hasher.update(&input_bytes);
+    Ok(HashResponse {
+        digest: hasher.finalize(),
+    })
+}
+
+/// Returns the bytes raw input, e.g. read from stdin, is hashed as.
+/// UTF-8 input is hashed as is, even if it is not valid UTF-8 such as binary data.
+pub fn encode_input_bytes(
+    input: &[u8],
+    encoding: InputEncoding,
+    strip_newline: bool,
+) -> Result<Vec<u8>, HashError> {
+    let input = match input.strip_suffix(b"\n") {
+        Some(stripped) if strip_newline => stripped.strip_suffix(b"\r").unwrap_or(stripped),
+        _ => input,
+    };
+    match encoding {
+        InputEncoding::Utf8 => Ok(input.to_vec()),
+        _ => {
+            let input =
+                std::str::from_utf8(input).map_err(|e| HashError::InvalidInput(e.to_string()))?;
+            encode_input(input, encoding, false)
+        }
+    }
+}
+
+/// Returns the bytes the input is hashed as.
+pub fn encode_input(
+    input: &str,
+    encoding: InputEncoding,
+    strip_newline: bool,
+) -> Result<Vec<u8>, HashError> {
+    let input = match input.strip_suffix('\n') {
+        Some(stripped) if strip_newline => stripped.strip_suffix('\r').unwrap_or(stripped),
+        _ => input,
+    };
+    let invalid = |e: &dyn fmt::Display| HashError::InvalidInput(e.to_string());
+    match encoding {
+        InputEncoding::Utf8 => Ok(input.as_bytes().to_vec()),
+        InputEncoding::Utf16Le => Ok(input.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+        InputEncoding::Utf16Be => Ok(input.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+        InputEncoding::Latin1 => input
+            .chars()
+            .map(|c| u8::try_from(c).map_err(|_| invalid(&format!("{c:?} is not in Latin-1"))))
+            .collect(),
+        InputEncoding::Hex => decode_hex(input).map_err(|e| invalid(&e)),
+        InputEncoding::Base64 => decode_base64(input).map_err(|e| invalid(&e)),
+    }
+}
+
+pub fn format_digest(digest: &[u8], format: OutputFormat) -> String {
+    match format {
+        OutputFormat::HexLower => base16ct::lower::encode_string(digest),
+        OutputFormat::HexUpper => base16ct::upper::encode_string(digest),
+        OutputFormat::Base64 => base64::encode(digest, Variant::Standard, false).output,
+        OutputFormat::Base64Url => base64::encode(digest, Variant::UrlSafe, true).output,
+    }
+}
+
+pub fn digest(algorithm: HashAlgorithm, bytes: &[u8]) -> Vec<u8> {
+    digest_with_len(algorithm, None, bytes)
+}
+
+fn digest_with_len(algorithm: HashAlgorithm, output_len: Option<usize>, bytes: &[u8]) -> Vec<u8> {
+    let mut hasher = Hasher::new(algorithm, output_len);
+    hasher.update(bytes);
+    hasher.finalize()
+}
+
+/// Decodes the HMAC key, hex and base64 keys may contain whitespace.
+pub fn decode_key(key: &str, encoding: KeyEncoding) -> Result<Vec<u8>, HashError> {
+    match encoding {
+        KeyEncoding::Utf8 => Ok(key.as_bytes().to_vec()),
+        KeyEncoding::Hex => decode_hex(key).map_err(|e| HashError::InvalidKey(e.to_string())),
+        KeyEncoding::Base64 => decode_base64(key).map_err(|e| HashError::InvalidKey(e.to_string())),
+    }
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, encoding::EncodingError> {
+    encoding::decode(s, Codec::HexLower)
+}
+
+fn decode_base64(s: &str) -> Result<Vec<u8>, base64::Base64Error> {
+    let req = Base64Request {
+        input: s,
+        mode: base64::Mode::Decode,
+        ..Default::default()
+    };
+    base64::calculate(&req).map(|res| res.bytes)
+}
+
+/// A digest to compare the output with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedDigest {
+    /// Lowercase hex without whitespace.
+    pub hex: String,
+    /// The file name if given as a `sha256sum` line.
+    pub file: Option<String>,
+}
+
+impl ExpectedDigest {
+    /// Returns the length of the digest in bytes.
+    pub fn len(&self) -> usize {
+        self.hex.len() / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hex.is_empty()
+    }
+
+    pub fn matches(&self, hex: &str) -> bool {
+        self.hex.eq_ignore_ascii_case(hex)
+    }
+}
+
+/// Parses a hex digest, ignoring case and whitespace, or a `<hex>  <file>` line of `sha256sum`
+/// and the like, whose file name may be marked binary with `*`.
+pub fn parse_expected(s: &str) -> Result<ExpectedDigest, HashError> {
+    let compact: String = s.split_whitespace().collect();
+    if !compact.is_empty() && compact.len().is_multiple_of(2) && is_hex_digits(&compact) {
+        return Ok(ExpectedDigest {
+            hex: compact.to_ascii_lowercase(),
+            file: None,
+        });
+    }
+
+    let line = s.lines().find(|l| !l.trim().is_empty()).unwrap_or_default();
+    let entry = manifest::parse_line(line).ok_or(HashError::InvalidExpected)?;
+    Ok(ExpectedDigest {
+        hex: entry.hex,
+        file: Some(entry.path),
+    })
+}
+
+pub(crate) fn is_hex_digits(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Returns the algorithms with the default output length in bytes, in the order of `ALL`.
+pub fn algorithms_with_len(len: usize) -> Vec<HashAlgorithm> {
+    HashAlgorithm::ALL
+        .into_iter()
+        .filter(|algorithm| !algorithm.is_xof() && algorithm.output_len() == len)
+        .collect()
+}
+
+/// Computes the digest of everything read from the reader, without holding it in memory at once.
+pub fn digest_reader<R: Read + ?Sized>(hasher: Hasher, reader: &mut R) -> std::io::Result<Vec<u8>> {
+    let mut digests = digest_reader_all(vec![hasher], reader)?;
+    Ok(digests.remove(0))
+}
+
+/// Computes the digest like [`digest_reader`], without a trailing `\n` or `\r\n` of the input.
+pub fn digest_reader_strip_newline<R: Read + ?Sized>(
+    mut hasher: Hasher,
+    reader: &mut R,
+) -> std::io::Result<Vec<u8>> {
+    let mut buf = vec![0u8; READ_CHUNK_LEN];
+    // the last 2 bytes read are held back until it is known whether they end the input
+    let mut held: Vec<u8> = Vec::with_capacity(2);
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => {
+                let flush = (held.len() + n).saturating_sub(2);
+                let from_held = flush.min(held.len());
+                hasher.update(&held[..from_held]);
+                hasher.update(&buf[..flush - from_held]);
+                held.drain(..from_held);
+                held.extend_from_slice(&buf[flush - from_held..n]);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    let rest = match held.strip_suffix(b"\n") {
+        Some(stripped) => stripped.strip_suffix(b"\r").unwrap_or(stripped),
+        None => &held,
+    };
+    hasher.update(rest);
+    Ok(hasher.finalize())
+}
+
+/// Computes the digests of all the hashers, reading the reader only once.
+pub fn digest_reader_all<R: Read + ?Sized>(
+    mut hashers: Vec<Hasher>,
+    reader: &mut R,
+) -> std::io::Result<Vec<Vec<u8>>> {
+    let mut buf = vec![0u8; READ_CHUNK_LEN];
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => hashers
+                .iter_mut()
+                .for_each(|hasher| hasher.update(&buf[..n])),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(hashers.into_iter().map(Hasher::finalize).collect())
+}
+
+/// Incremental state of any of the algorithms, checksums are output in big-endian.
+pub struct Hasher {
+    state: HasherState,
+    hmac: Option<HmacOuter>,
+}
+
+/// The outer hash of HMAC, computed over the inner digest on finalize.
+struct HmacOuter {
+    algorithm: HashAlgorithm,
+    output_len: Option<usize>,
+    /// The padded key XORed with opad.
+    key: Vec<u8>,
+}
+
+enum HasherState {
+    Digest(Box<dyn DynDigest + Send>),
+    Shake128(Shake128, usize),
+    Shake256(Shake256, usize),
+    Blake3(Box<blake3::Hasher>),
+    Crc32(crc::Digest<'static, u32>),
+    Crc64(crc::Digest<'static, u64>),
+    Adler32(adler2::Adler32),
+    Xxh32(Xxh32),
+    Xxh64(Xxh64),
+    Xxh3(Box<Xxh3>),
+}
+
+impl Hasher {
+    /// `output_len` is only used by extendable-output functions.
+    pub fn new(algorithm: HashAlgorithm, output_len: Option<usize>) -> Hasher {
+        let output_len = output_len.unwrap_or(algorithm.output_len());
+        let state = match algorithm {
+            HashAlgorithm::Md5 => HasherState::Digest(Box::new(Md5::new())),
+            HashAlgorithm::Sha1 => HasherState::Digest(Box::new(Sha1::new())),
+            HashAlgorithm::Sha224 => HasherState::Digest(Box::new(Sha224::new())),
+            HashAlgorithm::Sha256 => HasherState::Digest(Box::new(Sha256::new())),
+            HashAlgorithm::Sha384 => HasherState::Digest(Box::new(Sha384::new())),
+            HashAlgorithm::Sha512_224 => HasherState::Digest(Box::new(Sha512_224::new())),
+            HashAlgorithm::Sha512_256 => HasherState::Digest(Box::new(Sha512_256::new())),
+            HashAlgorithm::Sha512 => HasherState::Digest(Box::new(Sha512::new())),
+            HashAlgorithm::Sha3_224 => HasherState::Digest(Box::new(Sha3_224::new())),
+            HashAlgorithm::Sha3_256 => HasherState::Digest(Box::new(Sha3_256::new())),
+            HashAlgorithm::Sha3_384 => HasherState::Digest(Box::new(Sha3_384::new())),
+            HashAlgorithm::Sha3_512 => HasherState::Digest(Box::new(Sha3_512::new())),
+            HashAlgorithm::Keccak256 => HasherState::Digest(Box::new(Keccak256::new())),
+            HashAlgorithm::Shake128 => HasherState::Shake128(Shake128::default(), output_len),
+            HashAlgorithm::Shake256 => HasherState::Shake256(Shake256::default(), output_len),
+            HashAlgorithm::Blake2b => HasherState::Digest(Box::new(Blake2b512::new())),
+            HashAlgorithm::Blake2s => HasherState::Digest(Box::new(Blake2s256::new())),
+            HashAlgorithm::Blake3 => HasherState::Blake3(Box::new(blake3::Hasher::new())),
+            HashAlgorithm::Ripemd160 => HasherState::Digest(Box::new(Ripemd160::new())),
+            HashAlgorithm::Crc32 => HasherState::Crc32(CRC32.digest()),
+            HashAlgorithm::Crc32c => HasherState::Crc32(CRC32C.digest()),
+            HashAlgorithm::Crc64 => HasherState::Crc64(CRC64.digest()),
+            HashAlgorithm::Adler32 => HasherState::Adler32(adler2::Adler32::new()),
+            HashAlgorithm::Xxh32 => HasherState::Xxh32(Xxh32::new(0)),
+            HashAlgorithm::Xxh64 => HasherState::Xxh64(Xxh64::new(0)),
+            HashAlgorithm::Xxh3 => HasherState::Xxh3(Box::new(Xxh3::new())),
+        };
+        Hasher { state, hmac: None }
+    }
+
+    /// Computes HMAC (RFC 2104) with the algorithm as the underlying hash.
+    pub fn new_hmac(algorithm: HashAlgorithm, output_len: Option<usize>, key: &[u8]) -> Hasher {
+        let block_len = algorithm.block_len();
+        let mut padded_key = if key.len() > block_len {
+            digest_with_len(algorithm, output_len, key)
+        } else {
+            key.to_vec()
+        };
+        padded_key.resize(block_len, 0);
+
+        let inner_key: Vec<u8> = padded_key.iter().map(|b| b ^ 0x36).collect();
+        let outer_key: Vec<u8> = padded_key.iter().map(|b| b ^ 0x5c).collect();
+        let mut hasher = Hasher::new(algorithm, output_len);
+        hasher.update(&inner_key);
+        hasher.hmac = Some(HmacOuter {
+            algorithm,
+            output_len,
+            key: outer_key,
+        });
+        hasher
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        match &mut self.state {
+            HasherState::Digest(hasher) => hasher.update(bytes),
+            HasherState::Shake128(hasher, _) => Update::update(hasher, bytes),
+            HasherState::Shake256(hasher, _) => Update::update(hasher, bytes),
+            HasherState::Blake3(hasher) => {
+                hasher.update(bytes);
+            }
+            HasherState::Crc32(hasher) => hasher.update(bytes),
+            HasherState::Crc64(hasher) => hasher.update(bytes),
+            HasherState::Adler32(hasher) => hasher.write_slice(bytes),
+            HasherState::Xxh32(hasher) => hasher.update(bytes),
+            HasherState::Xxh64(hasher) => hasher.update(bytes),
+            HasherState::Xxh3(hasher) => hasher.update(bytes),
+        }
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        let digest = match self.state {
+            HasherState::Digest(hasher) => hasher.finalize().to_vec(),
+            HasherState::Shake128(hasher, len) => hasher.finalize_boxed(len).to_vec(),
+            HasherState::Shake256(hasher, len) => hasher.finalize_boxed(len).to_vec(),
+            HasherState::Blake3(hasher) => hasher.finalize().as_bytes().to_vec(),
+            HasherState::Crc32(hasher) => hasher.finalize().to_be_bytes().to_vec(),
+            HasherState::Crc64(hasher) => hasher.finalize().to_be_bytes().to_vec(),
+            HasherState::Adler32(hasher) => hasher.checksum().to_be_bytes().to_vec(),
+            HasherState::Xxh32(hasher) => hasher.digest().to_be_bytes().to_vec(),
+            HasherState::Xxh64(hasher) => hasher.digest().to_be_bytes().to_vec(),
+            HasherState::Xxh3(hasher) => hasher.digest().to_be_bytes().to_vec(),
+        };
+        match self.hmac {
+            Some(outer) => {
+                let mut hasher = Hasher::new(outer.algorithm, outer.output_len);
+                hasher.update(&outer.key);
+                hasher.update(&digest);
+                hasher.finalize()
+            }
+            None => digest,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(HashAlgorithm::Md5, "900150983cd24fb0d6963f7d28e17f72")]
+    #[case(HashAlgorithm::Sha1, "a9993e364706816aba3e25717850c26c9cd0d89d")]
+    #[case(
+        HashAlgorithm::Sha256,
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    )]
+    #[case(
+        HashAlgorithm::Sha3_256,
+        "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+    )]
+    #[case(
+        HashAlgorithm::Keccak256,
+        "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+    )]
+    #[case(
+        HashAlgorithm::Shake128,
+        "5881092dd818bf5cf8a3ddb793fbcba74097d5c526a6d35f97b83351940f2cc8"
+    )]
+    #[case(
+        HashAlgorithm::Blake2s,
+        "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982"
+    )]
+    #[case(
+        HashAlgorithm::Blake3,
+        "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+    )]
+    #[case(HashAlgorithm::Ripemd160, "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc")]
+    #[case(HashAlgorithm::Crc32, "352441c2")]
+    #[case(HashAlgorithm::Crc32c, "364b3fb7")]
+    #[case(HashAlgorithm::Crc64, "2cd8094a1a277627")]
+    #[case(HashAlgorithm::Adler32, "024d0127")]
+    #[case(HashAlgorithm::Xxh32, "32d153ff")]
+    #[case(HashAlgorithm::Xxh64, "44bc2cf5ad770999")]
+    #[case(HashAlgorithm::Xxh3, "78af5f94892f3950")]
+    fn test_calculate(#[case] algorithm: HashAlgorithm, #[case] expected: &str) {
+        let req = HashRequest {
+            input: "abc",
+            algorithm,
+            encoding: InputEncoding::Utf8,
+            strip_newline: false,
+            output_len: None,
+            key: None,
+        };
+        assert_eq!(calculate(&req).unwrap().to_hex(), expected);
+    }
+
+    #[rstest]
+    #[case(HashAlgorithm::Shake256, Some(16), "483366601360a8771c6863080cc4114d")]
+    #[case(HashAlgorithm::Shake256, None, "483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739d5a15bef186a5386c75744c0527e1faa9f8726e462a12a4feb06bd8801e751e4")]
+    #[case(
+        HashAlgorithm::Sha1,
+        Some(4),
+        "a9993e364706816aba3e25717850c26c9cd0d89d"
+    )]
+    fn test_calculate_output_len(
+        #[case] algorithm: HashAlgorithm,
+        #[case] output_len: Option<usize>,
+        #[case] expected: &str,
+    ) {
+        let req = HashRequest {
+            input: "abc",
+            algorithm,
+            encoding: InputEncoding::Utf8,
+            strip_newline: false,
+            output_len,
+            key: None,
+        };
+        assert_eq!(calculate(&req).unwrap().to_hex(), expected);
+    }
+
+    #[rstest]
+    #[case("héllo", InputEncoding::Utf8, false, Ok("68c3a96c6c6f"))]
+    #[case("hé\n", InputEncoding::Utf8, true, Ok("68c3a9"))]
+    #[case("hé\r\n", InputEncoding::Latin1, true, Ok("68e9"))]
+    #[case("hé\n\n", InputEncoding::Latin1, true, Ok("68e90a"))]
+    #[case("hé\n", InputEncoding::Utf16Le, false, Ok("6800e9000a00"))]
+    #[case("h€", InputEncoding::Utf16Be, false, Ok("006820ac"))]
+    #[case(
+        "h€",
+        InputEncoding::Latin1,
+        false,
+        Err("invalid input: '€' is not in Latin-1")
+    )]
+    #[case("61 62 63\n", InputEncoding::Hex, true, Ok("616263"))]
+    #[case("YWJj\n", InputEncoding::Base64, false, Ok("616263"))]
+    #[case(
+        "6g",
+        InputEncoding::Hex,
+        false,
+        Err("invalid input: invalid hex character 'g' at position 2")
+    )]
+    fn test_encode_input(
+        #[case] input: &str,
+        #[case] encoding: InputEncoding,
+        #[case] strip_newline: bool,
+        #[case] expected: Result<&str, &str>,
+    ) {
+        let actual = encode_input(input, encoding, strip_newline)
+            .map(|bytes| base16ct::lower::encode_string(&bytes))
+            .map_err(|e| e.to_string());
+        let expected = expected.map(String::from).map_err(String::from);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(OutputFormat::HexLower, "fb8f01")]
+    #[case(OutputFormat::HexUpper, "FB8F01")]
+    #[case(OutputFormat::Base64, "+48B")]
+    #[case(OutputFormat::Base64Url, "-48B")]
+    fn test_format_digest(#[case] format: OutputFormat, #[case] expected: &str) {
+        assert_eq!(format_digest(&[0xfb, 0x8f, 0x01], format), expected);
+    }
+
+    #[rstest]
+    #[case("900150983CD24FB0D6963F7D28E17F72", Ok(("900150983cd24fb0d6963f7d28e17f72", None)))]
+    #[case(" 9001 5098\n3cd2 4fb0 d696 3f7d 28e1 7f72\n", Ok(("900150983cd24fb0d6963f7d28e17f72", None)))]
+    #[case("900150983cd24fb0d6963f7d28e17f72  btox-0.1.0.tar.gz\n", Ok(("900150983cd24fb0d6963f7d28e17f72", Some("btox-0.1.0.tar.gz"))))]
+    #[case("900150983cd24fb0d6963f7d28e17f72 *my file.bin", Ok(("900150983cd24fb0d6963f7d28e17f72", Some("my file.bin"))))]
+    #[case("90015", Err(HashError::InvalidExpected))]
+    #[case("sha256:9001", Err(HashError::InvalidExpected))]
+    #[case("", Err(HashError::InvalidExpected))]
+    fn test_parse_expected(
+        #[case] input: &str,
+        #[case] expected: Result<(&str, Option<&str>), HashError>,
+    ) {
+        let expected = expected.map(|(hex, file)| ExpectedDigest {
+            hex: hex.into(),
+            file: file.map(Into::into),
+        });
+        assert_eq!(parse_expected(input), expected);
+    }
+
+    #[test]
+    fn test_algorithms_with_len() {
+        use HashAlgorithm::*;
+        assert_eq!(algorithms_with_len(16), vec![Md5]);
+        assert_eq!(algorithms_with_len(20), vec![Sha1, Ripemd160]);
+        assert_eq!(
+            algorithms_with_len(32),
+            vec![Sha256, Sha512_256, Sha3_256, Keccak256, Blake2s, Blake3]
+        );
+        assert_eq!(algorithms_with_len(4), vec![Crc32, Crc32c, Adler32, Xxh32]);
+        assert_eq!(algorithms_with_len(3), vec![]);
+    }
+
+    #[test]
+    fn test_digest_reader() {
+        let bytes: Vec<u8> = (0..READ_CHUNK_LEN * 2 + 1).map(|i| i as u8).collect();
+        for algorithm in HashAlgorithm::ALL {
+            let actual =
+                digest_reader(Hasher::new(algorithm, None), &mut bytes.as_slice()).unwrap();
+            assert_eq!(actual, digest(algorithm, &bytes), "{algorithm}");
+        }
+
+        let hashers = HashAlgorithm::ALL.map(|algorithm| Hasher::new(algorithm, None));
+        let actual = digest_reader_all(hashers.into(), &mut bytes.as_slice()).unwrap();
+        let expected: Vec<_> = HashAlgorithm::ALL
+            .map(|algorithm| digest(algorithm, &bytes))
+            .into();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(b"abc", b"", "abc")]
+    #[case(b"abc", b"\n", "abc")]
+    #[case(b"abc\r", b"\n", "abc")]
+    #[case(b"a", b"bc\r\n", "abc")]
+    #[case(b"abc\n", b"\n", "abc\n")]
+    #[case(b"abc\r", b"", "abc\r")]
+    #[case(b"\n", b"", "")]
+    fn test_digest_reader_strip_newline(
+        #[case] first: &[u8],
+        #[case] second: &[u8],
+        #[case] expected: &str,
+    ) {
+        // the input is read in two parts, which may split the newline
+        let mut reader = first.chain(second);
+        let hasher = Hasher::new(HashAlgorithm::Md5, None);
+        let actual = digest_reader_strip_newline(hasher, &mut reader).unwrap();
+        assert_eq!(actual, digest(HashAlgorithm::Md5, expected.as_bytes()));
+    }
+
+    // RFC 2202 and RFC 4231 test case 2, and a key longer than the block of SHA3-256
+    #[rstest]
+    #[case(HashAlgorithm::Md5, b"Jefe", "750c783e6ab0b503eaa86e310a5db738")]
+    #[case(
+        HashAlgorithm::Sha1,
+        b"Jefe",
+        "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79"
+    )]
+    #[case(
+        HashAlgorithm::Sha256,
+        b"Jefe",
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    )]
+    #[case(
+        HashAlgorithm::Sha512,
+        b"Jefe",
+        "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+    )]
+    #[case(
+        HashAlgorithm::Sha3_256,
+        b"Jefe",
+        "c7d4072e788877ae3596bbb0da73b887c9171f93095b294ae857fbe2645e1ba5"
+    )]
+    #[case(
+        HashAlgorithm::Sha3_256,
+        &[0xaa; 200],
+        "1385879c388e876b30ae6386a301518508bf6b3199fdb120f222f5342ff4c898"
+    )]
+    fn test_calculate_hmac(
+        #[case] algorithm: HashAlgorithm,
+        #[case] key: &[u8],
+        #[case] expected: &str,
+    ) {
+        let req = HashRequest {
+            input: "what do ya want for nothing?",
+            algorithm,
+            encoding: InputEncoding::Utf8,
+            strip_newline: false,
+            output_len: None,
+            key: Some(key),
+        };
+        assert_eq!(calculate(&req).unwrap().to_hex(), expected);
+    }
+
+    #[rstest]
+    #[case("Jefe", KeyEncoding::Utf8, Ok(b"Jefe".to_vec()))]
+    #[case("4a 65 66 65", KeyEncoding::Hex, Ok(b"Jefe".to_vec()))]
+    #[case("SmVmZQ==", KeyEncoding::Base64, Ok(b"Jefe".to_vec()))]
+    #[case(
+        "4a6x",
+        KeyEncoding::Hex,
+        Err("invalid HMAC key: invalid hex character 'x' at position 4")
+    )]
+    #[case(
+        "!",
+        KeyEncoding::Base64,
+        Err("invalid HMAC key: invalid base64 input")
+    )]
+    fn test_decode_key(
+        #[case] key: &str,
+        #[case] encoding: KeyEncoding,
+        #[case] expected: Result<Vec<u8>, &str>,
+    ) {
+        let actual = decode_key(key, encoding).map_err(|e| e.to_string());
+        assert_eq!(actual, expected.map_err(String::from));
+    }
+
+    #[test]
+    fn test_output_len() {
+        for algorithm in HashAlgorithm::ALL {
+            assert_eq!(
+                digest(algorithm, b"").len(),
+                algorithm.output_len(),
+                "{algorithm}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_algorithm_from_str() {
+        assert_eq!("SHA256".parse(), Ok(HashAlgorithm::Sha256));
+        assert_eq!("sha512-224".parse(), Ok(HashAlgorithm::Sha512_224));
+        assert_eq!("XXH3".parse(), Ok(HashAlgorithm::Xxh3));
+        assert_eq!(
+            "sha3".parse::<HashAlgorithm>(),
+            Err(HashError::UnknownAlgorithm("sha3".into()))
+        );
+    }
+}
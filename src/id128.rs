@@ -0,0 +1,147 @@
+//! Representations of 128-bit IDs, shared by UUID and ULID.
+
+use ulid::Ulid;
+use uuid::Uuid;
+
+use crate::ParsedLines;
+
+pub(crate) const BASE62_ALPHABET: &[u8] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// The same 128-bit value in the formats used by other languages and services.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Representations {
+    pub hyphenated: String,
+    pub simple: String,
+    pub braced: String,
+    pub urn: String,
+    pub ulid: String,
+    pub base62: String,
+    pub base58: String,
+    pub decimal: String,
+    /// Big-endian bytes as a Rust array, as `Uuid::from_bytes` takes.
+    pub rust_bytes: String,
+    /// Big-endian bytes as a Java array.
+    pub java_bytes: String,
+    /// Mixed-endian bytes as `System.Guid` stores them.
+    pub csharp_bytes: String,
+}
+
+pub fn convert(value: u128) -> Representations {
+    let id = Uuid::from_u128(value);
+    let bytes = id.as_bytes();
+    let rust_bytes: Vec<String> = bytes.iter().map(|b| format!("0x{b:02x}")).collect();
+    let java_bytes: Vec<String> = bytes
+        .iter()
+        .map(|b| {
+            if *b < 0x80 {
+                format!("0x{b:02x}")
+            } else {
+                format!("(byte) 0x{b:02x}")
+            }
+        })
+        .collect();
+    let csharp_bytes: Vec<String> = id
+        .to_bytes_le()
+        .iter()
+        .map(|b| format!("0x{b:02x}"))
+        .collect();
+    Representations {
+        hyphenated: id.hyphenated().to_string(),
+        simple: id.simple().to_string(),
+        braced: id.braced().to_string(),
+        urn: id.urn().to_string(),
+        ulid: Ulid(value).to_string(),
+        base62: encode_base(value, BASE62_ALPHABET),
+        base58: encode_base(value, BASE58_ALPHABET),
+        decimal: value.to_string(),
+        rust_bytes: format!("[{}]", rust_bytes.join(", ")),
+        java_bytes: format!("new byte[] {{ {} }}", java_bytes.join(", ")),
+        csharp_bytes: format!("new Guid(new byte[] {{ {} }})", csharp_bytes.join(", ")),
+    }
+}
+
+/// Parses a UUID in any of its formats, or a ULID.
+pub fn parse(s: &str) -> Option<u128> {
+    let s = s.trim();
+    Uuid::parse_str(s)
+        .map(|id| id.as_u128())
+        .or_else(|_| Ulid::from_string(s).map(|id| id.0))
+        .ok()
+}
+
+pub fn parse_lines(text: &str) -> ParsedLines<u128> {
+    let mut ids = Vec::new();
+    let mut failure_count = 0;
+    for s in text.lines() {
+        if let Some(id) = parse(s) {
+            ids.push(id);
+        } else {
+            failure_count += 1;
+        }
+    }
+    ParsedLines { ids, failure_count }
+}
+
+fn encode_base(mut value: u128, alphabet: &[u8]) -> String {
+    let base = alphabet.len() as u128;
+    let mut buf = Vec::new();
+    loop {
+        buf.push(alphabet[(value % base) as usize]);
+        value /= base;
+        if value == 0 {
+            break;
+        }
+    }
+    buf.reverse();
+    String::from_utf8(buf).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert() {
+        let value = 0x67e55044_10b1_426f_9247_bb680e5fe0c8;
+        let actual = convert(value);
+        assert_eq!(actual.hyphenated, "67e55044-10b1-426f-9247-bb680e5fe0c8");
+        assert_eq!(actual.simple, "67e5504410b1426f9247bb680e5fe0c8");
+        assert_eq!(actual.braced, "{67e55044-10b1-426f-9247-bb680e5fe0c8}");
+        assert_eq!(actual.urn, "urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8");
+        assert_eq!(actual.ulid, "37WN84845H89QS4HXVD075ZR68");
+        assert_eq!(actual.decimal, value.to_string());
+        assert_eq!(
+            actual.rust_bytes,
+            "[0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f, 0xe0, 0xc8]"
+        );
+        assert!(actual
+            .java_bytes
+            .starts_with("new byte[] { 0x67, (byte) 0xe5, 0x50,"));
+        assert!(actual.csharp_bytes.starts_with(
+            "new Guid(new byte[] { 0x44, 0x50, 0xe5, 0x67, 0xb1, 0x10, 0x6f, 0x42, 0x92,"
+        ));
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let text = "67e55044-10b1-426f-9247-bb680e5fe0c8\n{67e55044-10b1-426f-9247-bb680e5fe0c8}\n37WN84845H89QS4HXVD075ZR68\nfoo";
+        let parsed = parse_lines(text);
+        assert_eq!(parsed.ids, vec![0x67e55044_10b1_426f_9247_bb680e5fe0c8; 3]);
+        assert_eq!(parsed.failure_count, 1);
+    }
+
+    #[test]
+    fn test_encode_base() {
+        assert_eq!(encode_base(0, BASE62_ALPHABET), "0");
+        assert_eq!(encode_base(61, BASE62_ALPHABET), "z");
+        assert_eq!(encode_base(62, BASE62_ALPHABET), "10");
+        assert_eq!(encode_base(57, BASE58_ALPHABET), "z");
+        assert_eq!(encode_base(58, BASE58_ALPHABET), "21");
+        assert_eq!(
+            encode_base(u128::MAX, BASE62_ALPHABET),
+            "7n42DGM5Tflk9n8mt7Fhc7"
+        );
+    }
+}
@@ -0,0 +1,399 @@
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    SwitchPane,
+    SelectNextItem,
+    SelectPrevItem,
+    SelectNextValue,
+    SelectPrevValue,
+    ScrollDown,
+    ScrollUp,
+    Copy,
+    Paste,
+    Edit,
+    Generate,
+    Filter,
+    CommandPalette,
+    ResetPage,
+    Save,
+    LoadFile,
+    Confirm,
+    Cancel,
+    SelectNextCandidate,
+    SelectPrevCandidate,
+}
+
+/// Determines when the keys bound to an action are resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    /// Always resolved first, regardless of the focused pane and edit mode.
+    Global,
+    /// Resolved when a pane is not editing text.
+    Normal,
+    /// Resolved while editing text, all other keys are passed to the input.
+    Input,
+}
+
+impl Scope {
+    fn overlaps(&self, other: Scope) -> bool {
+        *self == other || *self == Scope::Global || other == Scope::Global
+    }
+}
+
+impl Action {
+    const ALL: [Action; 21] = [
+        Action::Quit,
+        Action::SwitchPane,
+        Action::SelectNextItem,
+        Action::SelectPrevItem,
+        Action::SelectNextValue,
+        Action::SelectPrevValue,
+        Action::ScrollDown,
+        Action::ScrollUp,
+        Action::Copy,
+        Action::Paste,
+        Action::Edit,
+        Action::Generate,
+        Action::Filter,
+        Action::CommandPalette,
+        Action::ResetPage,
+        Action::Save,
+        Action::LoadFile,
+        Action::Confirm,
+        Action::Cancel,
+        Action::SelectNextCandidate,
+        Action::SelectPrevCandidate,
+    ];
+
+    fn scope(&self) -> Scope {
+        match self {
+            Action::Quit | Action::SwitchPane => Scope::Global,
+            Action::Confirm
+            | Action::Cancel
+            | Action::SelectNextCandidate
+            | Action::SelectPrevCandidate => Scope::Input,
+            _ => Scope::Normal,
+        }
+    }
+
+    fn default_keys(&self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["ctrl-c"],
+            Action::SwitchPane => &["tab"],
+            Action::SelectNextItem => &["j", "down"],
+            Action::SelectPrevItem => &["k", "up"],
+            Action::SelectNextValue => &["l", "right"],
+            Action::SelectPrevValue => &["h", "left"],
+            Action::ScrollDown => &["ctrl-e"],
+            Action::ScrollUp => &["ctrl-y"],
+            Action::Copy => &["y"],
+            Action::Paste => &["p"],
+            Action::Edit => &["e"],
+            Action::Generate => &["enter"],
+            Action::Filter => &["/"],
+            Action::CommandPalette => &[":", "ctrl-p"],
+            Action::ResetPage => &["ctrl-r"],
+            Action::Save => &["ctrl-s"],
+            Action::LoadFile => &["ctrl-o"],
+            Action::Confirm => &["enter"],
+            Action::Cancel => &["esc"],
+            Action::SelectNextCandidate => &["down", "ctrl-n"],
+            Action::SelectPrevCandidate => &["up", "ctrl-p"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(key: KeyEvent) -> Self {
+        let mut modifiers = key.modifiers;
+        if let KeyCode::Char(_) | KeyCode::BackTab = key.code {
+            // shift is already reflected in the character itself, and implied by backtab
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        KeyChord {
+            code: key.code,
+            modifiers,
+        }
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = s;
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            if let Some(r) = lower.strip_prefix("ctrl-") {
+                modifiers |= KeyModifiers::CONTROL;
+                rest = &rest[rest.len() - r.len()..];
+            } else if let Some(r) = lower.strip_prefix("alt-") {
+                modifiers |= KeyModifiers::ALT;
+                rest = &rest[rest.len() - r.len()..];
+            } else if let Some(r) = lower.strip_prefix("shift-") {
+                modifiers |= KeyModifiers::SHIFT;
+                rest = &rest[rest.len() - r.len()..];
+            } else {
+                break;
+            }
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => {
+                if modifiers.contains(KeyModifiers::SHIFT) {
+                    modifiers.remove(KeyModifiers::SHIFT);
+                    KeyCode::Char(c.to_ascii_uppercase())
+                } else {
+                    KeyCode::Char(c)
+                }
+            }
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                // terminals report shift-tab as backtab
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => {
+                    modifiers.remove(KeyModifiers::SHIFT);
+                    KeyCode::BackTab
+                }
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                f if f.starts_with('f') && f[1..].parse::<u8>().is_ok() => {
+                    KeyCode::F(f[1..].parse().unwrap())
+                }
+                _ => return Err(format!("invalid key: {s}")),
+            },
+        };
+        Ok(KeyChord { code, modifiers })
+    }
+}
+
+impl TryFrom<String> for KeyChord {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "C-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "M-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "S-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            KeyCode::BackTab => write!(f, "S-Tab"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            code => write!(f, "{code:?}"),
+        }
+    }
+}
+
+/// Help text for the footer, the keys are resolved from the active keymap.
+pub struct Help {
+    actions: &'static [Action],
+    desc: &'static str,
+}
+
+impl Help {
+    pub fn new(actions: &'static [Action], desc: &'static str) -> Help {
+        Help { actions, desc }
+    }
+}
+
+pub struct Keymap {
+    bindings: HashMap<Action, Vec<KeyChord>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap::new(&HashMap::new())
+    }
+}
+
+impl Keymap {
+    /// Creates a keymap from the default bindings,
+    /// replacing the keys of the actions that are specified in `overrides`.
+    pub fn new(overrides: &HashMap<Action, Vec<KeyChord>>) -> Keymap {
+        let bindings = Action::ALL
+            .into_iter()
+            .map(|action| {
+                let keys = overrides.get(&action).cloned().unwrap_or_else(|| {
+                    action
+                        .default_keys()
+                        .iter()
+                        .map(|s| s.parse().unwrap())
+                        .collect()
+                });
+                (action, keys)
+            })
+            .collect();
+        Keymap { bindings }
+    }
+
+    /// Returns an error if the same key is bound to multiple actions that can be resolved at the same time.
+    pub fn validate(&self) -> Result<(), String> {
+        for (i, a1) in Action::ALL.iter().enumerate() {
+            for a2 in &Action::ALL[i + 1..] {
+                if !a1.scope().overlaps(a2.scope()) {
+                    continue;
+                }
+                if let Some(key) = self.bindings[a1]
+                    .iter()
+                    .find(|key| self.bindings[a2].contains(key))
+                {
+                    return Err(format!("key {key} is bound to both {a1:?} and {a2:?}"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn resolve_global(&self, key: KeyEvent) -> Option<Action> {
+        self.resolve_in(Scope::Global, key)
+    }
+
+    pub fn resolve(&self, key: KeyEvent) -> Option<Action> {
+        self.resolve_in(Scope::Normal, key)
+    }
+
+    pub fn resolve_input(&self, key: KeyEvent) -> Option<Action> {
+        self.resolve_in(Scope::Input, key)
+    }
+
+    fn resolve_in(&self, scope: Scope, key: KeyEvent) -> Option<Action> {
+        let chord = KeyChord::from(key);
+        self.bindings
+            .iter()
+            .find(|(action, keys)| action.scope() == scope && keys.contains(&chord))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn help_str(&self, help: &Help) -> String {
+        let keys: Vec<String> = help
+            .actions
+            .iter()
+            .filter_map(|action| self.bindings[action].first())
+            .map(|key| key.to_string())
+            .collect();
+        format!("<{}> {}", keys.join("/"), help.desc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case("j", KeyCode::Char('j'), KeyModifiers::NONE)]
+    #[case("J", KeyCode::Char('J'), KeyModifiers::NONE)]
+    #[case("shift-j", KeyCode::Char('J'), KeyModifiers::NONE)]
+    #[case("ctrl-e", KeyCode::Char('e'), KeyModifiers::CONTROL)]
+    #[case("Ctrl-Alt-x", KeyCode::Char('x'), KeyModifiers::CONTROL | KeyModifiers::ALT)]
+    #[case("enter", KeyCode::Enter, KeyModifiers::NONE)]
+    #[case("shift-tab", KeyCode::BackTab, KeyModifiers::NONE)]
+    #[case("backtab", KeyCode::BackTab, KeyModifiers::NONE)]
+    #[case("f5", KeyCode::F(5), KeyModifiers::NONE)]
+    #[case("-", KeyCode::Char('-'), KeyModifiers::NONE)]
+    fn test_parse_key_chord(
+        #[case] s: &str,
+        #[case] code: KeyCode,
+        #[case] modifiers: KeyModifiers,
+    ) {
+        assert_eq!(s.parse(), Ok(KeyChord { code, modifiers }));
+    }
+
+    #[test]
+    fn test_parse_invalid_key_chord() {
+        assert!("ctrl-".parse::<KeyChord>().is_err());
+        assert!("foo".parse::<KeyChord>().is_err());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let overrides = HashMap::from([
+            (Action::SelectNextItem, vec!["ctrl-n".parse().unwrap()]),
+            (Action::Cancel, vec!["ctrl-g".parse().unwrap()]),
+            (
+                Action::SelectPrevCandidate,
+                vec!["shift-tab".parse().unwrap()],
+            ),
+        ]);
+        let keymap = Keymap::new(&overrides);
+        assert!(keymap.validate().is_ok());
+
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+        let ctrl_n = key(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        let j = key(KeyCode::Char('j'), KeyModifiers::NONE);
+        let shift_k = key(KeyCode::Char('K'), KeyModifiers::SHIFT);
+        let ctrl_g = key(KeyCode::Char('g'), KeyModifiers::CONTROL);
+        let tab = key(KeyCode::Tab, KeyModifiers::NONE);
+        let shift_tab = key(KeyCode::BackTab, KeyModifiers::SHIFT);
+        assert_eq!(keymap.resolve(ctrl_n), Some(Action::SelectNextItem));
+        assert_eq!(keymap.resolve(j), None);
+        assert_eq!(keymap.resolve(shift_k), None);
+        assert_eq!(keymap.resolve(ctrl_g), None);
+        assert_eq!(keymap.resolve_input(ctrl_g), Some(Action::Cancel));
+        assert_eq!(keymap.resolve_global(tab), Some(Action::SwitchPane));
+        assert_eq!(
+            keymap.resolve_input(shift_tab),
+            Some(Action::SelectPrevCandidate)
+        );
+
+        let help = Help::new(
+            &[Action::SelectNextItem, Action::SelectPrevItem],
+            "Select item",
+        );
+        assert_eq!(keymap.help_str(&help), "<C-n/k> Select item");
+    }
+
+    #[test]
+    fn test_validate_conflict() {
+        let overrides = HashMap::from([(Action::Copy, vec!["p".parse().unwrap()])]);
+        assert!(Keymap::new(&overrides).validate().is_err());
+
+        let overrides = HashMap::from([(Action::Quit, vec!["esc".parse().unwrap()])]);
+        assert!(Keymap::new(&overrides).validate().is_err());
+
+        // keys in different scopes do not conflict
+        let overrides = HashMap::from([(Action::Cancel, vec!["e".parse().unwrap()])]);
+        assert!(Keymap::new(&overrides).validate().is_ok());
+    }
+}
@@ -0,0 +1,24 @@
+//! Conversion logic of btox.
+//!
+//! Every tool page of the terminal UI is a thin wrapper over the functions in these modules,
+//! so they can also be used from other programs and tested without a terminal.
+
+pub mod base64;
+pub mod bytes;
+pub mod encoding;
+pub mod hash;
+pub mod id128;
+pub mod manifest;
+pub mod number;
+pub mod other_id;
+pub mod ulid;
+pub mod unixtime;
+pub mod url;
+pub mod uuid;
+
+/// Result of parsing a multi-line text into IDs, one per line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedLines<T> {
+    pub ids: Vec<T>,
+    pub failure_count: usize,
+}
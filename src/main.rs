@@ -1,18 +1,55 @@
 mod app;
+mod cli;
+mod clipboard;
+mod config;
 mod event;
+mod keymap;
 mod macros;
 mod msg;
 mod pages;
+mod palette;
 mod panes;
+mod session;
+mod theme;
 mod util;
 mod widget;
 
-use crate::app::App;
+use std::process::ExitCode;
 
-fn main() -> std::io::Result<()> {
+use clap::Parser;
+
+use crate::{app::App, cli::Cli, config::Config};
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if let Some(command) = cli.command {
+        return cli::run(command);
+    }
+
+    let mut config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("btox: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if cli.no_session {
+        config.session.enabled = false;
+    }
+
+    match start_tui(&config) {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("btox: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn start_tui(config: &Config) -> std::io::Result<()> {
     let mut terminal = ratatui::try_init()?;
     let (_, rx) = event::new();
-    let ret = App::new().start(&mut terminal, rx);
+    let ret = App::new(config).start(&mut terminal, rx);
     ratatui::try_restore()?;
     ret
 }
@@ -0,0 +1,202 @@
+use std::fmt;
+
+use crate::hash::{self, HashAlgorithm};
+
+/// A line of a checksum manifest such as `SHA256SUMS`, in the format of `sha256sum` and the like.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    /// Lowercase hex.
+    pub hex: String,
+    /// The path of the file relative to the manifest, as written in it.
+    pub path: String,
+}
+
+impl ManifestEntry {
+    /// Returns the length of the digest in bytes.
+    pub fn len(&self) -> usize {
+        self.hex.len() / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hex.is_empty()
+    }
+}
+
+impl fmt::Display for ManifestEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}  {}", self.hex, self.path)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestError {
+    /// The line, counted from 1, is not a checksum line.
+    InvalidLine(usize),
+    Empty,
+    /// No algorithm has digests of the length in bytes.
+    UnknownLength(usize),
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManifestError::InvalidLine(n) => write!(f, "invalid checksum line {n}"),
+            ManifestError::Empty => write!(f, "no checksum lines"),
+            ManifestError::UnknownLength(len) => {
+                write!(f, "no algorithm has digests of {len} bytes")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+/// Parses a `<hex>  <path>` line, whose path may be marked binary with `*`.
+pub fn parse_line(line: &str) -> Option<ManifestEntry> {
+    let (hex, path) = line.trim().split_once(char::is_whitespace)?;
+    if hex.is_empty() || !hex.len().is_multiple_of(2) || !hash::is_hex_digits(hex) {
+        return None;
+    }
+    let path = path.trim_start();
+    let path = path.strip_prefix('*').unwrap_or(path);
+    if path.is_empty() {
+        return None;
+    }
+    Some(ManifestEntry {
+        hex: hex.to_ascii_lowercase(),
+        path: path.into(),
+    })
+}
+
+/// Parses every line of a manifest, blank lines and `#` comments are skipped.
+pub fn parse(text: &str) -> Result<Vec<ManifestEntry>, ManifestError> {
+    let entries = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(i, line)| parse_line(line).ok_or(ManifestError::InvalidLine(i + 1)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if entries.is_empty() {
+        return Err(ManifestError::Empty);
+    }
+    Ok(entries)
+}
+
+/// Returns the conventional file name of the manifest, e.g. `SHA256SUMS` and `B2SUMS`.
+pub fn file_name(algorithm: HashAlgorithm) -> String {
+    match algorithm {
+        HashAlgorithm::Blake2b => "B2SUMS".into(),
+        _ => format!("{}SUMS", algorithm.name().to_ascii_uppercase()),
+    }
+}
+
+/// Detects the algorithm by the file name of the manifest, e.g. `SHA256SUMS` or `release.md5`,
+/// or else by the length of the digests, preferring `current` if it has the length.
+pub fn detect_algorithm(
+    name: &str,
+    entries: &[ManifestEntry],
+    current: HashAlgorithm,
+) -> Result<HashAlgorithm, ManifestError> {
+    let name = name.to_ascii_lowercase();
+    let by_name = HashAlgorithm::ALL.into_iter().find(|algorithm| {
+        let ext = format!(".{}", algorithm.name());
+        name == file_name(*algorithm).to_ascii_lowercase()
+            || name.ends_with(&ext)
+            || name.ends_with(&format!("{ext}sum"))
+    });
+    if let Some(algorithm) = by_name {
+        return Ok(algorithm);
+    }
+
+    let len = entries.first().ok_or(ManifestError::Empty)?.len();
+    if current.is_xof() || current.output_len() == len {
+        return Ok(current);
+    }
+    hash::algorithms_with_len(len)
+        .first()
+        .copied()
+        .ok_or(ManifestError::UnknownLength(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn entry(hex: &str, path: &str) -> ManifestEntry {
+        ManifestEntry {
+            hex: hex.into(),
+            path: path.into(),
+        }
+    }
+
+    #[rstest]
+    #[case(
+        "d41d8cd98f00b204e9800998ecf8427e  empty.txt",
+        Some(entry("d41d8cd98f00b204e9800998ecf8427e", "empty.txt"))
+    )]
+    #[case(
+        "D41D8CD98F00B204E9800998ECF8427E *dir/my file.bin\r",
+        Some(entry("d41d8cd98f00b204e9800998ecf8427e", "dir/my file.bin"))
+    )]
+    #[case("d41d8cd98f00b204e9800998ecf8427e", None)]
+    #[case("d41d8cd98f00b204e9800998ecf8427  empty.txt", None)]
+    #[case("SHA256 (empty.txt) = e3b0", None)]
+    fn test_parse_line(#[case] line: &str, #[case] expected: Option<ManifestEntry>) {
+        assert_eq!(parse_line(line), expected);
+    }
+
+    #[test]
+    fn test_parse() {
+        let text = "# release 0.1.0\n\naa  a.txt\nbb  b.txt\n";
+        let expected = vec![entry("aa", "a.txt"), entry("bb", "b.txt")];
+        assert_eq!(parse(text), Ok(expected));
+
+        assert_eq!(
+            parse("aa  a.txt\nbroken\n"),
+            Err(ManifestError::InvalidLine(2))
+        );
+        assert_eq!(parse("# nothing\n"), Err(ManifestError::Empty));
+    }
+
+    #[rstest]
+    #[case(HashAlgorithm::Sha256, "SHA256SUMS")]
+    #[case(HashAlgorithm::Sha3_256, "SHA3-256SUMS")]
+    #[case(HashAlgorithm::Blake2b, "B2SUMS")]
+    fn test_file_name(#[case] algorithm: HashAlgorithm, #[case] expected: &str) {
+        assert_eq!(file_name(algorithm), expected);
+    }
+
+    #[rstest]
+    #[case("SHA1SUMS", 32, HashAlgorithm::Md5, Ok(HashAlgorithm::Sha1))]
+    #[case("b2sums", 64, HashAlgorithm::Md5, Ok(HashAlgorithm::Blake2b))]
+    #[case(
+        "btox.tar.gz.sha512",
+        64,
+        HashAlgorithm::Md5,
+        Ok(HashAlgorithm::Sha512)
+    )]
+    #[case("release.md5sum", 16, HashAlgorithm::Sha1, Ok(HashAlgorithm::Md5))]
+    #[case(
+        "checksums.txt",
+        32,
+        HashAlgorithm::Sha3_256,
+        Ok(HashAlgorithm::Sha3_256)
+    )]
+    #[case("checksums.txt", 32, HashAlgorithm::Md5, Ok(HashAlgorithm::Sha256))]
+    #[case(
+        "checksums.txt",
+        3,
+        HashAlgorithm::Md5,
+        Err(ManifestError::UnknownLength(3))
+    )]
+    fn test_detect_algorithm(
+        #[case] name: &str,
+        #[case] len: usize,
+        #[case] current: HashAlgorithm,
+        #[case] expected: Result<HashAlgorithm, ManifestError>,
+    ) {
+        let entries = vec![entry(&"00".repeat(len), "a.txt")];
+        assert_eq!(detect_algorithm(name, &entries, current), expected);
+    }
+}
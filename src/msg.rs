@@ -1,3 +1,8 @@
+use crate::{
+    pages::{base64, encoding, hash, number, other_id, ulid, unixtime, url, uuid},
+    panes::list::PageType,
+};
+
 #[derive(Debug, Clone)]
 pub enum Msg {
     Quit,
@@ -5,18 +10,36 @@ pub enum Msg {
 
     ListPaneSelectNext,
     ListPaneSelectPrev,
+    ListPaneSelectPage(PageType),
+    ListPaneFilterStart,
+    ListPaneFilterEnd,
+    ListPaneFilterConfirm,
+    ListPaneFilterKeyEvent(ratatui::crossterm::event::KeyEvent),
 
     NotifyInfo(String),
     NotifyWarn(String),
     NotifyError(String),
 
+    PaletteOpen,
+    PaletteClose,
+    PaletteSelectNext,
+    PaletteSelectPrev,
+    PaletteExecute,
+    PaletteKeyEvent(ratatui::crossterm::event::KeyEvent),
+
+    /// Messages to be processed in order, e.g. the ones of a palette command.
+    Batch(Vec<Msg>),
+
     ToolPaneSelectUuidPage,
     ToolPaneSelectUlidPage,
     ToolPaneSelectBase64Page,
+    ToolPaneSelectEncodingPage,
     ToolPaneSelectUrlPage,
     ToolPaneSelectHashPage,
     ToolPaneSelectUnixTimePage,
     ToolPaneSelectNumberBasePage,
+    ToolPaneSelectOtherIdPage,
+    ToolPaneResetPage,
 
     Page(PageMsg),
 }
@@ -26,10 +49,29 @@ pub enum PageMsg {
     Uuid(UuidMsg),
     Ulid(UlidMsg),
     Base64(Base64Msg),
+    Encoding(EncodingMsg),
     Url(UrlMsg),
     Hash(HashMsg),
     UnixTime(UnixTimeMsg),
     NumberBase(NumberBaseMsg),
+    OtherId(OtherIdMsg),
+}
+
+impl PageMsg {
+    /// Returns the page the message is for, which may not be the current one for messages of background tasks.
+    pub fn page_type(&self) -> PageType {
+        match self {
+            PageMsg::Uuid(_) => PageType::Uuid,
+            PageMsg::Ulid(_) => PageType::Ulid,
+            PageMsg::Base64(_) => PageType::Base64,
+            PageMsg::Encoding(_) => PageType::Encoding,
+            PageMsg::Url(_) => PageType::Url,
+            PageMsg::Hash(_) => PageType::Hash,
+            PageMsg::UnixTime(_) => PageType::UnixTime,
+            PageMsg::NumberBase(_) => PageType::NumberBase,
+            PageMsg::OtherId(_) => PageType::OtherId,
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -43,6 +85,16 @@ pub enum UuidMsg {
     Generate,
     Copy,
     Paste,
+    SelectItem(uuid::PageItems),
+    SetHyphen(uuid::HyphenItemSelect),
+    SetCase(uuid::CaseItemSelect),
+    SetVersion(uuid::VersionItemSelect),
+    SetCount(usize),
+    SetNamespace(uuid::NamespaceItemSelect),
+    SetView(uuid::ViewItemSelect),
+    EditStart,
+    EditEnd,
+    EditKeyEvent(ratatui::crossterm::event::KeyEvent),
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -56,6 +108,14 @@ pub enum UlidMsg {
     Generate,
     Copy,
     Paste,
+    SelectItem(ulid::PageItems),
+    SetCase(ulid::CaseItemSelect),
+    SetCount(usize),
+    SetGeneration(ulid::GenerationItemSelect),
+    SetView(ulid::ViewItemSelect),
+    EditStart,
+    EditEnd,
+    EditKeyEvent(ratatui::crossterm::event::KeyEvent),
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -68,6 +128,43 @@ pub enum Base64Msg {
     ScrollUp,
     Copy,
     Paste,
+    SelectItem(base64::PageItems),
+    SetMode(base64::EncodeOrDecodeSelect),
+    SetVariant(base64::VariantSelect),
+    SetPadding(base64::PaddingSelect),
+    SetOutputView(base64::OutputViewSelect),
+    SaveStart,
+    SaveEnd,
+    SaveConfirm,
+    SaveKeyEvent(ratatui::crossterm::event::KeyEvent),
+    LoadStart,
+    LoadEnd,
+    LoadConfirm,
+    LoadSelectNextCandidate,
+    LoadSelectPrevCandidate,
+    LoadKeyEvent(ratatui::crossterm::event::KeyEvent),
+    /// Sent from the background thread reading the file.
+    LoadProgress,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum EncodingMsg {
+    SelectNextItem,
+    SelectPrevItem,
+    CurrentItemSelectNext,
+    CurrentItemSelectPrev,
+    ScrollDown,
+    ScrollUp,
+    Copy,
+    Paste,
+    SelectItem(encoding::PageItems),
+    SetMode(encoding::EncodeOrDecodeSelect),
+    SetCodec(encoding::CodecSelect),
+    SetOutputView(encoding::OutputViewSelect),
+    SaveStart,
+    SaveEnd,
+    SaveConfirm,
+    SaveKeyEvent(ratatui::crossterm::event::KeyEvent),
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -80,6 +177,17 @@ pub enum UrlMsg {
     ScrollUp,
     Copy,
     Paste,
+    SelectItem(url::PageItems),
+    SetMode(url::EncodeOrDecodeSelect),
+    SetCharset(url::CharsetSelect),
+    LoadStart,
+    LoadEnd,
+    LoadConfirm,
+    LoadSelectNextCandidate,
+    LoadSelectPrevCandidate,
+    LoadKeyEvent(ratatui::crossterm::event::KeyEvent),
+    /// Sent from the background thread reading the file.
+    LoadProgress,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -92,6 +200,40 @@ pub enum HashMsg {
     ScrollUp,
     Copy,
     Paste,
+    SelectItem(hash::PageItems),
+    /// Selects the row of the algorithm in the all algorithms view.
+    SelectRow(usize),
+    SetView(hash::ViewItemSelect),
+    SetAlgorithm(hash::AlgoItemSelect),
+    SetOutputLen(usize),
+    SetMode(hash::ModeItemSelect),
+    SetKeyEncoding(hash::KeyEncodingItemSelect),
+    SetEncoding(hash::EncodeItemSelect),
+    SetNewline(hash::NewlineItemSelect),
+    SetFormat(hash::FormatItemSelect),
+    EditStart,
+    EditEnd,
+    EditKeyEvent(ratatui::crossterm::event::KeyEvent),
+    PickStart,
+    PickEnd,
+    PickConfirm,
+    PickSelectNextCandidate,
+    PickSelectPrevCandidate,
+    PickKeyEvent(ratatui::crossterm::event::KeyEvent),
+    LoadStart,
+    LoadEnd,
+    LoadConfirm,
+    LoadSelectNextCandidate,
+    LoadSelectPrevCandidate,
+    LoadKeyEvent(ratatui::crossterm::event::KeyEvent),
+    /// Sent from the background thread reading the file.
+    LoadProgress,
+    ManifestVerifyStart,
+    ManifestGenerateStart,
+    /// Sent from the background thread verifying or generating the manifest.
+    ManifestProgress,
+    ManifestClose,
+    SaveStart,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -105,6 +247,8 @@ pub enum UnixTimeMsg {
     EditKeyEvent(ratatui::crossterm::event::KeyEvent),
     Copy,
     Paste,
+    SelectItem(unixtime::PageItems),
+    SetTimeZone(unixtime::TimeZoneItemSelect),
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -118,4 +262,26 @@ pub enum NumberBaseMsg {
     EditKeyEvent(ratatui::crossterm::event::KeyEvent),
     Copy,
     Paste,
+    SelectItem(number::PageItems),
+    SetCase(number::CaseItemSelect),
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum OtherIdMsg {
+    SelectNextItem,
+    SelectPrevItem,
+    CurrentItemSelectNext,
+    CurrentItemSelectPrev,
+    ScrollDown,
+    ScrollUp,
+    Generate,
+    Copy,
+    Paste,
+    SelectItem(other_id::PageItems),
+    SetKind(other_id::KindItemSelect),
+    SetCount(usize),
+    SetView(other_id::ViewItemSelect),
+    EditStart,
+    EditEnd,
+    EditKeyEvent(ratatui::crossterm::event::KeyEvent),
 }
@@ -0,0 +1,111 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Radix {
+    Binary,
+    Octal,
+    Decimal,
+    Hexadecimal,
+}
+
+impl Radix {
+    pub fn value(&self) -> u32 {
+        match self {
+            Radix::Binary => 2,
+            Radix::Octal => 8,
+            Radix::Decimal => 10,
+            Radix::Hexadecimal => 16,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Radix::Binary => "binary",
+            Radix::Octal => "octal",
+            Radix::Decimal => "decimal",
+            Radix::Hexadecimal => "hexadecimal",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Case {
+    #[default]
+    Lowercase,
+    Uppercase,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NumberError {
+    InvalidNumber(Radix),
+}
+
+impl fmt::Display for NumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberError::InvalidNumber(radix) => write!(f, "invalid {} number", radix.name()),
+        }
+    }
+}
+
+impl std::error::Error for NumberError {}
+
+#[derive(Debug, Clone)]
+pub struct NumberRequest<'a> {
+    pub input: &'a str,
+    pub radix: Radix,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberResponse {
+    pub value: u128,
+}
+
+impl NumberResponse {
+    pub fn format(&self, radix: Radix, case: Case) -> String {
+        let value = self.value;
+        match (radix, case) {
+            (Radix::Binary, _) => format!("{value:b}"),
+            (Radix::Octal, _) => format!("{value:o}"),
+            (Radix::Decimal, _) => format!("{value}"),
+            (Radix::Hexadecimal, Case::Lowercase) => format!("{value:x}"),
+            (Radix::Hexadecimal, Case::Uppercase) => format!("{value:X}"),
+        }
+    }
+}
+
+pub fn parse(req: &NumberRequest) -> Result<NumberResponse, NumberError> {
+    u128::from_str_radix(req.input, req.radix.value())
+        .map(|value| NumberResponse { value })
+        .map_err(|_| NumberError::InvalidNumber(req.radix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case("11111111", Radix::Binary, Ok(255))]
+    #[case("377", Radix::Octal, Ok(255))]
+    #[case("255", Radix::Decimal, Ok(255))]
+    #[case("fF", Radix::Hexadecimal, Ok(255))]
+    #[case("12", Radix::Binary, Err(NumberError::InvalidNumber(Radix::Binary)))]
+    #[case("", Radix::Decimal, Err(NumberError::InvalidNumber(Radix::Decimal)))]
+    fn test_parse(
+        #[case] input: &str,
+        #[case] radix: Radix,
+        #[case] expected: Result<u128, NumberError>,
+    ) {
+        let req = NumberRequest { input, radix };
+        assert_eq!(parse(&req).map(|res| res.value), expected);
+    }
+
+    #[test]
+    fn test_format() {
+        let res = NumberResponse { value: 0xabc };
+        assert_eq!(res.format(Radix::Binary, Case::Uppercase), "101010111100");
+        assert_eq!(res.format(Radix::Hexadecimal, Case::Lowercase), "abc");
+        assert_eq!(res.format(Radix::Hexadecimal, Case::Uppercase), "ABC");
+    }
+}
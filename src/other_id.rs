@@ -0,0 +1,663 @@
+//! IDs other than UUID and ULID: NanoID, KSUID, CUID2, MongoDB ObjectId and Snowflake.
+
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU32, AtomicU64, Ordering},
+        OnceLock,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use sha3::{Digest, Sha3_512};
+
+use crate::{
+    id128::BASE62_ALPHABET,
+    unixtime::{self, ParsedInput},
+};
+
+pub const NANOID_DEFAULT_ALPHABET: &str =
+    "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789_-";
+pub const NANOID_DEFAULT_LENGTH: usize = 21;
+pub const NANOID_LENGTH_MAX: usize = 64;
+
+/// Seconds since the Unix epoch of the KSUID epoch, 2014-05-13T16:53:20Z.
+pub const KSUID_EPOCH: i64 = 1_400_000_000;
+const KSUID_ENCODED_LEN: usize = 27;
+
+const CUID2_LENGTH: usize = 24;
+const CUID2_MAX_LENGTH: usize = 32;
+const BASE36_ALPHABET: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Milliseconds since the Unix epoch of the Twitter Snowflake epoch, 2010-11-04T01:42:54.657Z.
+pub const SNOWFLAKE_TWITTER_EPOCH_MS: i64 = 1_288_834_974_657;
+/// Largest datacenter and worker ID, 5 bits each.
+pub const SNOWFLAKE_NODE_MAX: u8 = 31;
+const SNOWFLAKE_SEQUENCE_MAX: u64 = (1 << 12) - 1;
+const SNOWFLAKE_TIMESTAMP_MAX: i64 = (1 << 41) - 1;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IdKind {
+    #[default]
+    NanoId,
+    Ksuid,
+    Cuid2,
+    ObjectId,
+    Snowflake,
+}
+
+impl fmt::Display for IdKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            IdKind::NanoId => "NanoID",
+            IdKind::Ksuid => "KSUID",
+            IdKind::Cuid2 => "CUID2",
+            IdKind::ObjectId => "ObjectId",
+            IdKind::Snowflake => "Snowflake",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OtherIdError {
+    /// The alphabet has less than 2 or more than 256 characters, or duplicates.
+    InvalidAlphabet,
+    InvalidLength,
+    InvalidEpoch,
+    InvalidNode,
+    /// The current time cannot be stored in the timestamp field of the ID.
+    TimestampOutOfRange,
+    InvalidId(IdKind),
+}
+
+impl fmt::Display for OtherIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OtherIdError::InvalidAlphabet => {
+                write!(f, "alphabet must have 2 to 256 distinct characters")
+            }
+            OtherIdError::InvalidLength => {
+                write!(f, "length must be between 1 and {NANOID_LENGTH_MAX}")
+            }
+            OtherIdError::InvalidEpoch => write!(f, "invalid epoch"),
+            OtherIdError::InvalidNode => {
+                write!(
+                    f,
+                    "datacenter and worker must be between 0 and {SNOWFLAKE_NODE_MAX}"
+                )
+            }
+            OtherIdError::TimestampOutOfRange => write!(f, "timestamp out of range"),
+            OtherIdError::InvalidId(kind) => write!(f, "invalid {kind}"),
+        }
+    }
+}
+
+impl std::error::Error for OtherIdError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NanoIdOptions {
+    pub alphabet: String,
+    pub length: usize,
+}
+
+impl Default for NanoIdOptions {
+    fn default() -> Self {
+        NanoIdOptions {
+            alphabet: NANOID_DEFAULT_ALPHABET.into(),
+            length: NANOID_DEFAULT_LENGTH,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnowflakeOptions {
+    /// Milliseconds since the Unix epoch that timestamp 0 stands for.
+    pub epoch_ms: i64,
+    pub datacenter: u8,
+    pub worker: u8,
+}
+
+impl Default for SnowflakeOptions {
+    fn default() -> Self {
+        SnowflakeOptions {
+            epoch_ms: SNOWFLAKE_TWITTER_EPOCH_MS,
+            datacenter: 0,
+            worker: 0,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct OtherIdRequest {
+    pub kind: IdKind,
+    pub count: usize,
+    pub nanoid: NanoIdOptions,
+    pub snowflake: SnowflakeOptions,
+}
+
+pub fn generate(req: &OtherIdRequest) -> Result<Vec<String>, OtherIdError> {
+    match req.kind {
+        IdKind::NanoId => {
+            if !(1..=NANOID_LENGTH_MAX).contains(&req.nanoid.length) {
+                return Err(OtherIdError::InvalidLength);
+            }
+            let alphabet = nanoid_alphabet(&req.nanoid.alphabet)?;
+            Ok((0..req.count)
+                .map(|_| generate_nanoid(&alphabet, req.nanoid.length))
+                .collect())
+        }
+        IdKind::Ksuid => (0..req.count).map(|_| generate_ksuid()).collect(),
+        IdKind::Cuid2 => Ok((0..req.count).map(|_| generate_cuid2()).collect()),
+        IdKind::ObjectId => (0..req.count).map(|_| generate_object_id()).collect(),
+        IdKind::Snowflake => generate_snowflakes(req.count, &req.snowflake),
+    }
+}
+
+/// Returns the characters of the alphabet, which must have 2 to 256 distinct ones.
+pub fn nanoid_alphabet(alphabet: &str) -> Result<Vec<char>, OtherIdError> {
+    let alphabet: Vec<char> = alphabet.chars().collect();
+    let mut sorted = alphabet.clone();
+    sorted.sort_unstable();
+    sorted.dedup();
+    if !(2..=256).contains(&alphabet.len()) || sorted.len() != alphabet.len() {
+        return Err(OtherIdError::InvalidAlphabet);
+    }
+    Ok(alphabet)
+}
+
+fn generate_nanoid(alphabet: &[char], length: usize) -> String {
+    let mut rng = rand::rng();
+    (0..length)
+        .map(|_| alphabet[rng.random_range(0..alphabet.len())])
+        .collect()
+}
+
+fn generate_ksuid() -> Result<String, OtherIdError> {
+    let timestamp = u32::try_from(now_millis() / 1000 - KSUID_EPOCH)
+        .map_err(|_| OtherIdError::TimestampOutOfRange)?;
+    let mut bytes = [0u8; 20];
+    bytes[..4].copy_from_slice(&timestamp.to_be_bytes());
+    rand::rng().fill(&mut bytes[4..]);
+    Ok(encode_ksuid(&bytes))
+}
+
+fn encode_ksuid(bytes: &[u8; 20]) -> String {
+    let encoded = encode_base(bytes, BASE62_ALPHABET);
+    format!("{encoded:0>KSUID_ENCODED_LEN$}")
+}
+
+/// Returns a CUID2 as the reference implementation does:
+/// a random letter followed by the base36 SHA3-512 of the time, random entropy, a counter and a fingerprint.
+fn generate_cuid2() -> String {
+    static COUNTER: OnceLock<AtomicU64> = OnceLock::new();
+    static FINGERPRINT: OnceLock<String> = OnceLock::new();
+
+    let counter = COUNTER.get_or_init(|| AtomicU64::new(rand::rng().random_range(0..476_782_367)));
+    let fingerprint = FINGERPRINT.get_or_init(|| {
+        let source = format!("{}{}", std::process::id(), base36_entropy(CUID2_MAX_LENGTH));
+        cuid2_hash(&source)[..CUID2_MAX_LENGTH].to_string()
+    });
+
+    let time = encode_base(&now_millis().to_be_bytes(), BASE36_ALPHABET);
+    let entropy = base36_entropy(CUID2_LENGTH);
+    let count = encode_base(
+        &counter.fetch_add(1, Ordering::Relaxed).to_be_bytes(),
+        BASE36_ALPHABET,
+    );
+    let hash = cuid2_hash(&format!("{time}{entropy}{count}{fingerprint}"));
+
+    let first_letter = BASE36_ALPHABET[rand::rng().random_range(10..36)] as char;
+    format!("{first_letter}{}", &hash[1..CUID2_LENGTH])
+}
+
+fn cuid2_hash(input: &str) -> String {
+    let digest = Sha3_512::digest(input.as_bytes());
+    // the first character is dropped since it is biased
+    encode_base(&digest, BASE36_ALPHABET)[1..].to_string()
+}
+
+fn base36_entropy(length: usize) -> String {
+    let mut rng = rand::rng();
+    (0..length)
+        .map(|_| BASE36_ALPHABET[rng.random_range(0..36)] as char)
+        .collect()
+}
+
+fn generate_object_id() -> Result<String, OtherIdError> {
+    // the random value is unique to the process and the counter is shared by all IDs of the process
+    static RANDOM: OnceLock<[u8; 5]> = OnceLock::new();
+    static COUNTER: OnceLock<AtomicU32> = OnceLock::new();
+
+    let random = RANDOM.get_or_init(|| rand::rng().random());
+    let counter = COUNTER.get_or_init(|| AtomicU32::new(rand::rng().random()));
+
+    let timestamp =
+        u32::try_from(now_millis() / 1000).map_err(|_| OtherIdError::TimestampOutOfRange)?;
+    let count = counter.fetch_add(1, Ordering::Relaxed);
+    let mut bytes = [0u8; 12];
+    bytes[..4].copy_from_slice(&timestamp.to_be_bytes());
+    bytes[4..9].copy_from_slice(random);
+    bytes[9..].copy_from_slice(&count.to_be_bytes()[1..]);
+    Ok(base16ct::lower::encode_string(&bytes))
+}
+
+fn generate_snowflakes(
+    count: usize,
+    options: &SnowflakeOptions,
+) -> Result<Vec<String>, OtherIdError> {
+    if options.datacenter > SNOWFLAKE_NODE_MAX || options.worker > SNOWFLAKE_NODE_MAX {
+        return Err(OtherIdError::InvalidNode);
+    }
+    let node = ((options.datacenter as u64) << 17) | ((options.worker as u64) << 12);
+    let mut ids = Vec::with_capacity(count);
+    let mut last_timestamp = -1;
+    let mut sequence = 0;
+    while ids.len() < count {
+        let timestamp = now_millis() - options.epoch_ms;
+        if !(0..=SNOWFLAKE_TIMESTAMP_MAX).contains(&timestamp) {
+            return Err(OtherIdError::TimestampOutOfRange);
+        }
+        if timestamp == last_timestamp {
+            if sequence == SNOWFLAKE_SEQUENCE_MAX {
+                // wait for the next millisecond once the sequence is used up
+                continue;
+            }
+            sequence += 1;
+        } else if timestamp > last_timestamp {
+            sequence = 0;
+        } else {
+            // the clock went backwards
+            continue;
+        }
+        last_timestamp = timestamp;
+        ids.push((((timestamp as u64) << 22) | node | sequence).to_string());
+    }
+    Ok(ids)
+}
+
+/// Parses the Snowflake epoch as a Unix timestamp (seconds to nanoseconds) or an RFC 3339 datetime.
+/// An empty string is the Twitter epoch.
+pub fn parse_snowflake_epoch(s: &str) -> Result<i64, OtherIdError> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Ok(SNOWFLAKE_TWITTER_EPOCH_MS);
+    }
+    let datetime = match unixtime::parse(s) {
+        Ok(ParsedInput::UnixTimestamp(dt)) => dt.datetime,
+        Ok(ParsedInput::Datetime(dt)) => dt,
+        Err(_) => return Err(OtherIdError::InvalidEpoch),
+    };
+    let epoch_ms = datetime.timestamp_millis();
+    if epoch_ms > now_millis() {
+        return Err(OtherIdError::InvalidEpoch);
+    }
+    Ok(epoch_ms)
+}
+
+/// Fields decoded from an ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtherIdInfo {
+    /// The time embedded in the ID, `None` for NanoID and CUID2, which have none.
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Other fields of the ID as name and value, in the order they are laid out.
+    pub fields: Vec<(&'static str, String)>,
+}
+
+/// Decodes an ID of the given kind, `snowflake.epoch_ms` is used for Snowflake IDs.
+pub fn inspect(
+    kind: IdKind,
+    s: &str,
+    nanoid: &NanoIdOptions,
+    snowflake: &SnowflakeOptions,
+) -> Result<OtherIdInfo, OtherIdError> {
+    let s = s.trim();
+    let invalid = OtherIdError::InvalidId(kind);
+    match kind {
+        IdKind::NanoId => {
+            let alphabet = nanoid_alphabet(&nanoid.alphabet)?;
+            let length = s.chars().count();
+            if length == 0 || !s.chars().all(|c| alphabet.contains(&c)) {
+                return Err(invalid);
+            }
+            let entropy = length as f64 * (alphabet.len() as f64).log2();
+            Ok(OtherIdInfo {
+                timestamp: None,
+                fields: vec![
+                    ("Length", length.to_string()),
+                    ("Alphabet size", alphabet.len().to_string()),
+                    ("Entropy", format!("{entropy:.1} bits")),
+                ],
+            })
+        }
+        IdKind::Ksuid => {
+            let bytes: [u8; 20] = decode_base(s, BASE62_ALPHABET)
+                .filter(|_| s.len() == KSUID_ENCODED_LEN)
+                .ok_or(invalid)?;
+            let timestamp = u32::from_be_bytes(bytes[..4].try_into().unwrap());
+            Ok(OtherIdInfo {
+                timestamp: DateTime::from_timestamp(timestamp as i64 + KSUID_EPOCH, 0),
+                fields: vec![
+                    ("Timestamp", timestamp.to_string()),
+                    ("Payload", base16ct::lower::encode_string(&bytes[4..])),
+                    ("Raw", base16ct::lower::encode_string(&bytes)),
+                ],
+            })
+        }
+        IdKind::Cuid2 => {
+            let mut chars = s.chars();
+            let valid = (2..=CUID2_MAX_LENGTH).contains(&s.len())
+                && chars.next().is_some_and(|c| c.is_ascii_lowercase())
+                && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+            if !valid {
+                return Err(invalid);
+            }
+            Ok(OtherIdInfo {
+                timestamp: None,
+                fields: vec![("Length", s.len().to_string())],
+            })
+        }
+        IdKind::ObjectId => {
+            let mut bytes = [0u8; 12];
+            let decoded = base16ct::mixed::decode(s, &mut bytes).map_err(|_| invalid.clone())?;
+            if decoded.len() != bytes.len() {
+                return Err(invalid);
+            }
+            let timestamp = u32::from_be_bytes(bytes[..4].try_into().unwrap());
+            let counter = u32::from_be_bytes([0, bytes[9], bytes[10], bytes[11]]);
+            Ok(OtherIdInfo {
+                timestamp: DateTime::from_timestamp(timestamp as i64, 0),
+                fields: vec![
+                    ("Timestamp", timestamp.to_string()),
+                    ("Random", base16ct::lower::encode_string(&bytes[4..9])),
+                    ("Counter", counter.to_string()),
+                ],
+            })
+        }
+        IdKind::Snowflake => {
+            let id: u64 = s.parse().map_err(|_| invalid)?;
+            let timestamp = (id >> 22) as i64;
+            Ok(OtherIdInfo {
+                timestamp: DateTime::from_timestamp_millis(timestamp + snowflake.epoch_ms),
+                fields: vec![
+                    ("Timestamp", format!("{timestamp} ms since epoch")),
+                    ("Datacenter", ((id >> 17) & 0x1f).to_string()),
+                    ("Worker", ((id >> 12) & 0x1f).to_string()),
+                    ("Sequence", (id & SNOWFLAKE_SEQUENCE_MAX).to_string()),
+                ],
+            })
+        }
+    }
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as i64)
+}
+
+/// Encodes big-endian bytes as a number in the base of the alphabet, without leading zeros.
+pub(crate) fn encode_base(bytes: &[u8], alphabet: &[u8]) -> String {
+    let base = alphabet.len() as u32;
+    let mut number: Vec<u8> = bytes.iter().copied().skip_while(|b| *b == 0).collect();
+    let mut digits = Vec::new();
+    while !number.is_empty() {
+        // long division of the number by the base
+        let mut quotient = Vec::with_capacity(number.len());
+        let mut remainder = 0u32;
+        for b in &number {
+            let acc = (remainder << 8) | *b as u32;
+            let q = (acc / base) as u8;
+            remainder = acc % base;
+            if !quotient.is_empty() || q != 0 {
+                quotient.push(q);
+            }
+        }
+        digits.push(alphabet[remainder as usize]);
+        number = quotient;
+    }
+    if digits.is_empty() {
+        digits.push(alphabet[0]);
+    }
+    digits.reverse();
+    String::from_utf8(digits).unwrap()
+}
+
+/// Decodes a number in the base of the alphabet into `N` big-endian bytes, `None` if it does not fit.
+fn decode_base<const N: usize>(s: &str, alphabet: &[u8]) -> Option<[u8; N]> {
+    let base = alphabet.len() as u32;
+    let mut bytes = [0u8; N];
+    if s.is_empty() {
+        return None;
+    }
+    for c in s.bytes() {
+        let mut carry = alphabet.iter().position(|a| *a == c)? as u32;
+        for b in bytes.iter_mut().rev() {
+            let acc = *b as u32 * base + carry;
+            *b = acc as u8;
+            carry = acc >> 8;
+        }
+        if carry != 0 {
+            return None;
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn generate_one(req: OtherIdRequest) -> String {
+        let req = OtherIdRequest { count: 1, ..req };
+        generate(&req).unwrap().remove(0)
+    }
+
+    fn inspect_default(kind: IdKind, s: &str) -> Result<OtherIdInfo, OtherIdError> {
+        inspect(
+            kind,
+            s,
+            &NanoIdOptions::default(),
+            &SnowflakeOptions::default(),
+        )
+    }
+
+    #[test]
+    fn test_generate_nanoid() {
+        let id = generate_one(OtherIdRequest::default());
+        assert_eq!(id.len(), NANOID_DEFAULT_LENGTH);
+        assert!(id.chars().all(|c| NANOID_DEFAULT_ALPHABET.contains(c)));
+
+        let nanoid = NanoIdOptions {
+            alphabet: "ab".into(),
+            length: 8,
+        };
+        let req = OtherIdRequest {
+            count: 10,
+            nanoid,
+            ..Default::default()
+        };
+        let ids = generate(&req).unwrap();
+        assert_eq!(ids.len(), 10);
+        assert!(ids
+            .iter()
+            .all(|id| id.len() == 8 && id.chars().all(|c| c == 'a' || c == 'b')));
+    }
+
+    #[rstest]
+    #[case("a", 21, OtherIdError::InvalidAlphabet)]
+    #[case("aba", 21, OtherIdError::InvalidAlphabet)]
+    #[case("ab", 0, OtherIdError::InvalidLength)]
+    #[case("ab", NANOID_LENGTH_MAX + 1, OtherIdError::InvalidLength)]
+    fn test_generate_nanoid_invalid(
+        #[case] alphabet: &str,
+        #[case] length: usize,
+        #[case] expected: OtherIdError,
+    ) {
+        let req = OtherIdRequest {
+            count: 1,
+            nanoid: NanoIdOptions {
+                alphabet: alphabet.into(),
+                length,
+            },
+            ..Default::default()
+        };
+        assert_eq!(generate(&req), Err(expected));
+    }
+
+    #[test]
+    fn test_generate_ksuid() {
+        let id = generate_one(OtherIdRequest {
+            kind: IdKind::Ksuid,
+            ..Default::default()
+        });
+        assert_eq!(id.len(), KSUID_ENCODED_LEN);
+        let info = inspect_default(IdKind::Ksuid, &id).unwrap();
+        let elapsed = Utc::now() - info.timestamp.unwrap();
+        assert!(elapsed.num_seconds() < 5);
+    }
+
+    #[test]
+    fn test_inspect_ksuid() {
+        let info = inspect_default(IdKind::Ksuid, "0ujtsYcgvSTl8PAuAdqWYSMnLOv").unwrap();
+        assert_eq!(info.timestamp, DateTime::from_timestamp(1_507_608_047, 0));
+        assert_eq!(info.fields[0], ("Timestamp", "107608047".into()));
+        assert_eq!(
+            info.fields[1],
+            ("Payload", "b5a1cd34b5f99d1154fb6853345c9735".into())
+        );
+
+        assert!(inspect_default(IdKind::Ksuid, "0ujtsYcgvSTl8PAuAdqWYSMnLO").is_err());
+        assert!(inspect_default(IdKind::Ksuid, "zzzzzzzzzzzzzzzzzzzzzzzzzzz").is_err());
+    }
+
+    #[test]
+    fn test_generate_cuid2() {
+        let req = OtherIdRequest {
+            kind: IdKind::Cuid2,
+            count: 2,
+            ..Default::default()
+        };
+        let ids = generate(&req).unwrap();
+        assert_ne!(ids[0], ids[1]);
+        for id in ids {
+            assert_eq!(id.len(), CUID2_LENGTH);
+            let info = inspect_default(IdKind::Cuid2, &id).unwrap();
+            assert_eq!(info.timestamp, None);
+        }
+        assert!(inspect_default(IdKind::Cuid2, "1abc").is_err());
+        assert!(inspect_default(IdKind::Cuid2, "Abc").is_err());
+    }
+
+    #[test]
+    fn test_generate_object_id() {
+        let req = OtherIdRequest {
+            kind: IdKind::ObjectId,
+            count: 2,
+            ..Default::default()
+        };
+        let ids = generate(&req).unwrap();
+        assert_eq!(ids[0].len(), 24);
+        assert_eq!(ids[0][8..18], ids[1][8..18]);
+        let counter = |id: &str| u32::from_str_radix(&id[18..], 16).unwrap();
+        assert_eq!((counter(&ids[0]) + 1) & 0xff_ffff, counter(&ids[1]));
+    }
+
+    #[test]
+    fn test_inspect_object_id() {
+        let info = inspect_default(IdKind::ObjectId, "507f1f77bcf86cd799439011").unwrap();
+        assert_eq!(info.timestamp, DateTime::from_timestamp(1_350_508_407, 0));
+        assert_eq!(info.fields[1], ("Random", "bcf86cd799".into()));
+        assert_eq!(info.fields[2], ("Counter", "4427793".into()));
+
+        assert!(inspect_default(IdKind::ObjectId, "507f1f77bcf86cd7994390").is_err());
+    }
+
+    #[test]
+    fn test_generate_snowflake() {
+        let snowflake = SnowflakeOptions {
+            datacenter: 3,
+            worker: 17,
+            ..Default::default()
+        };
+        let req = OtherIdRequest {
+            kind: IdKind::Snowflake,
+            count: 100,
+            snowflake,
+            ..Default::default()
+        };
+        let ids: Vec<u64> = generate(&req)
+            .unwrap()
+            .iter()
+            .map(|id| id.parse().unwrap())
+            .collect();
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+        let info = inspect(
+            IdKind::Snowflake,
+            &ids[0].to_string(),
+            &Default::default(),
+            &snowflake,
+        )
+        .unwrap();
+        assert_eq!(info.fields[1], ("Datacenter", "3".into()));
+        assert_eq!(info.fields[2], ("Worker", "17".into()));
+
+        let invalid_node = OtherIdRequest {
+            snowflake: SnowflakeOptions {
+                worker: 32,
+                ..Default::default()
+            },
+            ..req
+        };
+        assert_eq!(generate(&invalid_node), Err(OtherIdError::InvalidNode));
+    }
+
+    #[test]
+    fn test_inspect_snowflake() {
+        let info = inspect_default(IdKind::Snowflake, "1212161265398824960").unwrap();
+        assert_eq!(
+            info.timestamp,
+            DateTime::from_timestamp_millis(1_577_836_741_195)
+        );
+        assert_eq!(info.fields[1], ("Datacenter", "10".into()));
+        assert_eq!(info.fields[2], ("Worker", "28".into()));
+        assert_eq!(info.fields[3], ("Sequence", "0".into()));
+
+        assert!(inspect_default(IdKind::Snowflake, "-1").is_err());
+    }
+
+    #[rstest]
+    #[case("", Ok(SNOWFLAKE_TWITTER_EPOCH_MS))]
+    #[case("1420070400000", Ok(1_420_070_400_000))]
+    #[case("2015-01-01T00:00:00Z", Ok(1_420_070_400_000))]
+    #[case("foo", Err(OtherIdError::InvalidEpoch))]
+    #[case("9999999999999", Err(OtherIdError::InvalidEpoch))]
+    fn test_parse_snowflake_epoch(
+        #[case] input: &str,
+        #[case] expected: Result<i64, OtherIdError>,
+    ) {
+        assert_eq!(parse_snowflake_epoch(input), expected);
+    }
+
+    #[test]
+    fn test_inspect_nanoid() {
+        let info = inspect_default(IdKind::NanoId, "V1StGXR8_Z5jdHi6B-myT").unwrap();
+        assert_eq!(info.timestamp, None);
+        assert_eq!(info.fields[0], ("Length", "21".into()));
+        assert_eq!(info.fields[2], ("Entropy", "126.0 bits".into()));
+
+        assert!(inspect_default(IdKind::NanoId, "not a nanoid").is_err());
+    }
+
+    #[test]
+    fn test_encode_decode_base() {
+        assert_eq!(encode_base(&[0, 0], BASE36_ALPHABET), "0");
+        assert_eq!(encode_base(&[1, 0], BASE36_ALPHABET), "74");
+        assert_eq!(decode_base::<2>("74", BASE36_ALPHABET), Some([1, 0]));
+        assert_eq!(decode_base::<1>("74", BASE36_ALPHABET), None);
+        assert_eq!(decode_base::<1>("7!", BASE36_ALPHABET), None);
+    }
+}
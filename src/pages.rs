@@ -1,9 +1,14 @@
+mod file;
+mod manifest;
 pub mod page;
+mod picker;
 mod util;
 
 pub mod base64;
+pub mod encoding;
 pub mod hash;
 pub mod number;
+pub mod other_id;
 pub mod ulid;
 pub mod unixtime;
 pub mod url;
@@ -1,23 +1,37 @@
-use base64::{engine::general_purpose, Engine as _};
+use std::borrow::Cow;
+
+use btox::{
+    base64::{self, Base64Request, Mode, Variant},
+    bytes,
+};
 use itsuki::zero_indexed_enum;
 use ratatui::{
-    crossterm::event::KeyCode,
-    layout::Rect,
-    style::{Color, Style},
+    crossterm::event::KeyEvent,
+    layout::{Constraint, Layout, Rect},
     widgets::{Block, Borders, Padding, Paragraph, Wrap},
     Frame,
 };
-use ratatui_macros::vertical;
+use tui_input::{backend::crossterm::EventHandler, Input};
 
 use crate::{
-    fn_next_prev_mut, fn_str_map, key_code, key_code_char,
+    config::Base64Config,
+    fn_next_prev_mut, fn_str_map,
+    keymap::{Action, Help, Keymap},
     msg::{Base64Msg, Msg, PageMsg},
-    pages::{page::Page, util},
+    pages::{
+        file::{self, FileLoad, LoadState, PathPrompt, PromptResult, FILE_SIZE_MAX},
+        page::Page,
+        util,
+    },
+    palette::Command,
+    session::{Base64Session, Session},
+    theme::Theme,
     widget::{
         scroll::{ScrollOutput, ScrollOutputState},
         select::Select,
     },
 };
+use serde::{Deserialize, Serialize};
 
 pub struct Base64Page {
     focused: bool,
@@ -28,17 +42,32 @@ pub struct Base64Page {
 struct CurrentStatus {
     item: PageItems,
     eod_sel: EncodeOrDecodeSelect,
+    variant_sel: VariantSelect,
+    padding_sel: PaddingSelect,
     input: String,
     input_state: ScrollOutputState,
     output: String,
+    bytes: Vec<u8>,
+    view_sel: OutputViewSelect,
+    output_state: ScrollOutputState,
     status: InputStatus,
+    /// The path being entered to save the output to, `None` if not saving.
+    save_path: Option<Input>,
+    /// The file used instead of the input, if loaded.
+    file: Option<FileLoad>,
+    prompt: Option<PathPrompt>,
 }
 
 impl Base64Page {
-    pub fn new(focused: bool) -> Base64Page {
+    pub fn new(focused: bool, config: &Base64Config) -> Base64Page {
         Base64Page {
             focused,
-            cur: CurrentStatus::default(),
+            cur: CurrentStatus {
+                eod_sel: config.mode,
+                variant_sel: config.variant,
+                padding_sel: config.padding,
+                ..Default::default()
+            },
         }
     }
 }
@@ -47,6 +76,7 @@ impl Base64Page {
 enum InputStatus {
     #[default]
     None,
+    Info(String),
     Warn(String),
 }
 
@@ -55,11 +85,15 @@ enum InputStatus {
 enum PageItems {
     #[default]
     EncodeOrDecode,
+    Variant,
+    Padding,
+    OutputView,
     Input,
     Output,
 }
 
-#[derive(Default)]
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
 #[zero_indexed_enum]
 enum EncodeOrDecodeSelect {
     #[default]
@@ -76,17 +110,128 @@ impl EncodeOrDecodeSelect {
     fn_next_prev_mut! {}
 }
 
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[zero_indexed_enum]
+enum VariantSelect {
+    /// Detects the variant on decode, standard on encode.
+    #[default]
+    Auto,
+    Standard,
+    UrlSafe,
+    Mime,
+    Bcrypt,
+    Crypt,
+}
+
+impl VariantSelect {
+    fn_str_map! {
+        VariantSelect::Auto => "Auto",
+        VariantSelect::Standard => "Standard",
+        VariantSelect::UrlSafe => "URL-safe",
+        VariantSelect::Mime => "MIME",
+        VariantSelect::Bcrypt => "bcrypt",
+        VariantSelect::Crypt => "crypt",
+    }
+
+    fn_next_prev_mut! {}
+
+    fn variant(&self) -> Option<Variant> {
+        match self {
+            VariantSelect::Auto => None,
+            VariantSelect::Standard => Some(Variant::Standard),
+            VariantSelect::UrlSafe => Some(Variant::UrlSafe),
+            VariantSelect::Mime => Some(Variant::Mime),
+            VariantSelect::Bcrypt => Some(Variant::Bcrypt),
+            VariantSelect::Crypt => Some(Variant::Crypt),
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[zero_indexed_enum]
+enum PaddingSelect {
+    #[default]
+    WithPadding,
+    WithoutPadding,
+}
+
+impl PaddingSelect {
+    fn_str_map! {
+        PaddingSelect::WithPadding => "With padding",
+        PaddingSelect::WithoutPadding => "Without padding",
+    }
+
+    fn_next_prev_mut! {}
+}
+
+/// How the decoded bytes are shown, other than text they are shown as is even if not valid UTF-8.
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[zero_indexed_enum]
+enum OutputViewSelect {
+    #[default]
+    Text,
+    HexDump,
+    Hex,
+    CArray,
+    RustArray,
+}
+
+impl OutputViewSelect {
+    fn_str_map! {
+        OutputViewSelect::Text => "Text",
+        OutputViewSelect::HexDump => "Hex dump",
+        OutputViewSelect::Hex => "Hex",
+        OutputViewSelect::CArray => "C array",
+        OutputViewSelect::RustArray => "Rust array",
+    }
+
+    fn_next_prev_mut! {}
+}
+
+impl From<EncodeOrDecodeSelect> for Mode {
+    fn from(eod_sel: EncodeOrDecodeSelect) -> Self {
+        match eod_sel {
+            EncodeOrDecodeSelect::Encode => Mode::Encode,
+            EncodeOrDecodeSelect::Decode => Mode::Decode,
+        }
+    }
+}
+
 impl Page for Base64Page {
-    fn handle_key(&self, key: ratatui::crossterm::event::KeyEvent) -> Option<Msg> {
-        let msg = match key {
-            key_code_char!('j') | key_code!(KeyCode::Down) => Base64Msg::SelectNextItem,
-            key_code_char!('k') | key_code!(KeyCode::Up) => Base64Msg::SelectPrevItem,
-            key_code_char!('l') | key_code!(KeyCode::Right) => Base64Msg::CurrentItemSelectNext,
-            key_code_char!('h') | key_code!(KeyCode::Left) => Base64Msg::CurrentItemSelectPrev,
-            key_code_char!('e', Ctrl) => Base64Msg::ScrollDown,
-            key_code_char!('y', Ctrl) => Base64Msg::ScrollUp,
-            key_code_char!('y') => Base64Msg::Copy,
-            key_code_char!('p') => Base64Msg::Paste,
+    fn handle_key(&self, key: KeyEvent, keymap: &Keymap) -> Option<Msg> {
+        if self.cur.prompt.is_some() {
+            let msg = match keymap.resolve_input(key) {
+                Some(Action::Confirm) => Base64Msg::LoadConfirm,
+                Some(Action::Cancel) => Base64Msg::LoadEnd,
+                Some(Action::SelectNextCandidate) => Base64Msg::LoadSelectNextCandidate,
+                Some(Action::SelectPrevCandidate) => Base64Msg::LoadSelectPrevCandidate,
+                _ => Base64Msg::LoadKeyEvent(key),
+            };
+            return Some(Msg::Page(PageMsg::Base64(msg)));
+        }
+        if self.cur.save_path.is_some() {
+            let msg = match keymap.resolve_input(key) {
+                Some(Action::Confirm) => Base64Msg::SaveConfirm,
+                Some(Action::Cancel) => Base64Msg::SaveEnd,
+                _ => Base64Msg::SaveKeyEvent(key),
+            };
+            return Some(Msg::Page(PageMsg::Base64(msg)));
+        }
+
+        let msg = match keymap.resolve(key)? {
+            Action::SelectNextItem => Base64Msg::SelectNextItem,
+            Action::SelectPrevItem => Base64Msg::SelectPrevItem,
+            Action::SelectNextValue => Base64Msg::CurrentItemSelectNext,
+            Action::SelectPrevValue => Base64Msg::CurrentItemSelectPrev,
+            Action::ScrollDown => Base64Msg::ScrollDown,
+            Action::ScrollUp => Base64Msg::ScrollUp,
+            Action::Copy => Base64Msg::Copy,
+            Action::Paste => Base64Msg::Paste,
+            Action::Save => Base64Msg::SaveStart,
+            Action::LoadFile => Base64Msg::LoadStart,
             _ => return None,
         };
         Some(Msg::Page(PageMsg::Base64(msg)))
@@ -117,60 +262,159 @@ impl Page for Base64Page {
                     return self.copy_to_clipboard();
                 }
                 Base64Msg::Paste => {
-                    self.paste_from_clipboard();
+                    return self.paste_from_clipboard();
+                }
+                Base64Msg::SelectItem(item) => {
+                    if self.is_visible(item) {
+                        self.cur.item = item;
+                    }
+                }
+                Base64Msg::SetMode(eod_sel) => {
+                    self.cur.eod_sel = eod_sel;
+                    self.ensure_item_visible();
+                    self.update_output();
+                }
+                Base64Msg::SetVariant(variant_sel) => {
+                    self.cur.variant_sel = variant_sel;
+                    self.update_output();
+                }
+                Base64Msg::SetPadding(padding_sel) => {
+                    self.cur.padding_sel = padding_sel;
+                    self.update_output();
+                }
+                Base64Msg::SetOutputView(view_sel) => {
+                    self.cur.view_sel = view_sel;
+                    self.cur.output_state = ScrollOutputState::default();
+                }
+                Base64Msg::SaveStart => {
+                    self.save_start();
+                }
+                Base64Msg::SaveEnd => {
+                    self.cur.save_path = None;
+                }
+                Base64Msg::SaveConfirm => {
+                    return self.save_to_file();
+                }
+                Base64Msg::SaveKeyEvent(key) => {
+                    if let Some(path) = &mut self.cur.save_path {
+                        path.handle_event(&ratatui::crossterm::event::Event::Key(key));
+                    }
+                }
+                Base64Msg::LoadStart => {
+                    self.cur.prompt = Some(PathPrompt::new());
+                }
+                Base64Msg::LoadEnd => {
+                    self.cur.prompt = None;
+                }
+                Base64Msg::LoadConfirm => {
+                    return self.load_confirm();
+                }
+                Base64Msg::LoadSelectNextCandidate => {
+                    if let Some(prompt) = &mut self.cur.prompt {
+                        prompt.select_next();
+                    }
+                }
+                Base64Msg::LoadSelectPrevCandidate => {
+                    if let Some(prompt) = &mut self.cur.prompt {
+                        prompt.select_prev();
+                    }
+                }
+                Base64Msg::LoadKeyEvent(key) => {
+                    if let Some(prompt) = &mut self.cur.prompt {
+                        prompt.handle_key(key);
+                    }
+                }
+                Base64Msg::LoadProgress => {
+                    return self.load_progress();
                 }
             }
         }
         None
     }
 
-    fn render(&mut self, f: &mut Frame, area: Rect) {
-        let chunks = vertical![==2, >=0, ==1, >=0].split(area);
+    fn render(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
+        // padding and output view are shown in the same row since only one of them is visible
+        let chunks = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .split(area);
 
         let eod_sel = Select::new(
             EncodeOrDecodeSelect::strings_vec(),
             self.cur.eod_sel.val(),
             self.cur.item == PageItems::EncodeOrDecode,
             self.focused,
+            theme,
         );
         f.render_widget(eod_sel, chunks[0]);
 
-        let input_text = self.cur.input.clone();
-        let input = ScrollOutput::new(input_text, self.focused, self.cur.item == PageItems::Input)
-            .title("Input");
-        f.render_stateful_widget(input, chunks[1], &mut self.cur.input_state);
-
-        if let InputStatus::Warn(status) = &self.cur.status {
-            let status_style = Style::default().fg(Color::Red);
-            let status = Paragraph::new(status.as_str()).block(
-                Block::default()
-                    .borders(Borders::empty())
-                    .style(status_style)
-                    .padding(Padding::horizontal(1)),
+        let variant_sel = Select::new(
+            VariantSelect::strings_vec(),
+            self.cur.variant_sel.val(),
+            self.cur.item == PageItems::Variant,
+            self.focused,
+            theme,
+        );
+        f.render_widget(variant_sel, chunks[1]);
+
+        if self.is_visible(PageItems::Padding) {
+            let padding_sel = Select::new(
+                PaddingSelect::strings_vec(),
+                self.cur.padding_sel.val(),
+                self.cur.item == PageItems::Padding,
+                self.focused,
+                theme,
             );
-            f.render_widget(status, chunks[2]);
+            f.render_widget(padding_sel, chunks[2]);
+        }
+        if self.is_visible(PageItems::OutputView) {
+            let view_sel = Select::new(
+                OutputViewSelect::strings_vec(),
+                self.cur.view_sel.val(),
+                self.cur.item == PageItems::OutputView,
+                self.focused,
+                theme,
+            );
+            f.render_widget(view_sel, chunks[2]);
         }
 
-        let output_style = if self.focused {
-            if self.cur.item == PageItems::Output {
-                Style::default().fg(Color::Blue)
-            } else {
-                Style::default().fg(Color::Reset)
-            }
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
-
-        let output_text = self.cur.output.clone();
-        let output = Paragraph::new(output_text)
-            .block(
+        if let Some(file) = &self.cur.file {
+            let input_style = theme.item_style(self.focused, self.cur.item == PageItems::Input);
+            let input = Paragraph::new(file.info_lines(theme)).block(
                 Block::bordered()
-                    .style(output_style)
-                    .title("Output")
+                    .style(input_style)
+                    .title("Input (file)")
                     .padding(Padding::horizontal(1)),
+            );
+            f.render_widget(input, chunks[3]);
+        } else {
+            let input_text = self.cur.input.clone();
+            let input = ScrollOutput::new(
+                input_text,
+                self.focused,
+                self.cur.item == PageItems::Input,
+                theme,
             )
-            .wrap(Wrap { trim: false });
-        f.render_widget(output, chunks[3]);
+            .title("Input");
+            f.render_stateful_widget(input, chunks[3], &mut self.cur.input_state);
+        }
+
+        if let Some(path) = &self.cur.save_path {
+            self.render_save_prompt(f, chunks[4], theme, path);
+        } else {
+            self.render_status(f, chunks[4], theme);
+        }
+
+        self.render_output(f, chunks[5], theme);
+
+        if let Some(prompt) = &self.cur.prompt {
+            prompt.render(f, chunks[3].union(chunks[5]), theme);
+        }
     }
 
     fn focus(&mut self) {
@@ -179,32 +423,177 @@ impl Page for Base64Page {
 
     fn unfocus(&mut self) {
         self.focused = false;
+        self.cur.save_path = None;
+        self.cur.prompt = None;
     }
 
-    fn helps(&self) -> Vec<&str> {
-        let mut helps: Vec<&str> = Vec::new();
-        helps.push("<j/k> Select item");
-        if matches!(self.cur.item, PageItems::EncodeOrDecode) {
-            helps.push("<h/l> Select current item value");
+    fn helps(&self) -> Vec<Help> {
+        let mut helps: Vec<Help> = Vec::new();
+        if self.cur.prompt.is_some() {
+            helps.push(Help::new(
+                &[Action::SelectNextCandidate, Action::SelectPrevCandidate],
+                "Select candidate",
+            ));
+            helps.push(Help::new(&[Action::Confirm], "Load"));
+            helps.push(Help::new(&[Action::Cancel], "Cancel"));
+            return helps;
+        }
+        if self.cur.save_path.is_some() {
+            helps.push(Help::new(&[Action::Confirm], "Save"));
+            helps.push(Help::new(&[Action::Cancel], "Cancel"));
+            return helps;
+        }
+        helps.push(Help::new(
+            &[Action::SelectNextItem, Action::SelectPrevItem],
+            "Select item",
+        ));
+        if matches!(
+            self.cur.item,
+            PageItems::EncodeOrDecode
+                | PageItems::Variant
+                | PageItems::Padding
+                | PageItems::OutputView
+        ) {
+            helps.push(Help::new(
+                &[Action::SelectPrevValue, Action::SelectNextValue],
+                "Select current item value",
+            ));
         }
         if matches!(self.cur.item, PageItems::Output) {
-            helps.push("<y> Copy to clipboard");
+            if self.output_scrollable() {
+                helps.push(Help::new(
+                    &[Action::ScrollDown, Action::ScrollUp],
+                    "Scroll down/up",
+                ));
+            }
+            helps.push(Help::new(&[Action::Copy], "Copy to clipboard"));
+            helps.push(Help::new(&[Action::Save], "Save to file"));
         }
         if matches!(self.cur.item, PageItems::Input) {
-            helps.push("<C-e/C-y> Scroll down/up");
-            helps.push("<p> Paste from clipboard");
+            helps.push(Help::new(
+                &[Action::ScrollDown, Action::ScrollUp],
+                "Scroll down/up",
+            ));
+            helps.push(Help::new(&[Action::Paste], "Paste from clipboard"));
+            helps.push(Help::new(&[Action::LoadFile], "Load from file"));
         }
         helps
     }
+
+    fn commands(&self) -> Vec<Command> {
+        let msg = |msg| Msg::Page(PageMsg::Base64(msg));
+        let mut commands: Vec<Command> = EncodeOrDecodeSelect::vars_vec()
+            .into_iter()
+            .map(|eod_sel| {
+                let label = format!("Base64: {}", eod_sel.str().to_lowercase());
+                Command::new(label, vec![msg(Base64Msg::SetMode(eod_sel))])
+            })
+            .collect();
+        for variant_sel in VariantSelect::vars_vec() {
+            let label = format!("Base64: variant {}", variant_sel.str());
+            commands.push(Command::new(
+                label,
+                vec![msg(Base64Msg::SetVariant(variant_sel))],
+            ));
+        }
+        if self.is_visible(PageItems::Padding) {
+            for padding_sel in PaddingSelect::vars_vec() {
+                let label = format!("Base64: {}", padding_sel.str().to_lowercase());
+                commands.push(Command::new(
+                    label,
+                    vec![msg(Base64Msg::SetPadding(padding_sel))],
+                ));
+            }
+        }
+        if self.is_visible(PageItems::OutputView) {
+            for view_sel in OutputViewSelect::vars_vec() {
+                let label = format!("Base64: view {}", view_sel.str().to_lowercase());
+                commands.push(Command::new(
+                    label,
+                    vec![msg(Base64Msg::SetOutputView(view_sel))],
+                ));
+            }
+        }
+        commands.push(Command::new(
+            "Base64: paste input",
+            vec![
+                msg(Base64Msg::SelectItem(PageItems::Input)),
+                msg(Base64Msg::Paste),
+            ],
+        ));
+        commands.push(Command::new(
+            "Base64: load file",
+            vec![msg(Base64Msg::LoadStart)],
+        ));
+        commands.push(Command::new(
+            "Base64: copy output",
+            vec![
+                msg(Base64Msg::SelectItem(PageItems::Output)),
+                msg(Base64Msg::Copy),
+            ],
+        ));
+        commands.push(Command::new(
+            "Base64: save output to file",
+            vec![msg(Base64Msg::SaveStart)],
+        ));
+        commands
+    }
+
+    fn save(&self, session: &mut Session) {
+        session.base64 = Some(Base64Session {
+            mode: self.cur.eod_sel,
+            variant: self.cur.variant_sel,
+            padding: self.cur.padding_sel,
+            view: self.cur.view_sel,
+            input: self.cur.input.clone(),
+        });
+    }
+
+    fn restore(&mut self, session: &Session) {
+        let Some(saved) = &session.base64 else {
+            return;
+        };
+        self.cur.eod_sel = saved.mode;
+        self.cur.variant_sel = saved.variant;
+        self.cur.padding_sel = saved.padding;
+        self.cur.view_sel = saved.view;
+        self.cur.input = saved.input.clone();
+        self.update_output();
+    }
 }
 
 impl Base64Page {
     fn select_next_item(&mut self) {
         self.cur.item = self.cur.item.next();
+        while !self.is_visible(self.cur.item) {
+            self.cur.item = self.cur.item.next();
+        }
     }
 
     fn select_prev_item(&mut self) {
         self.cur.item = self.cur.item.prev();
+        while !self.is_visible(self.cur.item) {
+            self.cur.item = self.cur.item.prev();
+        }
+    }
+
+    fn ensure_item_visible(&mut self) {
+        if !self.is_visible(self.cur.item) {
+            self.cur.item = PageItems::EncodeOrDecode;
+        }
+    }
+
+    /// Padding is only chosen on encode, decode accepts both.
+    /// The encoded output is always text, so it has no other views.
+    fn is_visible(&self, item: PageItems) -> bool {
+        match item {
+            PageItems::Padding => self.cur.eod_sel == EncodeOrDecodeSelect::Encode,
+            PageItems::OutputView => self.cur.eod_sel == EncodeOrDecodeSelect::Decode,
+            PageItems::EncodeOrDecode
+            | PageItems::Variant
+            | PageItems::Input
+            | PageItems::Output => true,
+        }
     }
 
     fn current_item_select_next(&mut self) {
@@ -213,6 +602,18 @@ impl Base64Page {
                 self.cur.eod_sel.next_mut();
                 self.update_output();
             }
+            PageItems::Variant => {
+                self.cur.variant_sel.next_mut();
+                self.update_output();
+            }
+            PageItems::Padding => {
+                self.cur.padding_sel.next_mut();
+                self.update_output();
+            }
+            PageItems::OutputView => {
+                self.cur.view_sel.next_mut();
+                self.cur.output_state = ScrollOutputState::default();
+            }
             PageItems::Input => {}
             PageItems::Output => {}
         }
@@ -224,23 +625,65 @@ impl Base64Page {
                 self.cur.eod_sel.prev_mut();
                 self.update_output();
             }
+            PageItems::Variant => {
+                self.cur.variant_sel.prev_mut();
+                self.update_output();
+            }
+            PageItems::Padding => {
+                self.cur.padding_sel.prev_mut();
+                self.update_output();
+            }
+            PageItems::OutputView => {
+                self.cur.view_sel.prev_mut();
+                self.cur.output_state = ScrollOutputState::default();
+            }
             PageItems::Input => {}
             PageItems::Output => {}
         }
     }
 
     fn scroll_down(&mut self) {
-        if !matches!(self.cur.item, PageItems::Input) || self.cur.input.is_empty() {
-            return;
+        match self.cur.item {
+            PageItems::Input if !self.cur.input.is_empty() => self.cur.input_state.scroll_down(),
+            PageItems::Output if self.output_scrollable() => self.cur.output_state.scroll_down(),
+            _ => {}
         }
-        self.cur.input_state.scroll_down();
     }
 
     fn scroll_up(&mut self) {
-        if !matches!(self.cur.item, PageItems::Input) || self.cur.input.is_empty() {
-            return;
+        match self.cur.item {
+            PageItems::Input if !self.cur.input.is_empty() => self.cur.input_state.scroll_up(),
+            PageItems::Output if self.output_scrollable() => self.cur.output_state.scroll_up(),
+            _ => {}
+        }
+    }
+
+    /// Returns the current view of the output. The views other than text are only for decode.
+    fn current_view(&self) -> OutputViewSelect {
+        if self.is_visible(PageItems::OutputView) {
+            self.cur.view_sel
+        } else {
+            OutputViewSelect::Text
+        }
+    }
+
+    /// Multi-line views are scrolled, the others are wrapped to fit.
+    fn output_scrollable(&self) -> bool {
+        matches!(
+            self.current_view(),
+            OutputViewSelect::HexDump | OutputViewSelect::CArray | OutputViewSelect::RustArray
+        )
+    }
+
+    fn output_text(&self) -> String {
+        let bytes = &self.cur.bytes;
+        match self.current_view() {
+            OutputViewSelect::Text => self.cur.output.clone(),
+            OutputViewSelect::HexDump => bytes::hex_dump(bytes),
+            OutputViewSelect::Hex => bytes::hex_string(bytes),
+            OutputViewSelect::CArray => bytes::c_array(bytes),
+            OutputViewSelect::RustArray => bytes::rust_array(bytes),
         }
-        self.cur.input_state.scroll_up();
     }
 
     fn copy_to_clipboard(&self) -> Option<Msg> {
@@ -248,39 +691,196 @@ impl Base64Page {
             return None;
         }
 
-        let text = &self.cur.output;
-        util::copy_to_clipboard(text)
+        if self.current_view() == OutputViewSelect::Text && !self.is_utf8() {
+            let msg = "Output is not valid UTF-8, copy it from another view or save it to a file";
+            return Some(Msg::NotifyWarn(msg.into()));
+        }
+        util::copy_to_clipboard(&self.output_text())
     }
 
-    fn paste_from_clipboard(&mut self) {
-        if matches!(self.cur.item, PageItems::Input) {
-            self.cur.input = util::paste_from_clipboard().unwrap();
-            self.update_output();
+    fn is_utf8(&self) -> bool {
+        std::str::from_utf8(&self.cur.bytes).is_ok()
+    }
+
+    fn save_start(&mut self) {
+        if !self.cur.bytes.is_empty() {
+            self.cur.save_path = Some(Input::default());
         }
     }
 
-    fn update_output(&mut self) {
-        (self.cur.output, self.cur.status) = calculate_base64(&self.cur.input, self.cur.eod_sel);
+    /// Saves the output as is, the decoded bytes on decode regardless of the view.
+    fn save_to_file(&mut self) -> Option<Msg> {
+        let path = self.cur.save_path.take()?;
+        let path = path.value().trim();
+        if path.is_empty() {
+            return None;
+        }
+        Some(util::save_to_file(path, &self.cur.bytes))
     }
-}
 
-fn calculate_base64(input: &str, eod_sel: EncodeOrDecodeSelect) -> (String, InputStatus) {
-    match eod_sel {
-        EncodeOrDecodeSelect::Encode => {
-            (general_purpose::STANDARD.encode(input), InputStatus::None)
-        }
-        EncodeOrDecodeSelect::Decode => {
-            if let Ok(decoded) = general_purpose::STANDARD.decode(input) {
-                (
-                    String::from_utf8_lossy(&decoded).to_string(),
-                    InputStatus::None,
-                )
-            } else {
-                (
-                    String::new(),
-                    InputStatus::Warn("invalid base64 input".into()),
-                )
+    fn paste_from_clipboard(&mut self) -> Option<Msg> {
+        if !matches!(self.cur.item, PageItems::Input) {
+            return None;
+        }
+
+        self.cur.input = match util::paste_from_clipboard() {
+            Ok(text) => text,
+            Err(msg) => return Some(msg),
+        };
+        self.cur.file = None;
+        self.update_output();
+        None
+    }
+
+    fn load_confirm(&mut self) -> Option<Msg> {
+        let PromptResult::Open(path) = self.cur.prompt.as_mut()?.confirm() else {
+            return None;
+        };
+        self.cur.prompt = None;
+        if path.is_empty() {
+            return None;
+        }
+
+        let msg = Msg::Page(PageMsg::Base64(Base64Msg::LoadProgress));
+        match FileLoad::start(&path, Some(FILE_SIZE_MAX), msg, file::read_all) {
+            Ok(file) => {
+                self.cur.file = Some(file);
+                self.cur.input.clear();
+                self.cur.input_state = ScrollOutputState::default();
+                self.update_output();
+                None
+            }
+            Err(e) => Some(Msg::NotifyError(e)),
+        }
+    }
+
+    fn load_progress(&mut self) -> Option<Msg> {
+        let file = self.cur.file.as_mut()?;
+        if !file.poll() {
+            return None;
+        }
+        if let LoadState::Failed(e) = &file.state {
+            return Some(Msg::NotifyError(e.clone()));
+        }
+        self.update_output();
+        None
+    }
+
+    /// The loaded file is encoded as is, it is decoded only if it is text.
+    fn update_output(&mut self) {
+        let input = match &self.cur.file {
+            Some(file) => match file.bytes().map(std::str::from_utf8) {
+                Some(Ok(text)) => Cow::Borrowed(text),
+                Some(Err(_)) if self.cur.eod_sel == EncodeOrDecodeSelect::Decode => {
+                    self.cur.output.clear();
+                    self.cur.bytes.clear();
+                    self.cur.status = InputStatus::Warn("File is not valid UTF-8 text".into());
+                    return;
+                }
+                Some(Err(_)) => Cow::Borrowed(""),
+                None => {
+                    self.cur.output.clear();
+                    self.cur.bytes.clear();
+                    self.cur.status = InputStatus::None;
+                    return;
+                }
+            },
+            None => Cow::Borrowed(self.cur.input.as_str()),
+        };
+        let req = Base64Request {
+            input: &input,
+            mode: self.cur.eod_sel.into(),
+            variant: self.cur.variant_sel.variant(),
+            unpadded: self.cur.padding_sel == PaddingSelect::WithoutPadding,
+        };
+        let result = match self.cur.file.as_ref().and_then(FileLoad::bytes) {
+            Some(bytes) if req.mode == Mode::Encode => Ok(base64::encode(
+                bytes,
+                req.variant.unwrap_or_default(),
+                req.unpadded,
+            )),
+            _ => base64::calculate(&req),
+        };
+        (self.cur.output, self.cur.bytes, self.cur.status) = match result {
+            Ok(res) if req.mode == Mode::Decode && !input.trim().is_empty() => {
+                let verb = if req.variant.is_none() {
+                    "Detected"
+                } else {
+                    "Decoded as"
+                };
+                let padding = if res.padded { "padded" } else { "unpadded" };
+                let utf8 = if res.is_utf8() {
+                    "valid UTF-8"
+                } else {
+                    "not valid UTF-8"
+                };
+                let variant = match res.variant.ambiguous_with() {
+                    // crypt is only decoded when chosen, since it is detected as bcrypt
+                    Some(other) if req.variant.is_none() => format!("{} (or {other})", res.variant),
+                    _ => res.variant.to_string(),
+                };
+                let len = res.bytes.len();
+                let status = format!("{verb} {variant}, {padding} / {len} bytes, {utf8}");
+                (res.output, res.bytes, InputStatus::Info(status))
             }
+            Ok(res) => (res.output, res.bytes, InputStatus::None),
+            Err(e) => (String::new(), Vec::new(), InputStatus::Warn(e.to_string())),
+        };
+        self.cur.output_state = ScrollOutputState::default();
+    }
+
+    fn render_status(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let (status, status_style) = match &self.cur.status {
+            InputStatus::None => return,
+            InputStatus::Info(status) => (status, theme.info),
+            InputStatus::Warn(status) => (status, theme.error),
+        };
+        let status = Paragraph::new(status.as_str()).block(
+            Block::default()
+                .borders(Borders::empty())
+                .style(status_style)
+                .padding(Padding::horizontal(1)),
+        );
+        f.render_widget(status, area);
+    }
+
+    fn render_save_prompt(&self, f: &mut Frame, area: Rect, theme: &Theme, path: &Input) {
+        let label = "Save to: ";
+        let prompt = Paragraph::new(format!("{label}{}", path.value())).block(
+            Block::default()
+                .style(theme.text)
+                .padding(Padding::horizontal(1)),
+        );
+        f.render_widget(prompt, area);
+
+        let max_cursor = area.width.saturating_sub(2 + label.len() as u16);
+        let x = area.x + 1 + label.len() as u16 + (path.visual_cursor() as u16).min(max_cursor);
+        f.set_cursor_position((x, area.y));
+    }
+
+    fn render_output(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let output_text = self.output_text();
+        if self.output_scrollable() {
+            let output = ScrollOutput::new(
+                output_text,
+                self.focused,
+                self.cur.item == PageItems::Output,
+                theme,
+            )
+            .title("Output");
+            f.render_stateful_widget(output, area, &mut self.cur.output_state);
+            return;
         }
+
+        let output_style = theme.item_style(self.focused, self.cur.item == PageItems::Output);
+        let output = Paragraph::new(output_text)
+            .block(
+                Block::bordered()
+                    .style(output_style)
+                    .title("Output")
+                    .padding(Padding::horizontal(1)),
+            )
+            .wrap(Wrap { trim: false });
+        f.render_widget(output, area);
     }
 }
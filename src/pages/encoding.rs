@@ -0,0 +1,687 @@
+use btox::{
+    bytes,
+    encoding::{self, Codec, EncodingRequest, Mode},
+};
+use itsuki::zero_indexed_enum;
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::{Constraint, Layout, Rect},
+    widgets::{Block, Borders, Padding, Paragraph, Wrap},
+    Frame,
+};
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use crate::{
+    config::EncodingConfig,
+    fn_next_prev_mut, fn_str_map,
+    keymap::{Action, Help, Keymap},
+    msg::{EncodingMsg, Msg, PageMsg},
+    pages::{page::Page, util},
+    palette::Command,
+    session::{EncodingSession, Session},
+    theme::Theme,
+    widget::{
+        scroll::{ScrollOutput, ScrollOutputState},
+        select::Select,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+pub struct EncodingPage {
+    focused: bool,
+    cur: CurrentStatus,
+}
+
+#[derive(Default)]
+struct CurrentStatus {
+    item: PageItems,
+    eod_sel: EncodeOrDecodeSelect,
+    codec_sel: CodecSelect,
+    input: String,
+    input_state: ScrollOutputState,
+    output: String,
+    bytes: Vec<u8>,
+    view_sel: OutputViewSelect,
+    output_state: ScrollOutputState,
+    status: InputStatus,
+    /// The path being entered to save the output to, `None` if not saving.
+    save_path: Option<Input>,
+}
+
+impl EncodingPage {
+    pub fn new(focused: bool, config: &EncodingConfig) -> EncodingPage {
+        EncodingPage {
+            focused,
+            cur: CurrentStatus {
+                eod_sel: config.mode,
+                codec_sel: config.codec,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+#[derive(Default)]
+enum InputStatus {
+    #[default]
+    None,
+    Info(String),
+    Warn(String),
+}
+
+#[derive(Default)]
+#[zero_indexed_enum]
+enum PageItems {
+    #[default]
+    EncodeOrDecode,
+    Codec,
+    OutputView,
+    Input,
+    Output,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[zero_indexed_enum]
+enum EncodeOrDecodeSelect {
+    #[default]
+    Encode,
+    Decode,
+}
+
+impl EncodeOrDecodeSelect {
+    fn_str_map! {
+        EncodeOrDecodeSelect::Encode => "Encode",
+        EncodeOrDecodeSelect::Decode => "Decode",
+    }
+
+    fn_next_prev_mut! {}
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[zero_indexed_enum]
+enum CodecSelect {
+    #[default]
+    HexLower,
+    HexUpper,
+    Base32,
+    Base32Hex,
+    Crockford,
+    Base58Bitcoin,
+    Base58Flickr,
+    Z85,
+    Ascii85,
+}
+
+impl CodecSelect {
+    fn_str_map! {
+        CodecSelect::HexLower => "Hex (lowercase)",
+        CodecSelect::HexUpper => "Hex (uppercase)",
+        CodecSelect::Base32 => "Base32",
+        CodecSelect::Base32Hex => "base32hex",
+        CodecSelect::Crockford => "Crockford Base32",
+        CodecSelect::Base58Bitcoin => "Base58 (Bitcoin)",
+        CodecSelect::Base58Flickr => "Base58 (Flickr)",
+        CodecSelect::Z85 => "Z85",
+        CodecSelect::Ascii85 => "Ascii85",
+    }
+
+    fn_next_prev_mut! {}
+}
+
+impl From<CodecSelect> for Codec {
+    fn from(codec_sel: CodecSelect) -> Self {
+        match codec_sel {
+            CodecSelect::HexLower => Codec::HexLower,
+            CodecSelect::HexUpper => Codec::HexUpper,
+            CodecSelect::Base32 => Codec::Base32,
+            CodecSelect::Base32Hex => Codec::Base32Hex,
+            CodecSelect::Crockford => Codec::Crockford,
+            CodecSelect::Base58Bitcoin => Codec::Base58Bitcoin,
+            CodecSelect::Base58Flickr => Codec::Base58Flickr,
+            CodecSelect::Z85 => Codec::Z85,
+            CodecSelect::Ascii85 => Codec::Ascii85,
+        }
+    }
+}
+
+/// How the decoded bytes are shown, other than text they are shown as is even if not valid UTF-8.
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[zero_indexed_enum]
+enum OutputViewSelect {
+    #[default]
+    Text,
+    HexDump,
+    Hex,
+    CArray,
+    RustArray,
+}
+
+impl OutputViewSelect {
+    fn_str_map! {
+        OutputViewSelect::Text => "Text",
+        OutputViewSelect::HexDump => "Hex dump",
+        OutputViewSelect::Hex => "Hex",
+        OutputViewSelect::CArray => "C array",
+        OutputViewSelect::RustArray => "Rust array",
+    }
+
+    fn_next_prev_mut! {}
+}
+
+impl From<EncodeOrDecodeSelect> for Mode {
+    fn from(eod_sel: EncodeOrDecodeSelect) -> Self {
+        match eod_sel {
+            EncodeOrDecodeSelect::Encode => Mode::Encode,
+            EncodeOrDecodeSelect::Decode => Mode::Decode,
+        }
+    }
+}
+
+impl Page for EncodingPage {
+    fn handle_key(&self, key: KeyEvent, keymap: &Keymap) -> Option<Msg> {
+        if self.cur.save_path.is_some() {
+            let msg = match keymap.resolve_input(key) {
+                Some(Action::Confirm) => EncodingMsg::SaveConfirm,
+                Some(Action::Cancel) => EncodingMsg::SaveEnd,
+                _ => EncodingMsg::SaveKeyEvent(key),
+            };
+            return Some(Msg::Page(PageMsg::Encoding(msg)));
+        }
+
+        let msg = match keymap.resolve(key)? {
+            Action::SelectNextItem => EncodingMsg::SelectNextItem,
+            Action::SelectPrevItem => EncodingMsg::SelectPrevItem,
+            Action::SelectNextValue => EncodingMsg::CurrentItemSelectNext,
+            Action::SelectPrevValue => EncodingMsg::CurrentItemSelectPrev,
+            Action::ScrollDown => EncodingMsg::ScrollDown,
+            Action::ScrollUp => EncodingMsg::ScrollUp,
+            Action::Copy => EncodingMsg::Copy,
+            Action::Paste => EncodingMsg::Paste,
+            Action::Save => EncodingMsg::SaveStart,
+            _ => return None,
+        };
+        Some(Msg::Page(PageMsg::Encoding(msg)))
+    }
+
+    fn update(&mut self, msg: PageMsg) -> Option<Msg> {
+        if let PageMsg::Encoding(msg) = msg {
+            match msg {
+                EncodingMsg::SelectNextItem => {
+                    self.select_next_item();
+                }
+                EncodingMsg::SelectPrevItem => {
+                    self.select_prev_item();
+                }
+                EncodingMsg::CurrentItemSelectNext => {
+                    self.current_item_select_next();
+                }
+                EncodingMsg::CurrentItemSelectPrev => {
+                    self.current_item_select_prev();
+                }
+                EncodingMsg::ScrollDown => {
+                    self.scroll_down();
+                }
+                EncodingMsg::ScrollUp => {
+                    self.scroll_up();
+                }
+                EncodingMsg::Copy => {
+                    return self.copy_to_clipboard();
+                }
+                EncodingMsg::Paste => {
+                    return self.paste_from_clipboard();
+                }
+                EncodingMsg::SelectItem(item) => {
+                    if self.is_visible(item) {
+                        self.cur.item = item;
+                    }
+                }
+                EncodingMsg::SetMode(eod_sel) => {
+                    self.cur.eod_sel = eod_sel;
+                    self.ensure_item_visible();
+                    self.update_output();
+                }
+                EncodingMsg::SetCodec(codec_sel) => {
+                    self.cur.codec_sel = codec_sel;
+                    self.update_output();
+                }
+                EncodingMsg::SetOutputView(view_sel) => {
+                    self.cur.view_sel = view_sel;
+                    self.cur.output_state = ScrollOutputState::default();
+                }
+                EncodingMsg::SaveStart => {
+                    self.save_start();
+                }
+                EncodingMsg::SaveEnd => {
+                    self.cur.save_path = None;
+                }
+                EncodingMsg::SaveConfirm => {
+                    return self.save_to_file();
+                }
+                EncodingMsg::SaveKeyEvent(key) => {
+                    if let Some(path) = &mut self.cur.save_path {
+                        path.handle_event(&ratatui::crossterm::event::Event::Key(key));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn render(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let view_height = if self.is_visible(PageItems::OutputView) {
+            2
+        } else {
+            0
+        };
+        let chunks = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(view_height),
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+        let eod_sel = Select::new(
+            EncodeOrDecodeSelect::strings_vec(),
+            self.cur.eod_sel.val(),
+            self.cur.item == PageItems::EncodeOrDecode,
+            self.focused,
+            theme,
+        );
+        f.render_widget(eod_sel, chunks[0]);
+
+        let codec_sel = Select::new(
+            CodecSelect::strings_vec(),
+            self.cur.codec_sel.val(),
+            self.cur.item == PageItems::Codec,
+            self.focused,
+            theme,
+        );
+        f.render_widget(codec_sel, chunks[1]);
+
+        if self.is_visible(PageItems::OutputView) {
+            let view_sel = Select::new(
+                OutputViewSelect::strings_vec(),
+                self.cur.view_sel.val(),
+                self.cur.item == PageItems::OutputView,
+                self.focused,
+                theme,
+            );
+            f.render_widget(view_sel, chunks[2]);
+        }
+
+        let input_text = self.cur.input.clone();
+        let input = ScrollOutput::new(
+            input_text,
+            self.focused,
+            self.cur.item == PageItems::Input,
+            theme,
+        )
+        .title("Input");
+        f.render_stateful_widget(input, chunks[3], &mut self.cur.input_state);
+
+        if let Some(path) = &self.cur.save_path {
+            self.render_save_prompt(f, chunks[4], theme, path);
+        } else {
+            self.render_status(f, chunks[4], theme);
+        }
+
+        self.render_output(f, chunks[5], theme);
+    }
+
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = false;
+        self.cur.save_path = None;
+    }
+
+    fn helps(&self) -> Vec<Help> {
+        let mut helps: Vec<Help> = Vec::new();
+        if self.cur.save_path.is_some() {
+            helps.push(Help::new(&[Action::Confirm], "Save"));
+            helps.push(Help::new(&[Action::Cancel], "Cancel"));
+            return helps;
+        }
+        helps.push(Help::new(
+            &[Action::SelectNextItem, Action::SelectPrevItem],
+            "Select item",
+        ));
+        if matches!(
+            self.cur.item,
+            PageItems::EncodeOrDecode | PageItems::Codec | PageItems::OutputView
+        ) {
+            helps.push(Help::new(
+                &[Action::SelectPrevValue, Action::SelectNextValue],
+                "Select current item value",
+            ));
+        }
+        if matches!(self.cur.item, PageItems::Output) {
+            if self.output_scrollable() {
+                helps.push(Help::new(
+                    &[Action::ScrollDown, Action::ScrollUp],
+                    "Scroll down/up",
+                ));
+            }
+            helps.push(Help::new(&[Action::Copy], "Copy to clipboard"));
+            helps.push(Help::new(&[Action::Save], "Save to file"));
+        }
+        if matches!(self.cur.item, PageItems::Input) {
+            helps.push(Help::new(
+                &[Action::ScrollDown, Action::ScrollUp],
+                "Scroll down/up",
+            ));
+            helps.push(Help::new(&[Action::Paste], "Paste from clipboard"));
+        }
+        helps
+    }
+
+    fn commands(&self) -> Vec<Command> {
+        let msg = |msg| Msg::Page(PageMsg::Encoding(msg));
+        let mut commands: Vec<Command> = EncodeOrDecodeSelect::vars_vec()
+            .into_iter()
+            .map(|eod_sel| {
+                let label = format!("Encoding: {}", eod_sel.str().to_lowercase());
+                Command::new(label, vec![msg(EncodingMsg::SetMode(eod_sel))])
+            })
+            .collect();
+        for codec_sel in CodecSelect::vars_vec() {
+            let label = format!("Encoding: codec {}", codec_sel.str());
+            commands.push(Command::new(
+                label,
+                vec![msg(EncodingMsg::SetCodec(codec_sel))],
+            ));
+        }
+        if self.is_visible(PageItems::OutputView) {
+            for view_sel in OutputViewSelect::vars_vec() {
+                let label = format!("Encoding: view {}", view_sel.str().to_lowercase());
+                commands.push(Command::new(
+                    label,
+                    vec![msg(EncodingMsg::SetOutputView(view_sel))],
+                ));
+            }
+        }
+        commands.push(Command::new(
+            "Encoding: paste input",
+            vec![
+                msg(EncodingMsg::SelectItem(PageItems::Input)),
+                msg(EncodingMsg::Paste),
+            ],
+        ));
+        commands.push(Command::new(
+            "Encoding: copy output",
+            vec![
+                msg(EncodingMsg::SelectItem(PageItems::Output)),
+                msg(EncodingMsg::Copy),
+            ],
+        ));
+        commands.push(Command::new(
+            "Encoding: save output to file",
+            vec![msg(EncodingMsg::SaveStart)],
+        ));
+        commands
+    }
+
+    fn save(&self, session: &mut Session) {
+        session.encoding = Some(EncodingSession {
+            mode: self.cur.eod_sel,
+            codec: self.cur.codec_sel,
+            view: self.cur.view_sel,
+            input: self.cur.input.clone(),
+        });
+    }
+
+    fn restore(&mut self, session: &Session) {
+        let Some(saved) = &session.encoding else {
+            return;
+        };
+        self.cur.eod_sel = saved.mode;
+        self.cur.codec_sel = saved.codec;
+        self.cur.view_sel = saved.view;
+        self.cur.input = saved.input.clone();
+        self.update_output();
+    }
+}
+
+impl EncodingPage {
+    fn select_next_item(&mut self) {
+        self.cur.item = self.cur.item.next();
+        while !self.is_visible(self.cur.item) {
+            self.cur.item = self.cur.item.next();
+        }
+    }
+
+    fn select_prev_item(&mut self) {
+        self.cur.item = self.cur.item.prev();
+        while !self.is_visible(self.cur.item) {
+            self.cur.item = self.cur.item.prev();
+        }
+    }
+
+    fn ensure_item_visible(&mut self) {
+        if !self.is_visible(self.cur.item) {
+            self.cur.item = PageItems::EncodeOrDecode;
+        }
+    }
+
+    /// The encoded output is always text, so it has no other views.
+    fn is_visible(&self, item: PageItems) -> bool {
+        match item {
+            PageItems::OutputView => self.cur.eod_sel == EncodeOrDecodeSelect::Decode,
+            PageItems::EncodeOrDecode | PageItems::Codec | PageItems::Input | PageItems::Output => {
+                true
+            }
+        }
+    }
+
+    fn current_item_select_next(&mut self) {
+        match self.cur.item {
+            PageItems::EncodeOrDecode => {
+                self.cur.eod_sel.next_mut();
+                self.update_output();
+            }
+            PageItems::Codec => {
+                self.cur.codec_sel.next_mut();
+                self.update_output();
+            }
+            PageItems::OutputView => {
+                self.cur.view_sel.next_mut();
+                self.cur.output_state = ScrollOutputState::default();
+            }
+            PageItems::Input => {}
+            PageItems::Output => {}
+        }
+    }
+
+    fn current_item_select_prev(&mut self) {
+        match self.cur.item {
+            PageItems::EncodeOrDecode => {
+                self.cur.eod_sel.prev_mut();
+                self.update_output();
+            }
+            PageItems::Codec => {
+                self.cur.codec_sel.prev_mut();
+                self.update_output();
+            }
+            PageItems::OutputView => {
+                self.cur.view_sel.prev_mut();
+                self.cur.output_state = ScrollOutputState::default();
+            }
+            PageItems::Input => {}
+            PageItems::Output => {}
+        }
+    }
+
+    fn scroll_down(&mut self) {
+        match self.cur.item {
+            PageItems::Input if !self.cur.input.is_empty() => self.cur.input_state.scroll_down(),
+            PageItems::Output if self.output_scrollable() => self.cur.output_state.scroll_down(),
+            _ => {}
+        }
+    }
+
+    fn scroll_up(&mut self) {
+        match self.cur.item {
+            PageItems::Input if !self.cur.input.is_empty() => self.cur.input_state.scroll_up(),
+            PageItems::Output if self.output_scrollable() => self.cur.output_state.scroll_up(),
+            _ => {}
+        }
+    }
+
+    /// Returns the current view of the output. The views other than text are only for decode.
+    fn current_view(&self) -> OutputViewSelect {
+        if self.is_visible(PageItems::OutputView) {
+            self.cur.view_sel
+        } else {
+            OutputViewSelect::Text
+        }
+    }
+
+    /// Multi-line views are scrolled, the others are wrapped to fit.
+    fn output_scrollable(&self) -> bool {
+        matches!(
+            self.current_view(),
+            OutputViewSelect::HexDump | OutputViewSelect::CArray | OutputViewSelect::RustArray
+        )
+    }
+
+    fn output_text(&self) -> String {
+        let bytes = &self.cur.bytes;
+        match self.current_view() {
+            OutputViewSelect::Text => self.cur.output.clone(),
+            OutputViewSelect::HexDump => bytes::hex_dump(bytes),
+            OutputViewSelect::Hex => bytes::hex_string(bytes),
+            OutputViewSelect::CArray => bytes::c_array(bytes),
+            OutputViewSelect::RustArray => bytes::rust_array(bytes),
+        }
+    }
+
+    fn copy_to_clipboard(&self) -> Option<Msg> {
+        if !matches!(self.cur.item, PageItems::Output) {
+            return None;
+        }
+
+        if self.current_view() == OutputViewSelect::Text && !self.is_utf8() {
+            let msg = "Output is not valid UTF-8, copy it from another view or save it to a file";
+            return Some(Msg::NotifyWarn(msg.into()));
+        }
+        util::copy_to_clipboard(&self.output_text())
+    }
+
+    fn is_utf8(&self) -> bool {
+        std::str::from_utf8(&self.cur.bytes).is_ok()
+    }
+
+    fn save_start(&mut self) {
+        if !self.cur.bytes.is_empty() {
+            self.cur.save_path = Some(Input::default());
+        }
+    }
+
+    /// Saves the output as is, the decoded bytes on decode regardless of the view.
+    fn save_to_file(&mut self) -> Option<Msg> {
+        let path = self.cur.save_path.take()?;
+        let path = path.value().trim();
+        if path.is_empty() {
+            return None;
+        }
+        Some(util::save_to_file(path, &self.cur.bytes))
+    }
+
+    fn paste_from_clipboard(&mut self) -> Option<Msg> {
+        if !matches!(self.cur.item, PageItems::Input) {
+            return None;
+        }
+
+        self.cur.input = match util::paste_from_clipboard() {
+            Ok(text) => text,
+            Err(msg) => return Some(msg),
+        };
+        self.update_output();
+        None
+    }
+
+    fn update_output(&mut self) {
+        let req = EncodingRequest {
+            input: &self.cur.input,
+            mode: self.cur.eod_sel.into(),
+            codec: self.cur.codec_sel.into(),
+        };
+        (self.cur.output, self.cur.bytes, self.cur.status) = match encoding::calculate(&req) {
+            Ok(res) if req.mode == Mode::Decode && !self.cur.input.trim().is_empty() => {
+                let utf8 = if res.is_utf8() {
+                    "valid UTF-8"
+                } else {
+                    "not valid UTF-8"
+                };
+                let status = format!("{} bytes, {utf8}", res.bytes.len());
+                (res.output, res.bytes, InputStatus::Info(status))
+            }
+            Ok(res) => (res.output, res.bytes, InputStatus::None),
+            Err(e) => (String::new(), Vec::new(), InputStatus::Warn(e.to_string())),
+        };
+        self.cur.output_state = ScrollOutputState::default();
+    }
+
+    fn render_status(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let (status, status_style) = match &self.cur.status {
+            InputStatus::None => return,
+            InputStatus::Info(status) => (status, theme.info),
+            InputStatus::Warn(status) => (status, theme.error),
+        };
+        let status = Paragraph::new(status.as_str()).block(
+            Block::default()
+                .borders(Borders::empty())
+                .style(status_style)
+                .padding(Padding::horizontal(1)),
+        );
+        f.render_widget(status, area);
+    }
+
+    fn render_save_prompt(&self, f: &mut Frame, area: Rect, theme: &Theme, path: &Input) {
+        let label = "Save to: ";
+        let prompt = Paragraph::new(format!("{label}{}", path.value())).block(
+            Block::default()
+                .style(theme.text)
+                .padding(Padding::horizontal(1)),
+        );
+        f.render_widget(prompt, area);
+
+        let max_cursor = area.width.saturating_sub(2 + label.len() as u16);
+        let x = area.x + 1 + label.len() as u16 + (path.visual_cursor() as u16).min(max_cursor);
+        f.set_cursor_position((x, area.y));
+    }
+
+    fn render_output(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let output_text = self.output_text();
+        if self.output_scrollable() {
+            let output = ScrollOutput::new(
+                output_text,
+                self.focused,
+                self.cur.item == PageItems::Output,
+                theme,
+            )
+            .title("Output");
+            f.render_stateful_widget(output, area, &mut self.cur.output_state);
+            return;
+        }
+
+        let output_style = theme.item_style(self.focused, self.cur.item == PageItems::Output);
+        let output = Paragraph::new(output_text)
+            .block(
+                Block::bordered()
+                    .style(output_style)
+                    .title("Output")
+                    .padding(Padding::horizontal(1)),
+            )
+            .wrap(Wrap { trim: false });
+        f.render_widget(output, area);
+    }
+}
@@ -0,0 +1,423 @@
+use std::{
+    fs::{self, File},
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    crossterm::event::{Event, KeyEvent},
+    layout::{Margin, Rect},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListItem},
+    Frame,
+};
+use ratatui_macros::vertical;
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use crate::{event, msg::Msg, pages::util, theme::Theme};
+
+/// Largest file loaded into pages that show the whole output, larger files are only hashed.
+pub const FILE_SIZE_MAX: u64 = 16 * 1024 * 1024;
+
+const READ_CHUNK_LEN: usize = 64 * 1024;
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(50);
+const MAX_VISIBLE_CANDIDATES: usize = 8;
+
+/// A file read on a background thread, the result is what the work returns for its contents.
+pub struct FileLoad {
+    /// The path as entered, used to read the file again.
+    path: String,
+    pub name: String,
+    pub size: u64,
+    pub read: u64,
+    pub state: LoadState,
+    rx: mpsc::Receiver<LoadEvent>,
+}
+
+pub enum LoadState {
+    Loading,
+    Done(Vec<u8>),
+    Failed(String),
+}
+
+enum LoadEvent {
+    Progress(u64),
+    Done(io::Result<Vec<u8>>),
+}
+
+impl FileLoad {
+    /// Starts reading the file, `msg` is sent to the app on progress to apply it with `poll`.
+    /// Files larger than `max_size` are rejected before reading.
+    pub fn start<F>(
+        path: &str,
+        max_size: Option<u64>,
+        msg: Msg,
+        work: F,
+    ) -> Result<FileLoad, String>
+    where
+        F: FnOnce(&mut dyn Read) -> io::Result<Vec<u8>> + Send + 'static,
+    {
+        let expanded = util::expand_path(path);
+        let (file, size) = open(path, &expanded)?;
+        if let Some(max) = max_size.filter(|max| size > *max) {
+            let max = format_size(max);
+            return Err(format!("{path} is too large, up to {max} can be loaded"));
+        }
+        let name = expanded
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.into());
+        Ok(FileLoad {
+            path: path.into(),
+            name,
+            size,
+            read: 0,
+            state: LoadState::Loading,
+            rx: spawn(file, msg, work),
+        })
+    }
+
+    /// Reads the file again with other work, e.g. after the hash algorithm is changed.
+    /// The previous reading stops as its result is no longer received.
+    pub fn restart<F>(&mut self, msg: Msg, work: F)
+    where
+        F: FnOnce(&mut dyn Read) -> io::Result<Vec<u8>> + Send + 'static,
+    {
+        // dropping the receiver stops the previous reading, even if the file can not be opened
+        let (_, rx) = mpsc::channel();
+        self.rx = rx;
+        self.read = 0;
+        match open(&self.path, &util::expand_path(&self.path)) {
+            Ok((file, size)) => {
+                self.size = size;
+                self.state = LoadState::Loading;
+                self.rx = spawn(file, msg, work);
+            }
+            Err(e) => self.state = LoadState::Failed(e),
+        }
+    }
+
+    /// Applies the events received so far, returns whether the load finished with them.
+    pub fn poll(&mut self) -> bool {
+        let mut finished = false;
+        while let Ok(e) = self.rx.try_recv() {
+            match e {
+                LoadEvent::Progress(read) => self.read = read,
+                LoadEvent::Done(Ok(bytes)) => {
+                    self.read = self.size;
+                    self.state = LoadState::Done(bytes);
+                    finished = true;
+                }
+                LoadEvent::Done(Err(e)) => {
+                    self.state = LoadState::Failed(format!("Could not read {}: {e}", self.name));
+                    finished = true;
+                }
+            }
+        }
+        finished
+    }
+
+    pub fn bytes(&self) -> Option<&[u8]> {
+        match &self.state {
+            LoadState::Done(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Returns the lines describing the file, shown in place of the text input.
+    pub fn info_lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        let progress = match &self.state {
+            LoadState::Loading => {
+                let percent = (self.read * 100).checked_div(self.size).unwrap_or(0);
+                format!(
+                    "{percent}% ({} / {})",
+                    format_size(self.read),
+                    format_size(self.size)
+                )
+            }
+            LoadState::Done(_) => "Done".into(),
+            LoadState::Failed(e) => e.clone(),
+        };
+        vec![
+            util::field_line("File", self.name.clone(), theme),
+            util::field_line(
+                "Size",
+                format!("{} ({} bytes)", format_size(self.size), self.size),
+                theme,
+            ),
+            util::field_line("Progress", progress, theme),
+        ]
+    }
+}
+
+fn open(path: &str, expanded: &Path) -> Result<(File, u64), String> {
+    let metadata = fs::metadata(expanded).map_err(|e| format!("Could not open {path}: {e}"))?;
+    if metadata.is_dir() {
+        return Err(format!("{path} is a directory"));
+    }
+    let file = File::open(expanded).map_err(|e| format!("Could not open {path}: {e}"))?;
+    Ok((file, metadata.len()))
+}
+
+fn spawn<F>(file: File, msg: Msg, work: F) -> mpsc::Receiver<LoadEvent>
+where
+    F: FnOnce(&mut dyn Read) -> io::Result<Vec<u8>> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut reader = ProgressReader {
+            inner: file,
+            read: 0,
+            last_sent: Instant::now(),
+            tx: tx.clone(),
+            msg: msg.clone(),
+        };
+        let result = work(&mut reader);
+        // the receiver is gone if the page was reset or the file is read again
+        if tx.send(LoadEvent::Done(result)).is_ok() {
+            event::send(msg);
+        }
+    });
+    rx
+}
+
+/// Reads everything, for pages that need the whole contents.
+pub fn read_all(reader: &mut dyn Read) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Reports the bytes read so far, and stops reading once the page no longer waits for it.
+struct ProgressReader {
+    inner: File,
+    read: u64,
+    last_sent: Instant,
+    tx: mpsc::Sender<LoadEvent>,
+    msg: Msg,
+}
+
+impl Read for ProgressReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = buf.len().min(READ_CHUNK_LEN);
+        let n = self.inner.read(&mut buf[..len])?;
+        self.read += n as u64;
+        if self.last_sent.elapsed() >= PROGRESS_INTERVAL {
+            self.last_sent = Instant::now();
+            if self.tx.send(LoadEvent::Progress(self.read)).is_err() {
+                return Err(io::Error::other("cancelled"));
+            }
+            event::send(self.msg.clone());
+        }
+        Ok(n)
+    }
+}
+
+/// Formats a size in bytes with a binary unit, e.g. `1.5 MiB`.
+pub fn format_size(size: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if size < 1024 {
+        return format!("{size} B");
+    }
+    let mut value = size as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    format!("{value:.1} {unit}")
+}
+
+/// A prompt for the path of a file to load, listing the entries that complete it.
+#[derive(Default)]
+pub struct PathPrompt {
+    title: &'static str,
+    input: Input,
+    candidates: Vec<String>,
+    /// The candidate chosen with up and down, `None` to use the input as is.
+    selected: Option<usize>,
+}
+
+/// Result of confirming the prompt.
+pub enum PromptResult {
+    /// The path of the file to load.
+    Open(String),
+    /// A directory was completed, the prompt stays open with its entries.
+    Continue,
+}
+
+impl PathPrompt {
+    pub fn new() -> PathPrompt {
+        PathPrompt::with_title("Load file", "")
+    }
+
+    /// Starts with the path entered, e.g. the default one to save to.
+    pub fn with_title(title: &'static str, path: &str) -> PathPrompt {
+        let mut prompt = PathPrompt {
+            title,
+            input: Input::new(path.into()),
+            ..Default::default()
+        };
+        prompt.update_candidates();
+        prompt
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if self.input.handle_event(&Event::Key(key)).is_some() {
+            self.update_candidates();
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.candidates.is_empty() {
+            return;
+        }
+        self.selected = match self.selected {
+            Some(i) if i + 1 < self.candidates.len() => Some(i + 1),
+            Some(i) => Some(i),
+            None => Some(0),
+        };
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = match self.selected {
+            Some(0) | None => None,
+            Some(i) => Some(i - 1),
+        };
+    }
+
+    /// Completes the selected candidate, or returns the path entered if none is selected.
+    pub fn confirm(&mut self) -> PromptResult {
+        let Some(candidate) = self.selected.and_then(|i| self.candidates.get(i)).cloned() else {
+            return PromptResult::Open(self.input.value().trim().into());
+        };
+        self.input = Input::new(candidate.clone());
+        if candidate.ends_with('/') {
+            self.update_candidates();
+            PromptResult::Continue
+        } else {
+            PromptResult::Open(candidate)
+        }
+    }
+
+    fn update_candidates(&mut self) {
+        self.candidates = complete_path(self.input.value());
+        self.selected = None;
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let list_height = self.candidates.len().min(MAX_VISIBLE_CANDIDATES) as u16;
+        let height = (list_height + 3).min(area.height);
+        let prompt_area = Rect::new(area.x, area.y, area.width, height);
+
+        let block = Block::bordered()
+            .title(format!(" {} ", self.title))
+            .style(theme.focused);
+        let inner_area = block.inner(prompt_area).inner(Margin::new(1, 0));
+        f.render_widget(Clear, prompt_area);
+        f.render_widget(block, prompt_area);
+
+        let chunks = vertical![==1, >=0].split(inner_area);
+
+        let input_line = Line::from(vec![Span::raw("> "), Span::raw(self.input.value())]);
+        f.render_widget(input_line.style(theme.text), chunks[0]);
+        let max_cursor = chunks[0].width.saturating_sub(3);
+        let cursor_x = chunks[0].x + 2 + (self.input.visual_cursor() as u16).min(max_cursor);
+        f.set_cursor_position((cursor_x, chunks[0].y));
+
+        let visible = chunks[1].height as usize;
+        let offset = self.selected.map_or(0, |i| (i + 1).saturating_sub(visible));
+        let items = self
+            .candidates
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible)
+            .map(|(i, candidate)| {
+                let style = if self.selected == Some(i) {
+                    theme.selected
+                } else {
+                    theme.text
+                };
+                ListItem::new(candidate.as_str()).style(style)
+            });
+        f.render_widget(List::new(items), chunks[1]);
+    }
+}
+
+/// Returns the paths of the entries in the directory of the input that start with its file name part,
+/// directories end with `/`. Hidden entries are listed only if the file name part starts with `.`.
+pub fn complete_path(input: &str) -> Vec<String> {
+    let (dir, prefix) = match input.rfind('/') {
+        Some(i) => (&input[..=i], &input[i + 1..]),
+        None => ("", input),
+    };
+    let dir_path = if dir.is_empty() {
+        PathBuf::from(".")
+    } else {
+        util::expand_path(dir)
+    };
+    let Ok(entries) = fs::read_dir(&dir_path) else {
+        return Vec::new();
+    };
+    let mut candidates: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.')) {
+                return None;
+            }
+            let suffix = if is_dir(&entry.path()) { "/" } else { "" };
+            Some(format!("{dir}{name}{suffix}"))
+        })
+        .collect();
+    candidates.sort();
+    candidates
+}
+
+/// Follows symbolic links, so a link to a directory is completed as one.
+fn is_dir(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.is_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(0, "0 B")]
+    #[case(1023, "1023 B")]
+    #[case(1536, "1.5 KiB")]
+    #[case(5 * 1024 * 1024 * 1024, "5.0 GiB")]
+    fn test_format_size(#[case] size: u64, #[case] expected: &str) {
+        assert_eq!(format_size(size), expected);
+    }
+
+    #[test]
+    fn test_complete_path() {
+        let dir = std::env::temp_dir().join(format!("btox_complete_{}", std::process::id()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("sample.txt"), "").unwrap();
+        fs::write(dir.join(".hidden"), "").unwrap();
+        let base = format!("{}/", dir.display());
+
+        let actual = complete_path(&base);
+        assert_eq!(
+            actual,
+            vec![format!("{base}sample.txt"), format!("{base}sub/")]
+        );
+        let actual = complete_path(&format!("{base}sa"));
+        assert_eq!(actual, vec![format!("{base}sample.txt")]);
+        let actual = complete_path(&format!("{base}."));
+        assert_eq!(actual, vec![format!("{base}.hidden")]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,25 +1,51 @@
+use std::io::{self, Read};
+
+use btox::{
+    hash::{
+        self, ExpectedDigest, HashAlgorithm, HashError, HashRequest, Hasher, InputEncoding,
+        KeyEncoding, OutputFormat,
+    },
+    manifest,
+};
 use itsuki::zero_indexed_enum;
-use md5::{Digest, Md5};
 use ratatui::{
-    crossterm::event::KeyCode,
-    layout::Rect,
-    style::{Color, Style},
+    crossterm::event::KeyEvent,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::Line,
     widgets::{Block, Padding, Paragraph, Wrap},
     Frame,
 };
-use ratatui_macros::vertical;
-use sha1::Sha1;
-use sha2::{Sha224, Sha256, Sha384, Sha512, Sha512_224, Sha512_256};
 
 use crate::{
-    fn_next_prev_mut, fn_str_map, key_code, key_code_char,
+    config::HashConfig,
+    fn_next_prev_mut, fn_str_map,
+    keymap::{Action, Help, Keymap},
     msg::{HashMsg, Msg, PageMsg},
-    pages::{page::Page, util},
+    pages::{
+        file::{FileLoad, LoadState, PathPrompt, PromptResult},
+        manifest::{JobState, ManifestJob, ManifestKind},
+        page::Page,
+        picker::Picker,
+        util,
+    },
+    palette::Command,
+    session::{HashSession, Session},
+    theme::Theme,
     widget::{
         scroll::{ScrollOutput, ScrollOutputState},
         select::Select,
     },
 };
+use serde::{Deserialize, Serialize};
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+/// Output lengths in bytes to choose from for SHAKE128 and SHAKE256.
+const OUTPUT_LENS: [usize; 8] = [16, 20, 28, 32, 48, 64, 128, 256];
+const DEFAULT_OUTPUT_LEN: usize = 32;
+
+/// Reads the loaded file in the background, returning the digest.
+type FileWork = Box<dyn FnOnce(&mut dyn Read) -> io::Result<Vec<u8>> + Send>;
 
 pub struct HashPage {
     focused: bool,
@@ -29,41 +55,110 @@ pub struct HashPage {
 #[derive(Default)]
 struct CurrentStatus {
     item: PageItems,
+    view_sel: ViewItemSelect,
     algo_sel: AlgoItemSelect,
+    /// Output length in bytes, only for the algorithms with a variable one.
+    output_len: usize,
+    mode_sel: ModeItemSelect,
+    key_enc_sel: KeyEncodingItemSelect,
+    /// The HMAC key, which is not saved to the session.
+    key: Input,
+    /// The digest to compare the output with.
+    expected: Input,
+    /// Whether the key or the expected digest, whichever selected, is edited.
+    edit: bool,
     enc_sel: EncodeItemSelect,
+    newline_sel: NewlineItemSelect,
+    format_sel: FormatItemSelect,
     input: String,
     input_state: ScrollOutputState,
+    /// The file hashed instead of the input, if loaded.
+    file: Option<FileLoad>,
+    prompt: Option<PathPrompt>,
+    /// What the path entered in the prompt is for.
+    prompt_target: PromptTarget,
+    picker: Option<Picker>,
+    /// The manifest verified or generated, listed in place of the input and the output.
+    manifest: Option<ManifestJob>,
+    manifest_state: ScrollOutputState,
+    /// Empty until computed.
+    digest: Vec<u8>,
+    /// The digest in the output format.
     output: String,
+    /// The digests of every algorithm in the order of `AlgoItemSelect`, empty until computed.
+    all_digests: Vec<Vec<u8>>,
+    /// The row of the algorithm selected in the all algorithms view.
+    all_row: usize,
+    all_state: ScrollOutputState,
+    /// The reason the output is not computed, e.g. an invalid key.
+    error: Option<String>,
 }
 
 impl HashPage {
-    pub fn new(focused: bool) -> HashPage {
-        let algo_sel = AlgoItemSelect::default();
-        let input = String::new();
-        let output = calculate_hash(&input, algo_sel);
-        HashPage {
+    pub fn new(focused: bool, config: &HashConfig) -> HashPage {
+        let mut page = HashPage {
             focused,
             cur: CurrentStatus {
-                algo_sel,
-                input,
-                output,
+                algo_sel: config.algorithm,
+                output_len: DEFAULT_OUTPUT_LEN,
+                enc_sel: config.encoding,
+                format_sel: config.format,
                 ..Default::default()
             },
-        }
+        };
+        page.update_hash();
+        page
     }
 }
 
 #[derive(Default)]
 #[zero_indexed_enum]
 enum PageItems {
+    View,
     #[default]
     Algo,
+    OutputLen,
+    Mode,
+    KeyEncoding,
+    Key,
     Encode,
+    Newline,
+    Format,
     Input,
+    Expected,
     Output,
+    Manifest,
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+enum PromptTarget {
+    #[default]
+    File,
+    VerifyManifest,
+    GenerateManifest,
+    SaveManifest,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[zero_indexed_enum]
+enum ViewItemSelect {
+    #[default]
+    Single,
+    All,
+}
+
+impl ViewItemSelect {
+    fn_str_map! {
+        ViewItemSelect::Single => "Single algorithm",
+        ViewItemSelect::All => "All algorithms",
+    }
+
+    fn_next_prev_mut! {}
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
 #[zero_indexed_enum]
 enum AlgoItemSelect {
     #[default]
@@ -75,6 +170,24 @@ enum AlgoItemSelect {
     Sha512_224,
     Sha512_256,
     Sha512,
+    Sha3_224,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    Keccak256,
+    Shake128,
+    Shake256,
+    Blake2b,
+    Blake2s,
+    Blake3,
+    Ripemd160,
+    Crc32,
+    Crc32c,
+    Crc64,
+    Adler32,
+    Xxh32,
+    Xxh64,
+    Xxh3,
 }
 
 impl AlgoItemSelect {
@@ -87,37 +200,243 @@ impl AlgoItemSelect {
         AlgoItemSelect::Sha512_224 => "SHA-512/224",
         AlgoItemSelect::Sha512_256 => "SHA-512/256",
         AlgoItemSelect::Sha512 => "SHA-512",
+        AlgoItemSelect::Sha3_224 => "SHA3-224",
+        AlgoItemSelect::Sha3_256 => "SHA3-256",
+        AlgoItemSelect::Sha3_384 => "SHA3-384",
+        AlgoItemSelect::Sha3_512 => "SHA3-512",
+        AlgoItemSelect::Keccak256 => "Keccak-256",
+        AlgoItemSelect::Shake128 => "SHAKE128",
+        AlgoItemSelect::Shake256 => "SHAKE256",
+        AlgoItemSelect::Blake2b => "BLAKE2b-512",
+        AlgoItemSelect::Blake2s => "BLAKE2s-256",
+        AlgoItemSelect::Blake3 => "BLAKE3",
+        AlgoItemSelect::Ripemd160 => "RIPEMD-160",
+        AlgoItemSelect::Crc32 => "CRC-32",
+        AlgoItemSelect::Crc32c => "CRC-32C",
+        AlgoItemSelect::Crc64 => "CRC-64/XZ",
+        AlgoItemSelect::Adler32 => "Adler-32",
+        AlgoItemSelect::Xxh32 => "xxHash32",
+        AlgoItemSelect::Xxh64 => "xxHash64",
+        AlgoItemSelect::Xxh3 => "XXH3-64",
     }
 
     fn_next_prev_mut! {}
 }
 
-#[derive(Default)]
+impl From<AlgoItemSelect> for HashAlgorithm {
+    fn from(algo_sel: AlgoItemSelect) -> Self {
+        match algo_sel {
+            AlgoItemSelect::Md5 => HashAlgorithm::Md5,
+            AlgoItemSelect::Sha1 => HashAlgorithm::Sha1,
+            AlgoItemSelect::Sha224 => HashAlgorithm::Sha224,
+            AlgoItemSelect::Sha256 => HashAlgorithm::Sha256,
+            AlgoItemSelect::Sha384 => HashAlgorithm::Sha384,
+            AlgoItemSelect::Sha512_224 => HashAlgorithm::Sha512_224,
+            AlgoItemSelect::Sha512_256 => HashAlgorithm::Sha512_256,
+            AlgoItemSelect::Sha512 => HashAlgorithm::Sha512,
+            AlgoItemSelect::Sha3_224 => HashAlgorithm::Sha3_224,
+            AlgoItemSelect::Sha3_256 => HashAlgorithm::Sha3_256,
+            AlgoItemSelect::Sha3_384 => HashAlgorithm::Sha3_384,
+            AlgoItemSelect::Sha3_512 => HashAlgorithm::Sha3_512,
+            AlgoItemSelect::Keccak256 => HashAlgorithm::Keccak256,
+            AlgoItemSelect::Shake128 => HashAlgorithm::Shake128,
+            AlgoItemSelect::Shake256 => HashAlgorithm::Shake256,
+            AlgoItemSelect::Blake2b => HashAlgorithm::Blake2b,
+            AlgoItemSelect::Blake2s => HashAlgorithm::Blake2s,
+            AlgoItemSelect::Blake3 => HashAlgorithm::Blake3,
+            AlgoItemSelect::Ripemd160 => HashAlgorithm::Ripemd160,
+            AlgoItemSelect::Crc32 => HashAlgorithm::Crc32,
+            AlgoItemSelect::Crc32c => HashAlgorithm::Crc32c,
+            AlgoItemSelect::Crc64 => HashAlgorithm::Crc64,
+            AlgoItemSelect::Adler32 => HashAlgorithm::Adler32,
+            AlgoItemSelect::Xxh32 => HashAlgorithm::Xxh32,
+            AlgoItemSelect::Xxh64 => HashAlgorithm::Xxh64,
+            AlgoItemSelect::Xxh3 => HashAlgorithm::Xxh3,
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[zero_indexed_enum]
+enum ModeItemSelect {
+    #[default]
+    Digest,
+    Hmac,
+}
+
+impl ModeItemSelect {
+    fn_str_map! {
+        ModeItemSelect::Digest => "Digest",
+        ModeItemSelect::Hmac => "HMAC",
+    }
+
+    fn_next_prev_mut! {}
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[zero_indexed_enum]
+enum KeyEncodingItemSelect {
+    #[default]
+    Utf8,
+    Hex,
+    Base64,
+}
+
+impl KeyEncodingItemSelect {
+    fn_str_map! {
+        KeyEncodingItemSelect::Utf8 => "UTF-8 key",
+        KeyEncodingItemSelect::Hex => "Hex key",
+        KeyEncodingItemSelect::Base64 => "Base64 key",
+    }
+
+    fn_next_prev_mut! {}
+}
+
+impl From<KeyEncodingItemSelect> for KeyEncoding {
+    fn from(key_enc_sel: KeyEncodingItemSelect) -> Self {
+        match key_enc_sel {
+            KeyEncodingItemSelect::Utf8 => KeyEncoding::Utf8,
+            KeyEncodingItemSelect::Hex => KeyEncoding::Hex,
+            KeyEncodingItemSelect::Base64 => KeyEncoding::Base64,
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
 #[zero_indexed_enum]
 enum EncodeItemSelect {
     #[default]
     Utf8,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+    Hex,
+    Base64,
 }
 
 impl EncodeItemSelect {
     fn_str_map! {
         EncodeItemSelect::Utf8 => "UTF-8",
+        EncodeItemSelect::Utf16Le => "UTF-16LE",
+        EncodeItemSelect::Utf16Be => "UTF-16BE",
+        EncodeItemSelect::Latin1 => "Latin-1",
+        EncodeItemSelect::Hex => "Hex bytes",
+        EncodeItemSelect::Base64 => "Base64 bytes",
+    }
+
+    fn_next_prev_mut! {}
+}
+
+impl From<EncodeItemSelect> for InputEncoding {
+    fn from(enc_sel: EncodeItemSelect) -> Self {
+        match enc_sel {
+            EncodeItemSelect::Utf8 => InputEncoding::Utf8,
+            EncodeItemSelect::Utf16Le => InputEncoding::Utf16Le,
+            EncodeItemSelect::Utf16Be => InputEncoding::Utf16Be,
+            EncodeItemSelect::Latin1 => InputEncoding::Latin1,
+            EncodeItemSelect::Hex => InputEncoding::Hex,
+            EncodeItemSelect::Base64 => InputEncoding::Base64,
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[zero_indexed_enum]
+enum NewlineItemSelect {
+    #[default]
+    Keep,
+    Strip,
+}
+
+impl NewlineItemSelect {
+    fn_str_map! {
+        NewlineItemSelect::Keep => "Keep trailing newline",
+        NewlineItemSelect::Strip => "Strip trailing newline",
+    }
+
+    fn_next_prev_mut! {}
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[zero_indexed_enum]
+enum FormatItemSelect {
+    #[default]
+    HexLower,
+    HexUpper,
+    Base64,
+    Base64Url,
+}
+
+impl FormatItemSelect {
+    fn_str_map! {
+        FormatItemSelect::HexLower => "hex",
+        FormatItemSelect::HexUpper => "HEX",
+        FormatItemSelect::Base64 => "Base64",
+        FormatItemSelect::Base64Url => "Base64URL",
     }
 
     fn_next_prev_mut! {}
 }
 
+impl From<FormatItemSelect> for OutputFormat {
+    fn from(format_sel: FormatItemSelect) -> Self {
+        match format_sel {
+            FormatItemSelect::HexLower => OutputFormat::HexLower,
+            FormatItemSelect::HexUpper => OutputFormat::HexUpper,
+            FormatItemSelect::Base64 => OutputFormat::Base64,
+            FormatItemSelect::Base64Url => OutputFormat::Base64Url,
+        }
+    }
+}
+
 impl Page for HashPage {
-    fn handle_key(&self, key: ratatui::crossterm::event::KeyEvent) -> Option<Msg> {
-        let msg = match key {
-            key_code_char!('j') | key_code!(KeyCode::Down) => HashMsg::SelectNextItem,
-            key_code_char!('k') | key_code!(KeyCode::Up) => HashMsg::SelectPrevItem,
-            key_code_char!('l') | key_code!(KeyCode::Right) => HashMsg::CurrentItemSelectNext,
-            key_code_char!('h') | key_code!(KeyCode::Left) => HashMsg::CurrentItemSelectPrev,
-            key_code_char!('e', Ctrl) => HashMsg::ScrollDown,
-            key_code_char!('y', Ctrl) => HashMsg::ScrollUp,
-            key_code_char!('y') => HashMsg::Copy,
-            key_code_char!('p') => HashMsg::Paste,
+    fn handle_key(&self, key: KeyEvent, keymap: &Keymap) -> Option<Msg> {
+        if self.cur.edit {
+            let msg = match keymap.resolve_input(key) {
+                Some(Action::Cancel) => HashMsg::EditEnd,
+                _ => HashMsg::EditKeyEvent(key),
+            };
+            return Some(Msg::Page(PageMsg::Hash(msg)));
+        }
+        if self.cur.picker.is_some() {
+            let msg = match keymap.resolve_input(key) {
+                Some(Action::Confirm) => HashMsg::PickConfirm,
+                Some(Action::Cancel) => HashMsg::PickEnd,
+                Some(Action::SelectNextCandidate) => HashMsg::PickSelectNextCandidate,
+                Some(Action::SelectPrevCandidate) => HashMsg::PickSelectPrevCandidate,
+                _ => HashMsg::PickKeyEvent(key),
+            };
+            return Some(Msg::Page(PageMsg::Hash(msg)));
+        }
+        if self.cur.prompt.is_some() {
+            let msg = match keymap.resolve_input(key) {
+                Some(Action::Confirm) => HashMsg::LoadConfirm,
+                Some(Action::Cancel) => HashMsg::LoadEnd,
+                Some(Action::SelectNextCandidate) => HashMsg::LoadSelectNextCandidate,
+                Some(Action::SelectPrevCandidate) => HashMsg::LoadSelectPrevCandidate,
+                _ => HashMsg::LoadKeyEvent(key),
+            };
+            return Some(Msg::Page(PageMsg::Hash(msg)));
+        }
+
+        let msg = match keymap.resolve(key)? {
+            Action::SelectNextItem => HashMsg::SelectNextItem,
+            Action::SelectPrevItem => HashMsg::SelectPrevItem,
+            Action::SelectNextValue => HashMsg::CurrentItemSelectNext,
+            Action::SelectPrevValue => HashMsg::CurrentItemSelectPrev,
+            Action::ScrollDown => HashMsg::ScrollDown,
+            Action::ScrollUp => HashMsg::ScrollUp,
+            Action::Copy => HashMsg::Copy,
+            Action::Paste => HashMsg::Paste,
+            Action::LoadFile => HashMsg::LoadStart,
+            Action::Save => HashMsg::SaveStart,
+            Action::Edit if self.cur.item == PageItems::Algo => HashMsg::PickStart,
+            Action::Edit => HashMsg::EditStart,
             _ => return None,
         };
         Some(Msg::Page(PageMsg::Hash(msg)))
@@ -148,57 +467,357 @@ impl Page for HashPage {
                     return self.copy_to_clipboard();
                 }
                 HashMsg::Paste => {
-                    self.paste_from_clipboard();
+                    return self.paste_from_clipboard();
+                }
+                HashMsg::SelectItem(item) => {
+                    if self.is_visible(item) {
+                        self.cur.item = item;
+                    }
+                }
+                HashMsg::SelectRow(row) => {
+                    if self.is_all_view() && row < self.cur.all_digests.len() {
+                        self.cur.item = PageItems::Output;
+                        self.cur.all_row = row;
+                    }
+                }
+                HashMsg::SetView(view_sel) => {
+                    self.cur.view_sel = view_sel;
+                    self.ensure_item_visible();
+                    self.update_hash();
+                }
+                HashMsg::SetAlgorithm(algo_sel) => {
+                    self.cur.algo_sel = algo_sel;
+                    self.ensure_item_visible();
+                    self.update_hash();
+                }
+                HashMsg::SetOutputLen(output_len) => {
+                    if OUTPUT_LENS.contains(&output_len) {
+                        self.cur.output_len = output_len;
+                        self.update_hash();
+                    }
+                }
+                HashMsg::SetMode(mode_sel) => {
+                    self.cur.mode_sel = mode_sel;
+                    self.ensure_item_visible();
+                    self.update_hash();
+                }
+                HashMsg::SetKeyEncoding(key_enc_sel) => {
+                    self.cur.key_enc_sel = key_enc_sel;
+                    self.update_hash();
+                }
+                HashMsg::SetEncoding(enc_sel) => {
+                    self.cur.enc_sel = enc_sel;
+                    self.update_hash();
+                }
+                HashMsg::SetNewline(newline_sel) => {
+                    self.cur.newline_sel = newline_sel;
+                    self.update_hash();
+                }
+                HashMsg::SetFormat(format_sel) => {
+                    self.cur.format_sel = format_sel;
+                    self.update_output();
+                }
+                HashMsg::EditStart => {
+                    if matches!(self.cur.item, PageItems::Key | PageItems::Expected) {
+                        self.cur.edit = true;
+                    }
+                }
+                HashMsg::EditEnd => {
+                    self.cur.edit = false;
+                    if self.cur.item == PageItems::Expected && !self.is_all_view() {
+                        return self.detect_algorithm();
+                    }
+                }
+                HashMsg::EditKeyEvent(key) => {
+                    let event = &ratatui::crossterm::event::Event::Key(key);
+                    let input = match self.cur.item {
+                        PageItems::Key => &mut self.cur.key,
+                        PageItems::Expected => &mut self.cur.expected,
+                        _ => return None,
+                    };
+                    let changed = input.handle_event(event).is_some_and(|c| c.value);
+                    // the expected digest is only compared when rendered
+                    if changed && self.cur.item == PageItems::Key {
+                        self.update_hash();
+                    }
+                }
+                HashMsg::LoadStart => {
+                    self.cur.prompt_target = PromptTarget::File;
+                    self.cur.prompt = Some(PathPrompt::new());
+                }
+                HashMsg::LoadEnd => {
+                    self.cur.prompt = None;
+                }
+                HashMsg::LoadConfirm => {
+                    return self.load_confirm();
+                }
+                HashMsg::LoadSelectNextCandidate => {
+                    if let Some(prompt) = &mut self.cur.prompt {
+                        prompt.select_next();
+                    }
+                }
+                HashMsg::LoadSelectPrevCandidate => {
+                    if let Some(prompt) = &mut self.cur.prompt {
+                        prompt.select_prev();
+                    }
+                }
+                HashMsg::LoadKeyEvent(key) => {
+                    if let Some(prompt) = &mut self.cur.prompt {
+                        prompt.handle_key(key);
+                    }
+                }
+                HashMsg::LoadProgress => {
+                    return self.load_progress();
+                }
+                HashMsg::ManifestVerifyStart => {
+                    self.cur.prompt_target = PromptTarget::VerifyManifest;
+                    self.cur.prompt = Some(PathPrompt::with_title("Verify checksum file", ""));
+                }
+                HashMsg::ManifestGenerateStart => {
+                    self.cur.prompt_target = PromptTarget::GenerateManifest;
+                    let title = "Generate checksums of directory";
+                    self.cur.prompt = Some(PathPrompt::with_title(title, ""));
+                }
+                HashMsg::ManifestProgress => {
+                    return self.manifest_progress();
+                }
+                HashMsg::ManifestClose => {
+                    self.cur.manifest = None;
+                    self.ensure_item_visible();
+                }
+                HashMsg::SaveStart => {
+                    self.save_start();
+                }
+                HashMsg::PickStart => {
+                    let labels = AlgoItemSelect::strings_vec();
+                    self.cur.picker =
+                        Some(Picker::new("Algorithm", labels, self.cur.algo_sel.val()));
+                }
+                HashMsg::PickEnd => {
+                    self.cur.picker = None;
+                }
+                HashMsg::PickConfirm => {
+                    self.pick_confirm();
+                }
+                HashMsg::PickSelectNextCandidate => {
+                    if let Some(picker) = &mut self.cur.picker {
+                        picker.select_next();
+                    }
+                }
+                HashMsg::PickSelectPrevCandidate => {
+                    if let Some(picker) = &mut self.cur.picker {
+                        picker.select_prev();
+                    }
+                }
+                HashMsg::PickKeyEvent(key) => {
+                    if let Some(picker) = &mut self.cur.picker {
+                        picker.handle_key(key);
+                    }
                 }
             }
         }
         None
     }
 
-    fn render(&mut self, f: &mut Frame, area: Rect) {
-        let chunks = vertical![==2, ==2, >=0, ==5].split(area);
+    fn render(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let height = |item, height| {
+            if self.is_visible(item) {
+                height
+            } else {
+                0
+            }
+        };
+        // long outputs of SHAKE are wrapped to more lines than the default height
+        let output_width = area.width.saturating_sub(4).max(1);
+        let output_height = (self.cur.output.len() as u16).div_ceil(output_width) + 2;
+        // the table of all the digests takes the space of the input instead
+        let all_view = self.is_all_view() && self.is_visible(PageItems::Output);
+        let (input_constraint, output_constraint) = if all_view {
+            (Constraint::Length(5), Constraint::Min(0))
+        } else {
+            (
+                Constraint::Min(0),
+                Constraint::Length(height(PageItems::Output, output_height.max(5))),
+            )
+        };
+        let chunks = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(height(PageItems::Algo, 2)),
+            Constraint::Length(height(PageItems::OutputLen, 2)),
+            Constraint::Length(2),
+            Constraint::Length(height(PageItems::KeyEncoding, 2)),
+            Constraint::Length(height(PageItems::Key, 3)),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            input_constraint,
+            Constraint::Length(height(PageItems::Expected, 3)),
+            output_constraint,
+        ])
+        .split(area);
+
+        let view_sel = Select::new(
+            ViewItemSelect::strings_vec(),
+            self.cur.view_sel.val(),
+            self.cur.item == PageItems::View,
+            self.focused,
+            theme,
+        );
+        f.render_widget(view_sel, chunks[0]);
+
+        if self.is_visible(PageItems::Algo) {
+            let algo_sel = Select::new(
+                AlgoItemSelect::strings_vec(),
+                self.cur.algo_sel.val(),
+                self.cur.item == PageItems::Algo,
+                self.focused,
+                theme,
+            );
+            f.render_widget(algo_sel, chunks[1]);
+        }
+
+        if self.is_visible(PageItems::OutputLen) {
+            let output_len_sel = Select::new(
+                OUTPUT_LENS.map(|len| format!("{len} bytes")).to_vec(),
+                self.output_len_index(),
+                self.cur.item == PageItems::OutputLen,
+                self.focused,
+                theme,
+            );
+            f.render_widget(output_len_sel, chunks[2]);
+        }
 
-        let algo_sel = Select::new(
-            AlgoItemSelect::strings_vec(),
-            self.cur.algo_sel.val(),
-            self.cur.item == PageItems::Algo,
+        let mode_sel = Select::new(
+            ModeItemSelect::strings_vec(),
+            self.cur.mode_sel.val(),
+            self.cur.item == PageItems::Mode,
             self.focused,
+            theme,
         );
-        f.render_widget(algo_sel, chunks[0]);
+        f.render_widget(mode_sel, chunks[3]);
+
+        if self.is_visible(PageItems::KeyEncoding) {
+            let key_enc_sel = Select::new(
+                KeyEncodingItemSelect::strings_vec(),
+                self.cur.key_enc_sel.val(),
+                self.cur.item == PageItems::KeyEncoding,
+                self.focused,
+                theme,
+            );
+            f.render_widget(key_enc_sel, chunks[4]);
+        }
+        if self.is_visible(PageItems::Key) {
+            let key = Line::raw("Key");
+            self.render_text_input(f, chunks[5], theme, PageItems::Key, key, None);
+        }
 
         let enc_sel = Select::new(
             EncodeItemSelect::strings_vec(),
             self.cur.enc_sel.val(),
             self.cur.item == PageItems::Encode,
             self.focused,
+            theme,
         );
-        f.render_widget(enc_sel, chunks[1]);
+        f.render_widget(enc_sel, chunks[6]);
 
-        let input_text = self.cur.input.clone();
-        let input = ScrollOutput::new(input_text, self.focused, self.cur.item == PageItems::Input)
-            .title("Input");
-        f.render_stateful_widget(input, chunks[2], &mut self.cur.input_state);
+        let newline_sel = Select::new(
+            NewlineItemSelect::strings_vec(),
+            self.cur.newline_sel.val(),
+            self.cur.item == PageItems::Newline,
+            self.focused,
+            theme,
+        );
+        f.render_widget(newline_sel, chunks[7]);
 
-        let output_style = if self.focused {
-            if self.cur.item == PageItems::Output {
-                Style::default().fg(Color::Blue)
-            } else {
-                Style::default().fg(Color::Reset)
-            }
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
+        let format_sel = Select::new(
+            FormatItemSelect::strings_vec(),
+            self.cur.format_sel.val(),
+            self.cur.item == PageItems::Format,
+            self.focused,
+            theme,
+        );
+        f.render_widget(format_sel, chunks[8]);
 
-        let output_text = self.cur.output.clone();
-        let output = Paragraph::new(output_text)
-            .block(
+        if let Some(job) = &self.cur.manifest {
+            let title = job.title();
+            let manifest = ScrollOutput::new(
+                job.lines(theme),
+                self.focused,
+                self.cur.item == PageItems::Manifest,
+                theme,
+            )
+            .title(&title);
+            f.render_stateful_widget(manifest, chunks[9], &mut self.cur.manifest_state);
+        } else if let Some(file) = &self.cur.file {
+            let input_style = theme.item_style(self.focused, self.cur.item == PageItems::Input);
+            let input = Paragraph::new(file.info_lines(theme)).block(
                 Block::bordered()
-                    .style(output_style)
-                    .title("Output")
+                    .style(input_style)
+                    .title("Input (file)")
                     .padding(Padding::horizontal(1)),
+            );
+            f.render_widget(input, chunks[9]);
+        } else {
+            let input_text = self.cur.input.clone();
+            let input = ScrollOutput::new(
+                input_text,
+                self.focused,
+                self.cur.item == PageItems::Input,
+                theme,
             )
-            .wrap(Wrap { trim: false });
-        f.render_widget(output, chunks[3]);
+            .title("Input");
+            f.render_stateful_widget(input, chunks[9], &mut self.cur.input_state);
+        }
+
+        let verification = self.verification();
+        let expected_title = match hash::parse_expected(self.cur.expected.value()) {
+            Ok(ExpectedDigest {
+                file: Some(file), ..
+            }) => Line::raw(format!("Expected ({file})")),
+            _ => Line::raw("Expected"),
+        };
+        let status = match &verification {
+            Some(Ok(true)) if all_view => {
+                let labels: Vec<String> = self
+                    .matched_rows()
+                    .into_iter()
+                    .map(|row| AlgoItemSelect::vars_vec()[row].str().to_string())
+                    .collect();
+                Some(Line::styled(
+                    format!(" ✔ Match: {} ", labels.join(", ")),
+                    theme.info.add_modifier(Modifier::BOLD),
+                ))
+            }
+            Some(Ok(true)) => Some(Line::styled(
+                " ✔ Match ",
+                theme.info.add_modifier(Modifier::BOLD),
+            )),
+            Some(Ok(false)) if all_view => Some(Line::styled(
+                " ✘ No match ",
+                theme.error.add_modifier(Modifier::BOLD),
+            )),
+            Some(Ok(false)) => Some(Line::styled(
+                " ✘ Mismatch ",
+                theme.error.add_modifier(Modifier::BOLD),
+            )),
+            Some(Err(e)) => Some(Line::styled(format!(" {e} "), theme.warn)),
+            None => None,
+        };
+        let item = PageItems::Expected;
+        self.render_text_input(f, chunks[10], theme, item, expected_title, status);
+
+        if all_view {
+            self.render_all_digests(f, chunks[11], theme);
+        } else {
+            self.render_output(f, chunks[11], theme, &verification);
+        }
+
+        if let Some(prompt) = &self.cur.prompt {
+            prompt.render(f, chunks[9].union(chunks[11]), theme);
+        }
+        if let Some(picker) = &self.cur.picker {
+            picker.render(f, chunks[2].union(chunks[11]), theme);
+        }
     }
 
     fn focus(&mut self) {
@@ -207,65 +826,473 @@ impl Page for HashPage {
 
     fn unfocus(&mut self) {
         self.focused = false;
+        self.cur.prompt = None;
+        self.cur.picker = None;
+        self.cur.edit = false;
     }
 
-    fn helps(&self) -> Vec<&str> {
-        let mut helps: Vec<&str> = Vec::new();
-        helps.push("<j/k> Select item");
-        if matches!(self.cur.item, PageItems::Algo | PageItems::Encode) {
-            helps.push("<h/l> Select current item value");
+    fn helps(&self) -> Vec<Help> {
+        let mut helps: Vec<Help> = Vec::new();
+        if self.cur.edit {
+            helps.push(Help::new(&[Action::Cancel], "End edit"));
+            return helps;
+        }
+        if self.cur.picker.is_some() {
+            helps.push(Help::new(
+                &[Action::SelectNextCandidate, Action::SelectPrevCandidate],
+                "Select algorithm",
+            ));
+            helps.push(Help::new(&[Action::Confirm], "Choose"));
+            helps.push(Help::new(&[Action::Cancel], "Cancel"));
+            return helps;
+        }
+        if self.cur.prompt.is_some() {
+            helps.push(Help::new(
+                &[Action::SelectNextCandidate, Action::SelectPrevCandidate],
+                "Select candidate",
+            ));
+            helps.push(Help::new(&[Action::Confirm], "Load"));
+            helps.push(Help::new(&[Action::Cancel], "Cancel"));
+            return helps;
+        }
+        helps.push(Help::new(
+            &[Action::SelectNextItem, Action::SelectPrevItem],
+            "Select item",
+        ));
+        if matches!(
+            self.cur.item,
+            PageItems::View
+                | PageItems::Algo
+                | PageItems::OutputLen
+                | PageItems::Mode
+                | PageItems::KeyEncoding
+                | PageItems::Encode
+                | PageItems::Newline
+                | PageItems::Format
+        ) {
+            helps.push(Help::new(
+                &[Action::SelectPrevValue, Action::SelectNextValue],
+                "Select current item value",
+            ));
+        }
+        if matches!(self.cur.item, PageItems::Algo) {
+            helps.push(Help::new(&[Action::Edit], "Search algorithm"));
+        }
+        if matches!(self.cur.item, PageItems::Key | PageItems::Expected) {
+            helps.push(Help::new(&[Action::Edit], "Edit"));
+            helps.push(Help::new(&[Action::Paste], "Paste from clipboard"));
         }
         if matches!(self.cur.item, PageItems::Output) {
-            helps.push("<y> Copy to clipboard");
+            let label = if self.is_all_view() {
+                "Copy selected digest to clipboard"
+            } else {
+                "Copy to clipboard"
+            };
+            helps.push(Help::new(&[Action::Copy], label));
+        }
+        if matches!(self.cur.item, PageItems::Manifest) {
+            helps.push(Help::new(
+                &[Action::ScrollDown, Action::ScrollUp],
+                "Scroll down/up",
+            ));
+            helps.push(Help::new(&[Action::Copy], "Copy to clipboard"));
+            if self.manifest_text().is_some() {
+                helps.push(Help::new(&[Action::Save], "Save to file"));
+            }
         }
         if matches!(self.cur.item, PageItems::Input) {
-            helps.push("<C-e/C-y> Scroll down/up");
-            helps.push("<p> Paste from clipboard");
+            helps.push(Help::new(
+                &[Action::ScrollDown, Action::ScrollUp],
+                "Scroll down/up",
+            ));
+            helps.push(Help::new(&[Action::Paste], "Paste from clipboard"));
+            helps.push(Help::new(&[Action::LoadFile], "Load from file"));
         }
         helps
     }
+
+    fn commands(&self) -> Vec<Command> {
+        let msg = |msg| Msg::Page(PageMsg::Hash(msg));
+        let mut commands: Vec<Command> = ViewItemSelect::vars_vec()
+            .into_iter()
+            .map(|view_sel| {
+                let label = format!("Hash: view {}", view_sel.str().to_lowercase());
+                Command::new(label, vec![msg(HashMsg::SetView(view_sel))])
+            })
+            .collect();
+        for algo_sel in AlgoItemSelect::vars_vec() {
+            let label = format!("Hash: switch to {}", algo_sel.str());
+            commands.push(Command::new(
+                label,
+                vec![
+                    msg(HashMsg::SetView(ViewItemSelect::Single)),
+                    msg(HashMsg::SetAlgorithm(algo_sel)),
+                ],
+            ));
+        }
+        commands.push(Command::new(
+            "Hash: search algorithm",
+            vec![msg(HashMsg::PickStart)],
+        ));
+        if self.is_visible(PageItems::OutputLen) {
+            for output_len in OUTPUT_LENS {
+                let label = format!("Hash: output length {output_len} bytes");
+                commands.push(Command::new(
+                    label,
+                    vec![msg(HashMsg::SetOutputLen(output_len))],
+                ));
+            }
+        }
+        for mode_sel in ModeItemSelect::vars_vec() {
+            let label = format!("Hash: mode {}", mode_sel.str());
+            commands.push(Command::new(label, vec![msg(HashMsg::SetMode(mode_sel))]));
+        }
+        if self.is_visible(PageItems::Key) {
+            for key_enc_sel in KeyEncodingItemSelect::vars_vec() {
+                let label = format!("Hash: {}", key_enc_sel.str().to_lowercase());
+                commands.push(Command::new(
+                    label,
+                    vec![msg(HashMsg::SetKeyEncoding(key_enc_sel))],
+                ));
+            }
+            commands.push(Command::new(
+                "Hash: edit key",
+                vec![
+                    msg(HashMsg::SelectItem(PageItems::Key)),
+                    msg(HashMsg::EditStart),
+                ],
+            ));
+        }
+        for enc_sel in EncodeItemSelect::vars_vec() {
+            let label = format!("Hash: input encoding {}", enc_sel.str());
+            commands.push(Command::new(
+                label,
+                vec![msg(HashMsg::SetEncoding(enc_sel))],
+            ));
+        }
+        for newline_sel in NewlineItemSelect::vars_vec() {
+            let label = format!("Hash: {}", newline_sel.str().to_lowercase());
+            commands.push(Command::new(
+                label,
+                vec![msg(HashMsg::SetNewline(newline_sel))],
+            ));
+        }
+        for format_sel in FormatItemSelect::vars_vec() {
+            let label = format!("Hash: output format {}", format_sel.str());
+            commands.push(Command::new(
+                label,
+                vec![msg(HashMsg::SetFormat(format_sel))],
+            ));
+        }
+        commands.push(Command::new(
+            "Hash: paste input",
+            vec![
+                msg(HashMsg::SelectItem(PageItems::Input)),
+                msg(HashMsg::Paste),
+            ],
+        ));
+        commands.push(Command::new(
+            "Hash: load file",
+            vec![msg(HashMsg::LoadStart)],
+        ));
+        commands.push(Command::new(
+            "Hash: verify checksum file",
+            vec![msg(HashMsg::ManifestVerifyStart)],
+        ));
+        commands.push(Command::new(
+            "Hash: generate checksum file of directory",
+            vec![msg(HashMsg::ManifestGenerateStart)],
+        ));
+        if self.cur.manifest.is_some() {
+            if self.manifest_text().is_some() {
+                commands.push(Command::new(
+                    "Hash: save checksum file",
+                    vec![msg(HashMsg::SaveStart)],
+                ));
+            }
+            commands.push(Command::new(
+                "Hash: close checksum list",
+                vec![msg(HashMsg::ManifestClose)],
+            ));
+        }
+        commands.push(Command::new(
+            "Hash: paste expected digest",
+            vec![
+                msg(HashMsg::SelectItem(PageItems::Expected)),
+                msg(HashMsg::Paste),
+            ],
+        ));
+        commands.push(Command::new(
+            "Hash: edit expected digest",
+            vec![
+                msg(HashMsg::SelectItem(PageItems::Expected)),
+                msg(HashMsg::EditStart),
+            ],
+        ));
+        if self.is_all_view() {
+            for (row, algo_sel) in AlgoItemSelect::vars_vec().into_iter().enumerate() {
+                let label = format!("Hash: copy {} digest", algo_sel.str());
+                commands.push(Command::new(
+                    label,
+                    vec![msg(HashMsg::SelectRow(row)), msg(HashMsg::Copy)],
+                ));
+            }
+        } else {
+            commands.push(Command::new(
+                "Hash: copy output",
+                vec![
+                    msg(HashMsg::SelectItem(PageItems::Output)),
+                    msg(HashMsg::Copy),
+                ],
+            ));
+        }
+        commands
+    }
+
+    fn save(&self, session: &mut Session) {
+        session.hash = Some(HashSession {
+            view: self.cur.view_sel,
+            algorithm: self.cur.algo_sel,
+            output_len: self.cur.output_len,
+            mode: self.cur.mode_sel,
+            key_encoding: self.cur.key_enc_sel,
+            encoding: self.cur.enc_sel,
+            newline: self.cur.newline_sel,
+            format: self.cur.format_sel,
+            input: self.cur.input.clone(),
+            expected: self.cur.expected.value().into(),
+        });
+    }
+
+    fn restore(&mut self, session: &Session) {
+        let Some(saved) = &session.hash else {
+            return;
+        };
+        self.cur.view_sel = saved.view;
+        self.cur.algo_sel = saved.algorithm;
+        if OUTPUT_LENS.contains(&saved.output_len) {
+            self.cur.output_len = saved.output_len;
+        }
+        self.cur.mode_sel = saved.mode;
+        self.cur.key_enc_sel = saved.key_encoding;
+        self.cur.enc_sel = saved.encoding;
+        self.cur.newline_sel = saved.newline;
+        self.cur.format_sel = saved.format;
+        self.cur.input = saved.input.clone();
+        self.cur.expected = Input::new(saved.expected.clone());
+        self.update_hash();
+    }
 }
 
 impl HashPage {
+    /// Moves through the rows of the all algorithms view before leaving the output.
     fn select_next_item(&mut self) {
+        let rows = self.cur.all_digests.len();
+        if self.is_all_output() && self.cur.all_row + 1 < rows {
+            self.cur.all_row += 1;
+            return;
+        }
         self.cur.item = self.cur.item.next();
+        while !self.is_visible(self.cur.item) {
+            self.cur.item = self.cur.item.next();
+        }
+        if self.is_all_output() {
+            self.cur.all_row = 0;
+        }
     }
 
     fn select_prev_item(&mut self) {
+        if self.is_all_output() && self.cur.all_row > 0 {
+            self.cur.all_row -= 1;
+            return;
+        }
         self.cur.item = self.cur.item.prev();
+        while !self.is_visible(self.cur.item) {
+            self.cur.item = self.cur.item.prev();
+        }
+        if self.is_all_output() {
+            self.cur.all_row = self.cur.all_digests.len().saturating_sub(1);
+        }
+    }
+
+    fn ensure_item_visible(&mut self) {
+        if !self.is_visible(self.cur.item) {
+            self.cur.item = PageItems::View;
+        }
+    }
+
+    fn is_all_view(&self) -> bool {
+        self.cur.view_sel == ViewItemSelect::All
+    }
+
+    fn is_all_output(&self) -> bool {
+        self.is_all_view() && self.cur.item == PageItems::Output
+    }
+
+    /// The output length is only chosen for SHAKE128 and SHAKE256, the key only for HMAC.
+    /// All the algorithms are listed in the all algorithms view, the output length is for the SHAKE rows.
+    fn is_visible(&self, item: PageItems) -> bool {
+        match item {
+            PageItems::Algo => !self.is_all_view(),
+            PageItems::OutputLen => self.is_all_view() || self.algorithm().is_xof(),
+            PageItems::KeyEncoding | PageItems::Key => self.cur.mode_sel == ModeItemSelect::Hmac,
+            PageItems::Input | PageItems::Expected | PageItems::Output => {
+                self.cur.manifest.is_none()
+            }
+            PageItems::Manifest => self.cur.manifest.is_some(),
+            PageItems::View
+            | PageItems::Mode
+            | PageItems::Encode
+            | PageItems::Newline
+            | PageItems::Format => true,
+        }
+    }
+
+    fn algorithm(&self) -> HashAlgorithm {
+        self.cur.algo_sel.into()
+    }
+
+    fn output_len_index(&self) -> usize {
+        OUTPUT_LENS
+            .iter()
+            .position(|len| *len == self.cur.output_len)
+            .unwrap_or_default()
+    }
+
+    /// Returns the decoded HMAC key, `None` for plain digests.
+    fn key(&self) -> Result<Option<Vec<u8>>, HashError> {
+        match self.cur.mode_sel {
+            ModeItemSelect::Digest => Ok(None),
+            ModeItemSelect::Hmac => {
+                hash::decode_key(self.cur.key.value(), self.cur.key_enc_sel.into()).map(Some)
+            }
+        }
+    }
+
+    fn hasher(&self, algorithm: HashAlgorithm, key: Option<&[u8]>) -> Hasher {
+        let output_len = Some(self.cur.output_len);
+        match key {
+            Some(key) => Hasher::new_hmac(algorithm, output_len, key),
+            None => Hasher::new(algorithm, output_len),
+        }
+    }
+
+    /// Returns the work hashing the file in the background.
+    /// The digests of all the algorithms are concatenated since the file is read once for them.
+    fn file_work(&self, key: Option<&[u8]>) -> FileWork {
+        if self.is_all_view() {
+            let hashers = HashAlgorithm::ALL.map(|algorithm| self.hasher(algorithm, key));
+            Box::new(move |r| hash::digest_reader_all(hashers.into(), r).map(|d| d.concat()))
+        } else {
+            let hasher = self.hasher(self.algorithm(), key);
+            Box::new(move |r| hash::digest_reader(hasher, r))
+        }
+    }
+
+    /// Returns the length of the digest of the algorithm with the current output length.
+    fn digest_len(&self, algorithm: HashAlgorithm) -> usize {
+        if algorithm.is_xof() {
+            self.cur.output_len
+        } else {
+            algorithm.output_len()
+        }
     }
 
     fn current_item_select_next(&mut self) {
         match self.cur.item {
+            PageItems::View => {
+                self.cur.view_sel.next_mut();
+                self.ensure_item_visible();
+                self.update_hash();
+            }
             PageItems::Algo => {
                 self.cur.algo_sel.next_mut();
                 self.update_hash();
             }
+            PageItems::OutputLen => {
+                if let Some(len) = OUTPUT_LENS.get(self.output_len_index() + 1) {
+                    self.cur.output_len = *len;
+                    self.update_hash();
+                }
+            }
+            PageItems::Mode => {
+                self.cur.mode_sel.next_mut();
+                self.update_hash();
+            }
+            PageItems::KeyEncoding => {
+                self.cur.key_enc_sel.next_mut();
+                self.update_hash();
+            }
             PageItems::Encode => {
                 self.cur.enc_sel.next_mut();
                 self.update_hash();
             }
+            PageItems::Newline => {
+                self.cur.newline_sel.next_mut();
+                self.update_hash();
+            }
+            PageItems::Format => {
+                self.cur.format_sel.next_mut();
+                self.update_output();
+            }
+            PageItems::Key => {}
             PageItems::Input => {}
+            PageItems::Expected => {}
+            PageItems::Manifest => {}
             PageItems::Output => {}
         }
     }
 
     fn current_item_select_prev(&mut self) {
         match self.cur.item {
+            PageItems::View => {
+                self.cur.view_sel.prev_mut();
+                self.ensure_item_visible();
+                self.update_hash();
+            }
             PageItems::Algo => {
                 self.cur.algo_sel.prev_mut();
                 self.update_hash();
             }
+            PageItems::OutputLen => {
+                if let Some(i) = self.output_len_index().checked_sub(1) {
+                    self.cur.output_len = OUTPUT_LENS[i];
+                    self.update_hash();
+                }
+            }
+            PageItems::Mode => {
+                self.cur.mode_sel.prev_mut();
+                self.update_hash();
+            }
+            PageItems::KeyEncoding => {
+                self.cur.key_enc_sel.prev_mut();
+                self.update_hash();
+            }
             PageItems::Encode => {
                 self.cur.enc_sel.prev_mut();
                 self.update_hash();
             }
+            PageItems::Newline => {
+                self.cur.newline_sel.prev_mut();
+                self.update_hash();
+            }
+            PageItems::Format => {
+                self.cur.format_sel.prev_mut();
+                self.update_output();
+            }
+            PageItems::Key => {}
             PageItems::Input => {}
+            PageItems::Expected => {}
+            PageItems::Manifest => {}
             PageItems::Output => {}
         }
     }
 
     fn scroll_down(&mut self) {
+        if matches!(self.cur.item, PageItems::Manifest) {
+            self.cur.manifest_state.scroll_down();
+            return;
+        }
         if !matches!(self.cur.item, PageItems::Input) || self.cur.input.is_empty() {
             return;
         }
@@ -273,6 +1300,10 @@ impl HashPage {
     }
 
     fn scroll_up(&mut self) {
+        if matches!(self.cur.item, PageItems::Manifest) {
+            self.cur.manifest_state.scroll_up();
+            return;
+        }
         if !matches!(self.cur.item, PageItems::Input) || self.cur.input.is_empty() {
             return;
         }
@@ -280,44 +1311,465 @@ impl HashPage {
     }
 
     fn copy_to_clipboard(&self) -> Option<Msg> {
+        if let (PageItems::Manifest, Some(job)) = (self.cur.item, &self.cur.manifest) {
+            return util::copy_to_clipboard(&job.text());
+        }
         if !matches!(self.cur.item, PageItems::Output) {
             return None;
         }
+        if self.is_all_view() {
+            let digest = self.cur.all_digests.get(self.cur.all_row)?;
+            let text = hash::format_digest(digest, self.cur.format_sel.into());
+            return util::copy_to_clipboard(&text);
+        }
 
         let text = &self.cur.output;
         util::copy_to_clipboard(text)
     }
 
-    fn paste_from_clipboard(&mut self) {
-        if matches!(self.cur.item, PageItems::Input) {
-            self.cur.input = util::paste_from_clipboard().unwrap();
+    fn paste_from_clipboard(&mut self) -> Option<Msg> {
+        if !matches!(
+            self.cur.item,
+            PageItems::Input | PageItems::Key | PageItems::Expected
+        ) {
+            return None;
+        }
+
+        let text = match util::paste_from_clipboard() {
+            Ok(text) => text,
+            Err(msg) => return Some(msg),
+        };
+        match self.cur.item {
+            PageItems::Key => {
+                self.cur.key = Input::new(text.trim_end_matches(['\r', '\n']).into());
+            }
+            PageItems::Expected => {
+                // a single line input, the whitespace is ignored anyway
+                let text = text.trim().replace(['\r', '\n'], " ");
+                self.cur.expected = Input::new(text);
+                if self.is_all_view() {
+                    return None;
+                }
+                return self.detect_algorithm();
+            }
+            _ => {
+                self.cur.input = text;
+                self.cur.file = None;
+                self.cur.manifest = None;
+            }
+        }
+        self.update_hash();
+        None
+    }
+
+    /// Returns whether the output matches the expected digest, `None` if either is empty.
+    /// In the all algorithms view, whether the digest of any algorithm matches.
+    fn verification(&self) -> Option<Result<bool, HashError>> {
+        let computed = if self.is_all_view() {
+            !self.cur.all_digests.is_empty()
+        } else {
+            !self.cur.digest.is_empty()
+        };
+        if self.cur.expected.value().trim().is_empty() || !computed {
+            return None;
+        }
+        let expected = match hash::parse_expected(self.cur.expected.value()) {
+            Ok(expected) => expected,
+            Err(e) => return Some(Err(e)),
+        };
+        if self.is_all_view() {
+            return Some(Ok(!self.matched_rows().is_empty()));
+        }
+        let hex = base16ct::lower::encode_string(&self.cur.digest);
+        Some(Ok(expected.matches(&hex)))
+    }
+
+    /// Returns the rows of the all algorithms view whose digest matches the expected one.
+    fn matched_rows(&self) -> Vec<usize> {
+        let Ok(expected) = hash::parse_expected(self.cur.expected.value()) else {
+            return Vec::new();
+        };
+        self.cur
+            .all_digests
+            .iter()
+            .enumerate()
+            .filter(|(_, digest)| expected.matches(&base16ct::lower::encode_string(digest)))
+            .map(|(row, _)| row)
+            .collect()
+    }
+
+    /// Switches to the first algorithm whose digest has the length of the expected one,
+    /// unless the current algorithm already has it.
+    fn detect_algorithm(&mut self) -> Option<Msg> {
+        let expected = hash::parse_expected(self.cur.expected.value()).ok()?;
+        let len = expected.len();
+        let algorithm = self.algorithm();
+        if algorithm.is_xof() {
+            if OUTPUT_LENS.contains(&len) && self.cur.output_len != len {
+                self.cur.output_len = len;
+                self.update_hash();
+            }
+            return None;
+        }
+        if algorithm.output_len() == len {
+            return None;
+        }
+
+        let detected = *hash::algorithms_with_len(len).first()?;
+        self.cur.algo_sel = AlgoItemSelect::vars_vec()
+            .into_iter()
+            .find(|algo_sel| HashAlgorithm::from(*algo_sel) == detected)?;
+        self.ensure_item_visible();
+        self.update_hash();
+        let msg = format!(
+            "Switched to {} by the digest length",
+            self.cur.algo_sel.str()
+        );
+        Some(Msg::NotifyInfo(msg))
+    }
 
+    fn pick_confirm(&mut self) {
+        let Some(picker) = self.cur.picker.take() else {
+            return;
+        };
+        if let Some(i) = picker.selected() {
+            self.cur.algo_sel = AlgoItemSelect::vars_vec()[i];
+            self.ensure_item_visible();
             self.update_hash();
         }
     }
 
+    fn load_confirm(&mut self) -> Option<Msg> {
+        let PromptResult::Open(path) = self.cur.prompt.as_mut()?.confirm() else {
+            return None;
+        };
+        self.cur.prompt = None;
+        if path.is_empty() {
+            return None;
+        }
+
+        match self.cur.prompt_target {
+            PromptTarget::File => self.load_file(&path),
+            PromptTarget::VerifyManifest => {
+                let msg = Msg::Page(PageMsg::Hash(HashMsg::ManifestProgress));
+                self.start_manifest(ManifestJob::verify(&path, self.algorithm(), msg));
+                None
+            }
+            PromptTarget::GenerateManifest => {
+                let msg = Msg::Page(PageMsg::Hash(HashMsg::ManifestProgress));
+                self.start_manifest(ManifestJob::generate(&path, self.algorithm(), msg));
+                None
+            }
+            PromptTarget::SaveManifest => {
+                let text = self.manifest_text()?;
+                Some(util::save_to_file(&path, text.as_bytes()))
+            }
+        }
+    }
+
+    fn load_file(&mut self, path: &str) -> Option<Msg> {
+        let work = match self.key() {
+            Ok(key) => self.file_work(key.as_deref()),
+            Err(e) => return Some(Msg::NotifyWarn(e.to_string())),
+        };
+        let msg = Msg::Page(PageMsg::Hash(HashMsg::LoadProgress));
+        match FileLoad::start(path, None, msg, work) {
+            Ok(file) => {
+                self.cur.file = Some(file);
+                self.cur.manifest = None;
+                self.ensure_item_visible();
+                self.cur.input.clear();
+                self.cur.input_state = ScrollOutputState::default();
+                self.clear_digests();
+                None
+            }
+            Err(e) => Some(Msg::NotifyError(e)),
+        }
+    }
+
+    fn load_progress(&mut self) -> Option<Msg> {
+        let file = self.cur.file.as_mut()?;
+        if !file.poll() {
+            return None;
+        }
+        match &file.state {
+            // the digest with a previous key is discarded once the key is invalid
+            LoadState::Done(_) if self.cur.error.is_some() => None,
+            LoadState::Done(digest) => {
+                let digest = digest.clone();
+                if self.is_all_view() {
+                    self.split_all_digests(&digest);
+                } else {
+                    self.set_digest(digest);
+                }
+                None
+            }
+            LoadState::Failed(e) => Some(Msg::NotifyError(e.clone())),
+            LoadState::Loading => None,
+        }
+    }
+
+    fn start_manifest(&mut self, job: ManifestJob) {
+        self.cur.manifest = Some(job);
+        self.cur.manifest_state = ScrollOutputState::default();
+        self.cur.item = PageItems::Manifest;
+    }
+
+    fn manifest_progress(&mut self) -> Option<Msg> {
+        let job = self.cur.manifest.as_mut()?;
+        if !job.poll() {
+            return None;
+        }
+        match &job.state {
+            JobState::Failed(e) => Some(Msg::NotifyError(e.clone())),
+            JobState::Done | JobState::Running => None,
+        }
+    }
+
+    /// Returns the generated manifest once it is complete.
+    fn manifest_text(&self) -> Option<String> {
+        self.cur.manifest.as_ref()?.manifest_text()
+    }
+
+    /// Opens the prompt to save the generated manifest, into the directory by default.
+    fn save_start(&mut self) {
+        let Some(job) = &self.cur.manifest else {
+            return;
+        };
+        if self.manifest_text().is_none() {
+            return;
+        }
+        let name = manifest::file_name(self.algorithm());
+        let path = format!("{}/{name}", job.path.trim_end_matches('/'));
+        self.cur.prompt_target = PromptTarget::SaveManifest;
+        self.cur.prompt = Some(PathPrompt::with_title("Save checksum file", &path));
+    }
+
+    /// Streams the file again if loaded, the input encoding does not apply to it.
+    /// A generated manifest is generated again with the algorithm.
     fn update_hash(&mut self) {
-        self.cur.output = calculate_hash(&self.cur.input, self.cur.algo_sel);
+        if let Some(job) = &self.cur.manifest {
+            if job.kind == ManifestKind::Generate && job.algorithm != Some(self.algorithm()) {
+                let path = job.path.clone();
+                let msg = Msg::Page(PageMsg::Hash(HashMsg::ManifestProgress));
+                self.start_manifest(ManifestJob::generate(&path, self.algorithm(), msg));
+            }
+        }
+
+        let key = match self.key() {
+            Ok(key) => key,
+            Err(e) => {
+                self.clear_digests();
+                self.cur.error = Some(e.to_string());
+                return;
+            }
+        };
+        self.cur.error = None;
+
+        if self.cur.file.is_some() {
+            let work = self.file_work(key.as_deref());
+            let msg = Msg::Page(PageMsg::Hash(HashMsg::LoadProgress));
+            if let Some(file) = &mut self.cur.file {
+                file.restart(msg, work);
+            }
+            self.clear_digests();
+            return;
+        }
+
+        if self.is_all_view() {
+            self.update_all_digests(key.as_deref());
+            return;
+        }
+
+        let req = HashRequest {
+            input: &self.cur.input,
+            algorithm: self.cur.algo_sel.into(),
+            encoding: self.cur.enc_sel.into(),
+            strip_newline: self.cur.newline_sel == NewlineItemSelect::Strip,
+            output_len: Some(self.cur.output_len),
+            key: key.as_deref(),
+        };
+        match hash::calculate(&req) {
+            Ok(res) => self.set_digest(res.digest),
+            Err(e) => {
+                self.set_digest(Vec::new());
+                self.cur.error = Some(e.to_string());
+            }
+        }
+        self.cur.all_digests.clear();
     }
-}
 
-fn calculate_hash(input: &str, algo_sel: AlgoItemSelect) -> String {
-    let input_bytes = input.as_bytes();
-    match algo_sel {
-        AlgoItemSelect::Md5 => hash_to_str(&Md5::digest(input_bytes)),
-        AlgoItemSelect::Sha1 => hash_to_str(&Sha1::digest(input_bytes)),
-        AlgoItemSelect::Sha224 => hash_to_str(&Sha224::digest(input_bytes)),
-        AlgoItemSelect::Sha256 => hash_to_str(&Sha256::digest(input_bytes)),
-        AlgoItemSelect::Sha384 => hash_to_str(&Sha384::digest(input_bytes)),
-        AlgoItemSelect::Sha512_224 => hash_to_str(&Sha512_224::digest(input_bytes)),
-        AlgoItemSelect::Sha512_256 => hash_to_str(&Sha512_256::digest(input_bytes)),
-        AlgoItemSelect::Sha512 => hash_to_str(&Sha512::digest(input_bytes)),
+    /// Hashes the input with every algorithm, the input is encoded only once.
+    fn update_all_digests(&mut self, key: Option<&[u8]>) {
+        self.set_digest(Vec::new());
+        let strip_newline = self.cur.newline_sel == NewlineItemSelect::Strip;
+        let bytes =
+            match hash::encode_input(&self.cur.input, self.cur.enc_sel.into(), strip_newline) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    self.cur.all_digests.clear();
+                    self.cur.error = Some(e.to_string());
+                    return;
+                }
+            };
+        self.cur.all_digests = HashAlgorithm::ALL
+            .into_iter()
+            .map(|algorithm| {
+                let mut hasher = self.hasher(algorithm, key);
+                hasher.update(&bytes);
+                hasher.finalize()
+            })
+            .collect();
+    }
+
+    /// Splits the concatenated digests of all the algorithms computed from the file.
+    fn split_all_digests(&mut self, mut digests: &[u8]) {
+        self.cur.all_digests = HashAlgorithm::ALL
+            .into_iter()
+            .map(|algorithm| {
+                let (digest, rest) = digests.split_at(self.digest_len(algorithm));
+                digests = rest;
+                digest.to_vec()
+            })
+            .collect();
+    }
+
+    fn clear_digests(&mut self) {
+        self.set_digest(Vec::new());
+        self.cur.all_digests.clear();
+    }
+
+    fn set_digest(&mut self, digest: Vec<u8>) {
+        self.cur.digest = digest;
+        self.update_output();
+    }
+
+    fn update_output(&mut self) {
+        self.cur.output = if self.cur.digest.is_empty() {
+            String::new()
+        } else {
+            hash::format_digest(&self.cur.digest, self.cur.format_sel.into())
+        };
+    }
+
+    fn render_output(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        theme: &Theme,
+        verification: &Option<Result<bool, HashError>>,
+    ) {
+        let output_style = theme.item_style(self.focused, self.cur.item == PageItems::Output);
+
+        let output_text = match (&self.cur.error, verification) {
+            (Some(error), _) => Line::styled(error.clone(), theme.error),
+            (None, Some(Ok(true))) => Line::styled(self.cur.output.clone(), theme.info),
+            (None, Some(Ok(false))) => Line::styled(self.cur.output.clone(), theme.error),
+            (None, _) => Line::raw(self.cur.output.clone()),
+        };
+        let output = Paragraph::new(output_text)
+            .block(
+                Block::bordered()
+                    .style(output_style)
+                    .title("Output")
+                    .padding(Padding::horizontal(1)),
+            )
+            .wrap(Wrap { trim: false });
+        f.render_widget(output, area);
+    }
+
+    /// Renders the digests of every algorithm, one per row, highlighting those matching the expected.
+    fn render_all_digests(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let selected = self.cur.item == PageItems::Output;
+        let lines: Vec<Line> = if let Some(error) = &self.cur.error {
+            vec![Line::styled(error.clone(), theme.error)]
+        } else {
+            let matched = self.matched_rows();
+            AlgoItemSelect::vars_vec()
+                .into_iter()
+                .zip(&self.cur.all_digests)
+                .enumerate()
+                .map(|(row, (algo_sel, digest))| {
+                    let output = hash::format_digest(digest, self.cur.format_sel.into());
+                    let (mark, mut style) = if matched.contains(&row) {
+                        ("✔ ", theme.info.add_modifier(Modifier::BOLD))
+                    } else {
+                        ("  ", Style::default())
+                    };
+                    if self.focused && selected && row == self.cur.all_row {
+                        style = style.patch(theme.selected);
+                    }
+                    Line::styled(format!("{mark}{:<12}{output}", algo_sel.str()), style)
+                })
+                .collect()
+        };
+
+        // keeps the selected row in sight
+        let height = (area.height as usize).saturating_sub(2);
+        let state = &mut self.cur.all_state;
+        if self.cur.all_row < state.offset {
+            state.offset = self.cur.all_row;
+        } else if height > 0 && self.cur.all_row >= state.offset + height {
+            state.offset = self.cur.all_row + 1 - height;
+        }
+
+        let output = ScrollOutput::new(lines, self.focused, selected, theme)
+            .title("Output (all algorithms)");
+        f.render_stateful_widget(output, area, &mut self.cur.all_state);
+    }
+
+    fn render_text_input(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        theme: &Theme,
+        item: PageItems,
+        title: Line,
+        status: Option<Line>,
+    ) {
+        let input = match item {
+            PageItems::Key => &self.cur.key,
+            _ => &self.cur.expected,
+        };
+        let style = theme.item_style(self.focused, self.cur.item == item);
+
+        let mut block = Block::bordered()
+            .style(style)
+            .title(title)
+            .padding(Padding::horizontal(1));
+        if let Some(status) = status {
+            block = block.title(status.right_aligned());
+        }
+        let editing = self.cur.edit && self.cur.item == item;
+        util::render_input(f, area, input, block, editing);
     }
 }
 
-fn hash_to_str(hash: &[u8]) -> String {
-    let mut buf = [0u8; 128];
-    base16ct::lower::encode_str(hash, &mut buf)
-        .unwrap()
-        .to_string()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_render_long_multibyte_key() {
+        let mut page = HashPage::new(true, &HashConfig::default());
+        page.cur.mode_sel = ModeItemSelect::Hmac;
+        page.cur.key = Input::new(format!("a{}", "é".repeat(50)));
+        page.cur.item = PageItems::Key;
+        page.cur.edit = true;
+        page.update_hash();
+
+        let mut terminal = Terminal::new(TestBackend::new(41, 40)).unwrap();
+        terminal
+            .draw(|f| page.render(f, f.area(), &Theme::default()))
+            .unwrap();
+
+        // the end of the key is shown, with the cursor after it
+        let buffer = terminal.backend().buffer();
+        let row: String = (0..41)
+            .map(|x| buffer[(x, 9)].symbol().to_string())
+            .collect();
+        assert_eq!(row, format!("│ {} │", "é".repeat(37)));
+        terminal.backend_mut().assert_cursor_position((39, 9));
+    }
 }
@@ -0,0 +1,410 @@
+use std::{
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use btox::{
+    hash::{self, HashAlgorithm, Hasher},
+    manifest::{self, ManifestEntry},
+};
+use ratatui::{
+    style::Modifier,
+    text::{Line, Span},
+};
+
+use crate::{event, msg::Msg, pages::util, theme::Theme};
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestKind {
+    /// Checks the files listed in a manifest, like `sha256sum -c`.
+    Verify,
+    /// Hashes every file in a directory into a manifest.
+    Generate,
+}
+
+/// Verifies or generates a checksum manifest on a background thread, file by file.
+pub struct ManifestJob {
+    pub kind: ManifestKind,
+    /// The manifest or the directory, as entered.
+    pub path: String,
+    /// The algorithm, known once the manifest is read on verification.
+    pub algorithm: Option<HashAlgorithm>,
+    pub total: usize,
+    pub rows: Vec<ManifestRow>,
+    pub state: JobState,
+    rx: mpsc::Receiver<JobEvent>,
+}
+
+pub enum JobState {
+    Running,
+    Done,
+    Failed(String),
+}
+
+pub struct ManifestRow {
+    /// The path relative to the manifest or the directory.
+    pub path: String,
+    pub status: RowStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RowStatus {
+    Ok,
+    Failed,
+    Missing,
+    /// The file exists but could not be read, with the reason.
+    Unreadable(String),
+    /// The digest in lowercase hex, on generation.
+    Hashed(String),
+}
+
+enum JobEvent {
+    Start {
+        algorithm: HashAlgorithm,
+        total: usize,
+    },
+    Row(ManifestRow),
+    Done(Result<(), String>),
+}
+
+impl ManifestJob {
+    /// Starts verifying the manifest, the paths in it are relative to its directory.
+    /// The algorithm is detected by its name or the digest length, preferring `current`.
+    pub fn verify(path: &str, current: HashAlgorithm, msg: Msg) -> ManifestJob {
+        let expanded = util::expand_path(path);
+        let display = path.to_string();
+        let rx = spawn(msg, move |tx| {
+            let text = fs::read_to_string(&expanded)
+                .map_err(|e| format!("Could not read {display}: {e}"))?;
+            let entries = manifest::parse(&text).map_err(|e| format!("{display}: {e}"))?;
+            let name = expanded
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let algorithm = manifest::detect_algorithm(&name, &entries, current)
+                .map_err(|e| format!("{display}: {e}"))?;
+            let dir = expanded.parent().map(Path::to_path_buf).unwrap_or_default();
+            verify_entries(tx, algorithm, &dir, &entries);
+            Ok(())
+        });
+        ManifestJob::new(ManifestKind::Verify, path, None, rx)
+    }
+
+    /// Starts hashing the files in the directory and its subdirectories, except hidden ones
+    /// and a manifest of the same name as the one generated.
+    pub fn generate(path: &str, algorithm: HashAlgorithm, msg: Msg) -> ManifestJob {
+        let expanded = util::expand_path(path);
+        let display = path.to_string();
+        let rx = spawn(msg, move |tx| {
+            let mut files =
+                list_files(&expanded).map_err(|e| format!("Could not read {display}: {e}"))?;
+            let manifest_name = manifest::file_name(algorithm);
+            files.retain(|file| *file != manifest_name);
+            generate_entries(tx, algorithm, &expanded, &files);
+            Ok(())
+        });
+        ManifestJob::new(ManifestKind::Generate, path, Some(algorithm), rx)
+    }
+
+    fn new(
+        kind: ManifestKind,
+        path: &str,
+        algorithm: Option<HashAlgorithm>,
+        rx: mpsc::Receiver<JobEvent>,
+    ) -> ManifestJob {
+        ManifestJob {
+            kind,
+            path: path.into(),
+            algorithm,
+            total: 0,
+            rows: Vec::new(),
+            state: JobState::Running,
+            rx,
+        }
+    }
+
+    /// Applies the events received so far, returns whether the job finished with them.
+    pub fn poll(&mut self) -> bool {
+        let mut finished = false;
+        while let Ok(e) = self.rx.try_recv() {
+            match e {
+                JobEvent::Start { algorithm, total } => {
+                    self.algorithm = Some(algorithm);
+                    self.total = total;
+                }
+                JobEvent::Row(row) => self.rows.push(row),
+                JobEvent::Done(result) => {
+                    self.state = match result {
+                        Ok(()) => JobState::Done,
+                        Err(e) => JobState::Failed(e),
+                    };
+                    finished = true;
+                }
+            }
+        }
+        finished
+    }
+
+    /// Returns the generated manifest once all files are hashed, without unreadable files.
+    pub fn manifest_text(&self) -> Option<String> {
+        if self.kind != ManifestKind::Generate || !matches!(self.state, JobState::Done) {
+            return None;
+        }
+        let text = self
+            .rows
+            .iter()
+            .filter_map(|row| match &row.status {
+                RowStatus::Hashed(hex) => Some(format!("{hex}  {}\n", row.path)),
+                _ => None,
+            })
+            .collect();
+        Some(text)
+    }
+
+    /// Returns the block title with the progress or the counts of each result.
+    pub fn title(&self) -> String {
+        let algorithm = self.algorithm.map(|a| a.name()).unwrap_or("?");
+        let action = match self.kind {
+            ManifestKind::Verify => "Verify",
+            ManifestKind::Generate => "Generate",
+        };
+        let progress = match &self.state {
+            JobState::Running => format!("{} / {} files", self.rows.len(), self.total),
+            JobState::Failed(_) => "failed".into(),
+            JobState::Done => {
+                let count = |f: fn(&RowStatus) -> bool| {
+                    self.rows.iter().filter(|row| f(&row.status)).count()
+                };
+                match self.kind {
+                    ManifestKind::Verify => format!(
+                        "{} OK, {} FAILED, {} MISSING",
+                        count(|s| *s == RowStatus::Ok),
+                        count(|s| matches!(s, RowStatus::Failed | RowStatus::Unreadable(_))),
+                        count(|s| *s == RowStatus::Missing),
+                    ),
+                    ManifestKind::Generate => format!("{} files", self.rows.len()),
+                }
+            }
+        };
+        format!("{action} {} ({algorithm}): {progress}", self.path)
+    }
+
+    pub fn lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        if let JobState::Failed(e) = &self.state {
+            return vec![Line::styled(e.clone(), theme.error)];
+        }
+        if self.kind == ManifestKind::Generate {
+            return self.rows.iter().map(|row| row_line(row, theme)).collect();
+        }
+        // failures first, as they are what is looked for in a long list
+        let (ok, failed): (Vec<_>, Vec<_>) = self
+            .rows
+            .iter()
+            .partition(|row| row.status == RowStatus::Ok);
+        failed
+            .into_iter()
+            .chain(ok)
+            .map(|row| row_line(row, theme))
+            .collect()
+    }
+
+    /// Returns the lines as plain text to copy, the manifest itself on generation.
+    pub fn text(&self) -> String {
+        if let Some(text) = self.manifest_text() {
+            return text;
+        }
+        self.lines(&Theme::default())
+            .iter()
+            .map(|line| format!("{line}\n"))
+            .collect()
+    }
+}
+
+fn row_line(row: &ManifestRow, theme: &Theme) -> Line<'static> {
+    let bold = Modifier::BOLD;
+    let status = match &row.status {
+        RowStatus::Ok => Span::styled("OK      ", theme.info),
+        RowStatus::Failed => Span::styled("FAILED  ", theme.error.add_modifier(bold)),
+        RowStatus::Missing => Span::styled("MISSING ", theme.warn.add_modifier(bold)),
+        RowStatus::Unreadable(_) => Span::styled("FAILED  ", theme.error.add_modifier(bold)),
+        RowStatus::Hashed(hex) => Span::raw(format!("{hex}  ")),
+    };
+    let mut spans = vec![status, Span::raw(row.path.clone())];
+    if let RowStatus::Unreadable(e) = &row.status {
+        spans.push(Span::styled(format!(" ({e})"), theme.disabled));
+    }
+    Line::from(spans)
+}
+
+/// Runs the work on a new thread, the receiver is dropped to stop it after the current file.
+fn spawn<F>(msg: Msg, work: F) -> mpsc::Receiver<JobEvent>
+where
+    F: FnOnce(&mut JobSender) -> Result<(), String> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut sender = JobSender {
+            tx,
+            msg,
+            last_sent: Instant::now(),
+            cancelled: false,
+        };
+        let result = work(&mut sender);
+        if !sender.cancelled && sender.tx.send(JobEvent::Done(result)).is_ok() {
+            event::send(sender.msg.clone());
+        }
+    });
+    rx
+}
+
+/// Sends the events to the page, notifying the app at most once per interval.
+struct JobSender {
+    tx: mpsc::Sender<JobEvent>,
+    msg: Msg,
+    last_sent: Instant,
+    cancelled: bool,
+}
+
+impl JobSender {
+    /// Returns false once the page no longer waits for the job.
+    fn send(&mut self, e: JobEvent) -> bool {
+        if self.tx.send(e).is_err() {
+            self.cancelled = true;
+            return false;
+        }
+        if self.last_sent.elapsed() >= PROGRESS_INTERVAL {
+            self.last_sent = Instant::now();
+            event::send(self.msg.clone());
+        }
+        true
+    }
+}
+
+fn verify_entries(
+    tx: &mut JobSender,
+    algorithm: HashAlgorithm,
+    dir: &Path,
+    entries: &[ManifestEntry],
+) {
+    let total = entries.len();
+    if !tx.send(JobEvent::Start { algorithm, total }) {
+        return;
+    }
+    for entry in entries {
+        let status = match digest_file(algorithm, Some(entry.len()), &dir.join(&entry.path)) {
+            Ok(hex) if hex == entry.hex => RowStatus::Ok,
+            Ok(_) => RowStatus::Failed,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => RowStatus::Missing,
+            Err(e) => RowStatus::Unreadable(e.to_string()),
+        };
+        let path = entry.path.clone();
+        if !tx.send(JobEvent::Row(ManifestRow { path, status })) {
+            return;
+        }
+    }
+}
+
+fn generate_entries(tx: &mut JobSender, algorithm: HashAlgorithm, dir: &Path, files: &[String]) {
+    let total = files.len();
+    if !tx.send(JobEvent::Start { algorithm, total }) {
+        return;
+    }
+    for file in files {
+        let status = match digest_file(algorithm, None, &dir.join(file)) {
+            Ok(hex) => RowStatus::Hashed(hex),
+            Err(e) => RowStatus::Unreadable(e.to_string()),
+        };
+        let path = file.clone();
+        if !tx.send(JobEvent::Row(ManifestRow { path, status })) {
+            return;
+        }
+    }
+}
+
+fn digest_file(
+    algorithm: HashAlgorithm,
+    output_len: Option<usize>,
+    path: &Path,
+) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let digest = hash::digest_reader(Hasher::new(algorithm, output_len), &mut file)?;
+    Ok(base16ct::lower::encode_string(&digest))
+}
+
+/// Returns the paths of the files under the directory relative to it, separated by `/` and sorted.
+/// Hidden entries are skipped, and symbolic links to directories are not followed.
+fn list_files(root: &Path) -> io::Result<Vec<String>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![PathBuf::new()];
+    while let Some(rel) = dirs.pop() {
+        for entry in fs::read_dir(root.join(&rel))? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with('.') {
+                continue;
+            }
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                dirs.push(rel.join(&name));
+            } else if file_type.is_file() || entry.path().is_file() {
+                let path = rel.join(&name);
+                files.push(path.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::PageMsg;
+
+    fn wait(job: &mut ManifestJob) {
+        while !job.poll() {
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_generate_and_verify() {
+        let dir = std::env::temp_dir().join(format!("btox_manifest_{}", std::process::id()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("a.txt"), "abc").unwrap();
+        fs::write(dir.join("sub/b.txt"), "").unwrap();
+        fs::write(dir.join(".hidden"), "").unwrap();
+        let path = dir.display().to_string();
+        let msg = Msg::Page(PageMsg::Hash(crate::msg::HashMsg::ManifestProgress));
+
+        let mut job = ManifestJob::generate(&path, HashAlgorithm::Md5, msg.clone());
+        wait(&mut job);
+        let text = job.manifest_text().unwrap();
+        assert_eq!(
+            text,
+            "900150983cd24fb0d6963f7d28e17f72  a.txt\nd41d8cd98f00b204e9800998ecf8427e  sub/b.txt\n"
+        );
+
+        let manifest = format!("{text}00000000000000000000000000000000  missing.txt\n");
+        fs::write(dir.join("MD5SUMS"), manifest).unwrap();
+        fs::write(dir.join("sub/b.txt"), "changed").unwrap();
+        let manifest_path = format!("{path}/MD5SUMS");
+        let mut job = ManifestJob::verify(&manifest_path, HashAlgorithm::Sha256, msg);
+        wait(&mut job);
+        assert_eq!(job.algorithm, Some(HashAlgorithm::Md5));
+        let statuses: Vec<_> = job.rows.iter().map(|row| row.status.clone()).collect();
+        assert_eq!(
+            statuses,
+            vec![RowStatus::Ok, RowStatus::Failed, RowStatus::Missing]
+        );
+        assert!(job.title().ends_with("1 OK, 1 FAILED, 1 MISSING"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
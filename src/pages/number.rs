@@ -1,8 +1,8 @@
+use btox::number::{self, Case, NumberRequest, Radix};
 use itsuki::zero_indexed_enum;
 use ratatui::{
-    crossterm::event::KeyCode,
+    crossterm::event::KeyEvent,
     layout::Rect,
-    style::{Color, Style},
     widgets::{Block, Borders, Padding, Paragraph},
     Frame,
 };
@@ -10,11 +10,17 @@ use ratatui_macros::vertical;
 use tui_input::{backend::crossterm::EventHandler, Input};
 
 use crate::{
-    fn_next_prev_mut, fn_str_map, key_code, key_code_char,
+    config::NumberConfig,
+    fn_next_prev_mut, fn_str_map,
+    keymap::{Action, Help, Keymap},
     msg::{Msg, NumberBaseMsg, PageMsg},
     pages::{page::Page, util},
+    palette::Command,
+    session::{NumberSession, Session},
+    theme::Theme,
     widget::select::Select,
 };
+use serde::{Deserialize, Serialize};
 
 pub struct NumberBasePage {
     focused: bool,
@@ -37,10 +43,13 @@ struct CurrentStatus {
 }
 
 impl NumberBasePage {
-    pub fn new(focused: bool) -> NumberBasePage {
+    pub fn new(focused: bool, config: &NumberConfig) -> NumberBasePage {
         NumberBasePage {
             focused,
-            cur: CurrentStatus::default(),
+            cur: CurrentStatus {
+                case_sel: config.case,
+                ..Default::default()
+            },
         }
     }
 }
@@ -66,7 +75,8 @@ impl PageItems {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
 #[zero_indexed_enum]
 enum CaseItemSelect {
     #[default]
@@ -83,26 +93,31 @@ impl CaseItemSelect {
     fn_next_prev_mut! {}
 }
 
+impl From<CaseItemSelect> for Case {
+    fn from(case_sel: CaseItemSelect) -> Self {
+        match case_sel {
+            CaseItemSelect::Lowercase => Case::Lowercase,
+            CaseItemSelect::Uppercase => Case::Uppercase,
+        }
+    }
+}
+
 impl Page for NumberBasePage {
-    fn handle_key(&self, key: ratatui::crossterm::event::KeyEvent) -> Option<Msg> {
+    fn handle_key(&self, key: KeyEvent, keymap: &Keymap) -> Option<Msg> {
         let msg = if self.cur.edit {
-            match key {
-                key_code!(KeyCode::Esc) => NumberBaseMsg::EditEnd,
+            match keymap.resolve_input(key) {
+                Some(Action::Cancel) => NumberBaseMsg::EditEnd,
                 _ => NumberBaseMsg::EditKeyEvent(key),
             }
         } else {
-            match key {
-                key_code_char!('j') | key_code!(KeyCode::Down) => NumberBaseMsg::SelectNextItem,
-                key_code_char!('k') | key_code!(KeyCode::Up) => NumberBaseMsg::SelectPrevItem,
-                key_code_char!('l') | key_code!(KeyCode::Right) => {
-                    NumberBaseMsg::CurrentItemSelectNext
-                }
-                key_code_char!('h') | key_code!(KeyCode::Left) => {
-                    NumberBaseMsg::CurrentItemSelectPrev
-                }
-                key_code_char!('y') => NumberBaseMsg::Copy,
-                key_code_char!('p') => NumberBaseMsg::Paste,
-                key_code_char!('e') => NumberBaseMsg::EditStart,
+            match keymap.resolve(key)? {
+                Action::SelectNextItem => NumberBaseMsg::SelectNextItem,
+                Action::SelectPrevItem => NumberBaseMsg::SelectPrevItem,
+                Action::SelectNextValue => NumberBaseMsg::CurrentItemSelectNext,
+                Action::SelectPrevValue => NumberBaseMsg::CurrentItemSelectPrev,
+                Action::Copy => NumberBaseMsg::Copy,
+                Action::Paste => NumberBaseMsg::Paste,
+                Action::Edit => NumberBaseMsg::EditStart,
                 _ => return None,
             }
         };
@@ -128,7 +143,14 @@ impl Page for NumberBasePage {
                     return self.copy_to_clipboard();
                 }
                 NumberBaseMsg::Paste => {
-                    self.paste_from_clipboard();
+                    return self.paste_from_clipboard();
+                }
+                NumberBaseMsg::SelectItem(item) => {
+                    self.cur.item = item;
+                }
+                NumberBaseMsg::SetCase(case_sel) => {
+                    self.cur.case_sel = case_sel;
+                    self.update_hex_case();
                 }
                 NumberBaseMsg::EditStart => {
                     self.edit_start();
@@ -144,31 +166,49 @@ impl Page for NumberBasePage {
         None
     }
 
-    fn render(&mut self, f: &mut Frame, area: Rect) {
+    fn render(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
         let chunks = vertical![==3, ==1, ==3, ==1, ==3, ==1, ==3, ==2, ==1].split(area);
 
-        self.render_input(f, chunks[0], &self.cur.binary_input, PageItems::Binary);
+        self.render_input(
+            f,
+            chunks[0],
+            theme,
+            &self.cur.binary_input,
+            PageItems::Binary,
+        );
 
         if !self.cur.binary_status.is_empty() {
-            self.render_status(f, chunks[1], self.cur.binary_status.as_str());
+            self.render_status(f, chunks[1], theme, self.cur.binary_status.as_str());
         }
 
-        self.render_input(f, chunks[2], &self.cur.octal_input, PageItems::Octal);
+        self.render_input(f, chunks[2], theme, &self.cur.octal_input, PageItems::Octal);
 
         if !self.cur.octal_status.is_empty() {
-            self.render_status(f, chunks[3], self.cur.octal_status.as_str());
+            self.render_status(f, chunks[3], theme, self.cur.octal_status.as_str());
         }
 
-        self.render_input(f, chunks[4], &self.cur.decimal_input, PageItems::Decimal);
+        self.render_input(
+            f,
+            chunks[4],
+            theme,
+            &self.cur.decimal_input,
+            PageItems::Decimal,
+        );
 
         if !self.cur.decimal_status.is_empty() {
-            self.render_status(f, chunks[5], self.cur.decimal_status.as_str());
+            self.render_status(f, chunks[5], theme, self.cur.decimal_status.as_str());
         }
 
-        self.render_input(f, chunks[6], &self.cur.hex_input, PageItems::Hexadecimal);
+        self.render_input(
+            f,
+            chunks[6],
+            theme,
+            &self.cur.hex_input,
+            PageItems::Hexadecimal,
+        );
 
         if !self.cur.hex_status.is_empty() {
-            self.render_status(f, chunks[7], self.cur.hex_status.as_str());
+            self.render_status(f, chunks[7], theme, self.cur.hex_status.as_str());
         }
 
         let case_sel = Select::new(
@@ -176,6 +216,7 @@ impl Page for NumberBasePage {
             self.cur.case_sel.val(),
             self.cur.item == PageItems::Case,
             self.focused,
+            theme,
         );
         f.render_widget(case_sel, chunks[8]);
     }
@@ -188,18 +229,65 @@ impl Page for NumberBasePage {
         self.focused = false;
     }
 
-    fn helps(&self) -> Vec<&str> {
-        let mut helps: Vec<&str> = Vec::new();
+    fn helps(&self) -> Vec<Help> {
+        let mut helps: Vec<Help> = Vec::new();
         if self.cur.edit {
-            helps.push("<Esc> End edit");
+            helps.push(Help::new(&[Action::Cancel], "End edit"));
         } else {
-            helps.push("<e> Edit");
-            helps.push("<j/k> Select item");
-            helps.push("<y> Copy to clipboard");
-            helps.push("<p> Paste from clipboard");
+            helps.push(Help::new(&[Action::Edit], "Edit"));
+            helps.push(Help::new(
+                &[Action::SelectNextItem, Action::SelectPrevItem],
+                "Select item",
+            ));
+            helps.push(Help::new(&[Action::Copy], "Copy to clipboard"));
+            helps.push(Help::new(&[Action::Paste], "Paste from clipboard"));
         }
         helps
     }
+
+    fn commands(&self) -> Vec<Command> {
+        let msg = |msg| Msg::Page(PageMsg::NumberBase(msg));
+        let mut commands = Vec::new();
+        for item in PageItems::vars_vec() {
+            if item == PageItems::Case {
+                continue;
+            }
+            let name = item.str().to_lowercase();
+            let select = msg(NumberBaseMsg::SelectItem(item));
+            let edit = vec![select.clone(), msg(NumberBaseMsg::EditStart)];
+            let copy = vec![select.clone(), msg(NumberBaseMsg::Copy)];
+            let paste = vec![select, msg(NumberBaseMsg::Paste)];
+            commands.push(Command::new(format!("Number base: edit {name}"), edit));
+            commands.push(Command::new(format!("Number base: copy {name}"), copy));
+            commands.push(Command::new(format!("Number base: paste {name}"), paste));
+        }
+        for case_sel in CaseItemSelect::vars_vec() {
+            let label = format!("Number base: {}", case_sel.str().to_lowercase());
+            commands.push(Command::new(
+                label,
+                vec![msg(NumberBaseMsg::SetCase(case_sel))],
+            ));
+        }
+        commands
+    }
+
+    fn save(&self, session: &mut Session) {
+        session.number = Some(NumberSession {
+            case: self.cur.case_sel,
+            input: self.cur.decimal_input.value().into(),
+        });
+    }
+
+    fn restore(&mut self, session: &Session) {
+        let Some(saved) = &session.number else {
+            return;
+        };
+        self.cur.case_sel = saved.case;
+        if !saved.input.is_empty() {
+            self.update_decimal_input(saved.input.clone());
+            self.update_numbers(PageItems::Decimal);
+        }
+    }
 }
 
 impl NumberBasePage {
@@ -274,8 +362,15 @@ impl NumberBasePage {
         util::copy_to_clipboard(text)
     }
 
-    fn paste_from_clipboard(&mut self) {
-        let text = util::paste_from_clipboard().unwrap();
+    fn paste_from_clipboard(&mut self) -> Option<Msg> {
+        if matches!(self.cur.item, PageItems::Case) {
+            return None;
+        }
+
+        let text = match util::paste_from_clipboard() {
+            Ok(text) => text,
+            Err(msg) => return Some(msg),
+        };
         match self.cur.item {
             PageItems::Binary => {
                 self.update_binary_input(text);
@@ -289,32 +384,31 @@ impl NumberBasePage {
             PageItems::Hexadecimal => {
                 self.update_hex_input(text);
             }
-            PageItems::Case => {
-                return;
-            }
+            PageItems::Case => {}
         }
 
         self.update_numbers(self.cur.item);
+        None
     }
 
     fn update_numbers(&mut self, updated_item: PageItems) {
-        #[allow(clippy::from_str_radix_10)]
-        let updated_value = match updated_item {
-            PageItems::Binary => u128::from_str_radix(self.cur.binary_input.value(), 2),
-            PageItems::Octal => u128::from_str_radix(self.cur.octal_input.value(), 8),
-            PageItems::Decimal => u128::from_str_radix(self.cur.decimal_input.value(), 10),
-            PageItems::Hexadecimal => u128::from_str_radix(self.cur.hex_input.value(), 16),
+        let (input, radix) = match updated_item {
+            PageItems::Binary => (self.cur.binary_input.value(), Radix::Binary),
+            PageItems::Octal => (self.cur.octal_input.value(), Radix::Octal),
+            PageItems::Decimal => (self.cur.decimal_input.value(), Radix::Decimal),
+            PageItems::Hexadecimal => (self.cur.hex_input.value(), Radix::Hexadecimal),
             PageItems::Case => {
                 return;
             }
         };
-        match updated_value {
-            Ok(value) => {
-                self.update_binary_input(format!("{value:b}"));
-                self.update_octal_input(format!("{value:o}"));
-                self.update_decimal_input(format!("{value}"));
-                self.update_hex_input(format!("{value:x}"));
-                self.update_hex_case();
+        let req = NumberRequest { input, radix };
+        match number::parse(&req) {
+            Ok(res) => {
+                let case = self.cur.case_sel.into();
+                self.update_binary_input(res.format(Radix::Binary, case));
+                self.update_octal_input(res.format(Radix::Octal, case));
+                self.update_decimal_input(res.format(Radix::Decimal, case));
+                self.update_hex_input(res.format(Radix::Hexadecimal, case));
                 self.cur.binary_status = String::new();
                 self.cur.octal_status = String::new();
                 self.cur.decimal_status = String::new();
@@ -366,39 +460,31 @@ impl NumberBasePage {
         }
     }
 
-    fn render_input(&self, f: &mut Frame, area: Rect, input: &Input, item: PageItems) {
-        let input_style = if self.focused {
-            if self.cur.item == item {
-                Style::default().fg(Color::Blue)
-            } else {
-                Style::default().fg(Color::Reset)
-            }
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
-
-        let input_max_width = area.width - 4;
-        let input_value = input.value();
-        let input_start = input_value.len().saturating_sub(input_max_width as usize);
-        let input_content = &input_value[input_start..];
-        let input_widget = Paragraph::new(input_content).block(
-            Block::bordered()
-                .style(input_style)
-                .title(item.str())
-                .padding(Padding::horizontal(1)),
+    fn render_input(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        theme: &Theme,
+        input: &Input,
+        item: PageItems,
+    ) {
+        let input_style = theme.item_style(self.focused, self.cur.item == item);
+
+        let block = Block::bordered()
+            .style(input_style)
+            .title(item.str())
+            .padding(Padding::horizontal(1));
+        util::render_input(
+            f,
+            area,
+            input,
+            block,
+            self.cur.edit && self.cur.item == item,
         );
-        f.render_widget(input_widget, area);
-
-        if self.cur.edit && self.cur.item == item {
-            let visual_cursor = input.visual_cursor() as u16;
-            let x = area.x + 2 + visual_cursor.min(input_max_width);
-            let y = area.y + 1;
-            f.set_cursor_position((x, y));
-        }
     }
 
-    fn render_status(&self, f: &mut Frame, area: Rect, status: &str) {
-        let status_style = Style::default().fg(Color::Red);
+    fn render_status(&self, f: &mut Frame, area: Rect, theme: &Theme, status: &str) {
+        let status_style = theme.error;
         let status = Paragraph::new(status).block(
             Block::default()
                 .borders(Borders::empty())
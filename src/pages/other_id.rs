@@ -0,0 +1,767 @@
+use btox::other_id::{
+    self, IdKind, NanoIdOptions, OtherIdRequest, SnowflakeOptions, NANOID_LENGTH_MAX,
+    SNOWFLAKE_NODE_MAX,
+};
+use chrono::{Local, SecondsFormat};
+use itsuki::zero_indexed_enum;
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::{Constraint, Layout, Rect},
+    text::Line,
+    widgets::{Block, Padding},
+    Frame,
+};
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use crate::{
+    config::OtherIdConfig,
+    fn_next_prev_mut, fn_str_map,
+    keymap::{Action, Help, Keymap},
+    msg::{Msg, OtherIdMsg, PageMsg},
+    pages::{page::Page, util},
+    palette::Command,
+    session::{OtherIdSession, Session},
+    theme::Theme,
+    widget::{
+        scroll::{ScrollOutput, ScrollOutputState},
+        select::Select,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+pub(crate) const COUNT_MAX: usize = 100;
+
+pub struct OtherIdPage {
+    focused: bool,
+    cur: CurrentStatus,
+
+    ids: Vec<String>,
+}
+
+#[derive(Default)]
+struct CurrentStatus {
+    item: PageItems,
+    kind_sel: KindItemSelect,
+    count: usize,
+    nanoid_length: usize,
+    nanoid_alphabet: Input,
+    snowflake_epoch: Input,
+    snowflake_datacenter: u8,
+    snowflake_worker: u8,
+    view_sel: ViewItemSelect,
+    output_state: ScrollOutputState,
+    edit: bool,
+}
+
+impl OtherIdPage {
+    pub fn new(focused: bool, config: &OtherIdConfig) -> OtherIdPage {
+        let snowflake_epoch = config
+            .snowflake_epoch
+            .map(|epoch| epoch.to_string())
+            .unwrap_or_default();
+        OtherIdPage {
+            focused,
+            cur: CurrentStatus {
+                kind_sel: config.kind,
+                count: config.count,
+                nanoid_length: config.nanoid_length,
+                nanoid_alphabet: Input::new(config.nanoid_alphabet.clone()),
+                snowflake_epoch: Input::new(snowflake_epoch),
+                snowflake_datacenter: config.snowflake_datacenter,
+                snowflake_worker: config.snowflake_worker,
+                ..Default::default()
+            },
+            ids: Vec::new(),
+        }
+    }
+}
+
+#[derive(Default)]
+#[zero_indexed_enum]
+enum PageItems {
+    #[default]
+    Kind,
+    Count,
+    NanoIdLength,
+    NanoIdAlphabet,
+    SnowflakeEpoch,
+    SnowflakeDatacenter,
+    SnowflakeWorker,
+    View,
+    Output,
+}
+
+impl PageItems {
+    fn is_input(&self) -> bool {
+        matches!(self, PageItems::NanoIdAlphabet | PageItems::SnowflakeEpoch)
+    }
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[zero_indexed_enum]
+enum KindItemSelect {
+    #[default]
+    NanoId,
+    Ksuid,
+    Cuid2,
+    ObjectId,
+    Snowflake,
+}
+
+impl KindItemSelect {
+    fn_str_map! {
+        KindItemSelect::NanoId => "NanoID",
+        KindItemSelect::Ksuid => "KSUID",
+        KindItemSelect::Cuid2 => "CUID2",
+        KindItemSelect::ObjectId => "ObjectId",
+        KindItemSelect::Snowflake => "Snowflake",
+    }
+
+    fn_next_prev_mut! {}
+}
+
+impl From<KindItemSelect> for IdKind {
+    fn from(kind_sel: KindItemSelect) -> Self {
+        match kind_sel {
+            KindItemSelect::NanoId => IdKind::NanoId,
+            KindItemSelect::Ksuid => IdKind::Ksuid,
+            KindItemSelect::Cuid2 => IdKind::Cuid2,
+            KindItemSelect::ObjectId => IdKind::ObjectId,
+            KindItemSelect::Snowflake => IdKind::Snowflake,
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[zero_indexed_enum]
+enum ViewItemSelect {
+    #[default]
+    List,
+    Decode,
+}
+
+impl ViewItemSelect {
+    fn_str_map! {
+        ViewItemSelect::List => "List",
+        ViewItemSelect::Decode => "Decode",
+    }
+
+    fn_next_prev_mut! {}
+}
+
+impl Page for OtherIdPage {
+    fn handle_key(&self, key: KeyEvent, keymap: &Keymap) -> Option<Msg> {
+        if self.cur.edit {
+            let msg = match keymap.resolve_input(key) {
+                Some(Action::Cancel) => OtherIdMsg::EditEnd,
+                _ => OtherIdMsg::EditKeyEvent(key),
+            };
+            return Some(Msg::Page(PageMsg::OtherId(msg)));
+        }
+
+        let msg = match keymap.resolve(key)? {
+            Action::SelectNextItem => OtherIdMsg::SelectNextItem,
+            Action::SelectPrevItem => OtherIdMsg::SelectPrevItem,
+            Action::SelectNextValue => OtherIdMsg::CurrentItemSelectNext,
+            Action::SelectPrevValue => OtherIdMsg::CurrentItemSelectPrev,
+            Action::ScrollDown => OtherIdMsg::ScrollDown,
+            Action::ScrollUp => OtherIdMsg::ScrollUp,
+            Action::Copy => OtherIdMsg::Copy,
+            Action::Paste => OtherIdMsg::Paste,
+            Action::Generate => OtherIdMsg::Generate,
+            Action::Edit => OtherIdMsg::EditStart,
+            _ => return None,
+        };
+        Some(Msg::Page(PageMsg::OtherId(msg)))
+    }
+
+    fn update(&mut self, msg: PageMsg) -> Option<Msg> {
+        if let PageMsg::OtherId(msg) = msg {
+            match msg {
+                OtherIdMsg::SelectNextItem => {
+                    self.select_next_item();
+                }
+                OtherIdMsg::SelectPrevItem => {
+                    self.select_prev_item();
+                }
+                OtherIdMsg::CurrentItemSelectNext => {
+                    self.current_item_select_next();
+                }
+                OtherIdMsg::CurrentItemSelectPrev => {
+                    self.current_item_select_prev();
+                }
+                OtherIdMsg::ScrollDown => {
+                    self.scroll_down();
+                }
+                OtherIdMsg::ScrollUp => {
+                    self.scroll_up();
+                }
+                OtherIdMsg::Generate => {
+                    return self.generate_ids();
+                }
+                OtherIdMsg::Copy => {
+                    return self.copy_to_clipboard();
+                }
+                OtherIdMsg::Paste => {
+                    return self.paste_from_clipboard();
+                }
+                OtherIdMsg::SelectItem(item) => {
+                    if self.is_visible(item) {
+                        self.cur.item = item;
+                    }
+                }
+                OtherIdMsg::SetKind(kind_sel) => {
+                    self.set_kind(kind_sel);
+                }
+                OtherIdMsg::SetCount(count) => {
+                    self.cur.count = count.clamp(1, COUNT_MAX);
+                }
+                OtherIdMsg::SetView(view_sel) => {
+                    self.cur.view_sel = view_sel;
+                    self.cur.output_state = ScrollOutputState::default();
+                }
+                OtherIdMsg::EditStart => {
+                    self.edit_start();
+                }
+                OtherIdMsg::EditEnd => {
+                    self.edit_end();
+                }
+                OtherIdMsg::EditKeyEvent(key) => {
+                    self.edit(key);
+                }
+            }
+        }
+        None
+    }
+
+    fn render(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let items: Vec<PageItems> = PageItems::vars_vec()
+            .into_iter()
+            .filter(|item| self.is_visible(*item))
+            .collect();
+        let constraints = items.iter().map(|item| match item {
+            PageItems::Output => Constraint::Min(0),
+            item if item.is_input() => Constraint::Length(3),
+            _ => Constraint::Length(2),
+        });
+        let chunks = Layout::vertical(constraints).split(area);
+
+        for (item, chunk) in items.into_iter().zip(chunks.iter()) {
+            match item {
+                PageItems::Kind => {
+                    self.render_select(f, *chunk, theme, item, KindItemSelect::strings_vec())
+                }
+                PageItems::Count => {
+                    let counts = (1..=COUNT_MAX).map(|i| format!("{i}")).collect();
+                    self.render_select(f, *chunk, theme, item, counts)
+                }
+                PageItems::NanoIdLength => {
+                    let lengths = (1..=NANOID_LENGTH_MAX)
+                        .map(|i| format!("Length {i}"))
+                        .collect();
+                    self.render_select(f, *chunk, theme, item, lengths)
+                }
+                PageItems::NanoIdAlphabet => {
+                    self.render_input(f, *chunk, theme, item, "Alphabet (default if empty)")
+                }
+                PageItems::SnowflakeEpoch => {
+                    self.render_input(f, *chunk, theme, item, "Epoch (Twitter epoch if empty)")
+                }
+                PageItems::SnowflakeDatacenter => {
+                    let ids = (0..=SNOWFLAKE_NODE_MAX)
+                        .map(|i| format!("Datacenter {i}"))
+                        .collect();
+                    self.render_select(f, *chunk, theme, item, ids)
+                }
+                PageItems::SnowflakeWorker => {
+                    let ids = (0..=SNOWFLAKE_NODE_MAX)
+                        .map(|i| format!("Worker {i}"))
+                        .collect();
+                    self.render_select(f, *chunk, theme, item, ids)
+                }
+                PageItems::View => {
+                    self.render_select(f, *chunk, theme, item, ViewItemSelect::strings_vec())
+                }
+                PageItems::Output => self.render_output(f, *chunk, theme),
+            }
+        }
+    }
+
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+
+    fn helps(&self) -> Vec<Help> {
+        let mut helps: Vec<Help> = Vec::new();
+        if self.cur.edit {
+            helps.push(Help::new(&[Action::Cancel], "End edit"));
+            return helps;
+        }
+        helps.push(Help::new(
+            &[Action::SelectNextItem, Action::SelectPrevItem],
+            "Select item",
+        ));
+        if self.cur.item.is_input() {
+            helps.push(Help::new(&[Action::Edit], "Edit"));
+            helps.push(Help::new(&[Action::Paste], "Paste from clipboard"));
+        } else if !matches!(self.cur.item, PageItems::Output) {
+            helps.push(Help::new(
+                &[Action::SelectPrevValue, Action::SelectNextValue],
+                "Select current item value",
+            ));
+        }
+        helps.push(Help::new(&[Action::Generate], "Generate id"));
+        if matches!(self.cur.item, PageItems::Output) {
+            helps.push(Help::new(
+                &[Action::ScrollDown, Action::ScrollUp],
+                "Scroll down/up",
+            ));
+            helps.push(Help::new(&[Action::Copy], "Copy to clipboard"));
+            helps.push(Help::new(&[Action::Paste], "Paste from clipboard"));
+        }
+        helps
+    }
+
+    fn commands(&self) -> Vec<Command> {
+        let msg = |msg| Msg::Page(PageMsg::OtherId(msg));
+        let mut commands = vec![Command::new(
+            "Other IDs: generate",
+            vec![msg(OtherIdMsg::Generate)],
+        )];
+        for kind_sel in KindItemSelect::vars_vec() {
+            let label = format!("Other IDs: generate {}", kind_sel.str());
+            let msgs = vec![
+                msg(OtherIdMsg::SetKind(kind_sel)),
+                msg(OtherIdMsg::Generate),
+            ];
+            commands.push(Command::new(label, msgs));
+        }
+        for count in [1, 10] {
+            let label = format!("Other IDs: generate {count}");
+            let msgs = vec![msg(OtherIdMsg::SetCount(count)), msg(OtherIdMsg::Generate)];
+            commands.push(Command::new(label, msgs));
+        }
+        for view_sel in ViewItemSelect::vars_vec() {
+            let label = format!("Other IDs: view {}", view_sel.str().to_lowercase());
+            commands.push(Command::new(
+                label,
+                vec![msg(OtherIdMsg::SetView(view_sel))],
+            ));
+        }
+        let output = msg(OtherIdMsg::SelectItem(PageItems::Output));
+        commands.push(Command::new(
+            "Other IDs: copy output",
+            vec![output.clone(), msg(OtherIdMsg::Copy)],
+        ));
+        commands.push(Command::new(
+            "Other IDs: paste from clipboard",
+            vec![output.clone(), msg(OtherIdMsg::Paste)],
+        ));
+        commands.push(Command::new(
+            "Other IDs: decode IDs from clipboard",
+            vec![
+                output,
+                msg(OtherIdMsg::Paste),
+                msg(OtherIdMsg::SetView(ViewItemSelect::Decode)),
+            ],
+        ));
+        commands
+    }
+
+    fn save(&self, session: &mut Session) {
+        session.other_id = Some(OtherIdSession {
+            kind: self.cur.kind_sel,
+            count: self.cur.count,
+            nanoid_length: self.cur.nanoid_length,
+            nanoid_alphabet: self.cur.nanoid_alphabet.value().into(),
+            snowflake_epoch: self.cur.snowflake_epoch.value().into(),
+            snowflake_datacenter: self.cur.snowflake_datacenter,
+            snowflake_worker: self.cur.snowflake_worker,
+            view: self.cur.view_sel,
+        });
+    }
+
+    fn restore(&mut self, session: &Session) {
+        let Some(saved) = &session.other_id else {
+            return;
+        };
+        self.cur.kind_sel = saved.kind;
+        self.cur.count = saved.count.clamp(1, COUNT_MAX);
+        self.cur.nanoid_length = saved.nanoid_length.clamp(1, NANOID_LENGTH_MAX);
+        self.cur.nanoid_alphabet = Input::new(saved.nanoid_alphabet.clone());
+        self.cur.snowflake_epoch = Input::new(saved.snowflake_epoch.clone());
+        self.cur.snowflake_datacenter = saved.snowflake_datacenter.min(SNOWFLAKE_NODE_MAX);
+        self.cur.snowflake_worker = saved.snowflake_worker.min(SNOWFLAKE_NODE_MAX);
+        self.cur.view_sel = saved.view;
+    }
+}
+
+impl OtherIdPage {
+    fn select_next_item(&mut self) {
+        self.cur.item = self.cur.item.next();
+        while !self.is_visible(self.cur.item) {
+            self.cur.item = self.cur.item.next();
+        }
+    }
+
+    fn select_prev_item(&mut self) {
+        self.cur.item = self.cur.item.prev();
+        while !self.is_visible(self.cur.item) {
+            self.cur.item = self.cur.item.prev();
+        }
+    }
+
+    /// Returns whether the item is used by the selected kind.
+    fn is_visible(&self, item: PageItems) -> bool {
+        let kind_sel = self.cur.kind_sel;
+        match item {
+            PageItems::NanoIdLength | PageItems::NanoIdAlphabet => {
+                kind_sel == KindItemSelect::NanoId
+            }
+            PageItems::SnowflakeEpoch
+            | PageItems::SnowflakeDatacenter
+            | PageItems::SnowflakeWorker => kind_sel == KindItemSelect::Snowflake,
+            PageItems::Kind | PageItems::Count | PageItems::View | PageItems::Output => true,
+        }
+    }
+
+    /// IDs of another kind are cleared since they could not be decoded as the new kind.
+    fn set_kind(&mut self, kind_sel: KindItemSelect) {
+        if self.cur.kind_sel != kind_sel {
+            self.cur.kind_sel = kind_sel;
+            self.ids.clear();
+            self.cur.output_state = ScrollOutputState::default();
+        }
+        if !self.is_visible(self.cur.item) {
+            self.cur.item = PageItems::Kind;
+        }
+    }
+
+    fn current_item_select_next(&mut self) {
+        match self.cur.item {
+            PageItems::Kind => {
+                let mut kind_sel = self.cur.kind_sel;
+                kind_sel.next_mut();
+                self.set_kind(kind_sel);
+            }
+            PageItems::Count => {
+                if self.cur.count < COUNT_MAX {
+                    self.cur.count += 1;
+                }
+            }
+            PageItems::NanoIdLength => {
+                if self.cur.nanoid_length < NANOID_LENGTH_MAX {
+                    self.cur.nanoid_length += 1;
+                }
+            }
+            PageItems::SnowflakeDatacenter => {
+                if self.cur.snowflake_datacenter < SNOWFLAKE_NODE_MAX {
+                    self.cur.snowflake_datacenter += 1;
+                }
+            }
+            PageItems::SnowflakeWorker => {
+                if self.cur.snowflake_worker < SNOWFLAKE_NODE_MAX {
+                    self.cur.snowflake_worker += 1;
+                }
+            }
+            PageItems::View => {
+                self.cur.view_sel.next_mut();
+                self.cur.output_state = ScrollOutputState::default();
+            }
+            PageItems::NanoIdAlphabet | PageItems::SnowflakeEpoch | PageItems::Output => {}
+        }
+    }
+
+    fn current_item_select_prev(&mut self) {
+        match self.cur.item {
+            PageItems::Kind => {
+                let mut kind_sel = self.cur.kind_sel;
+                kind_sel.prev_mut();
+                self.set_kind(kind_sel);
+            }
+            PageItems::Count => {
+                if self.cur.count > 1 {
+                    self.cur.count -= 1;
+                }
+            }
+            PageItems::NanoIdLength => {
+                if self.cur.nanoid_length > 1 {
+                    self.cur.nanoid_length -= 1;
+                }
+            }
+            PageItems::SnowflakeDatacenter => {
+                self.cur.snowflake_datacenter = self.cur.snowflake_datacenter.saturating_sub(1);
+            }
+            PageItems::SnowflakeWorker => {
+                self.cur.snowflake_worker = self.cur.snowflake_worker.saturating_sub(1);
+            }
+            PageItems::View => {
+                self.cur.view_sel.prev_mut();
+                self.cur.output_state = ScrollOutputState::default();
+            }
+            PageItems::NanoIdAlphabet | PageItems::SnowflakeEpoch | PageItems::Output => {}
+        }
+    }
+
+    fn scroll_down(&mut self) {
+        if !matches!(self.cur.item, PageItems::Output) || self.ids.is_empty() {
+            return;
+        }
+        self.cur.output_state.scroll_down();
+    }
+
+    fn scroll_up(&mut self) {
+        if !matches!(self.cur.item, PageItems::Output) || self.ids.is_empty() {
+            return;
+        }
+        self.cur.output_state.scroll_up();
+    }
+
+    fn edit_start(&mut self) {
+        if self.cur.item.is_input() {
+            self.cur.edit = true;
+        }
+    }
+
+    fn edit_end(&mut self) {
+        self.cur.edit = false;
+    }
+
+    fn edit(&mut self, key: KeyEvent) {
+        let event = &ratatui::crossterm::event::Event::Key(key);
+        if let Some(input) = self.current_input_mut() {
+            input.handle_event(event);
+        }
+    }
+
+    fn current_input_mut(&mut self) -> Option<&mut Input> {
+        match self.cur.item {
+            PageItems::NanoIdAlphabet => Some(&mut self.cur.nanoid_alphabet),
+            PageItems::SnowflakeEpoch => Some(&mut self.cur.snowflake_epoch),
+            _ => None,
+        }
+    }
+
+    fn generate_ids(&mut self) -> Option<Msg> {
+        let kind_sel = self.cur.kind_sel.str();
+        let req = match self.build_request() {
+            Ok(req) => req,
+            Err(e) => {
+                return Some(Msg::NotifyWarn(format!(
+                    "Could not generate {kind_sel}: {e}"
+                )))
+            }
+        };
+        match other_id::generate(&req) {
+            Ok(ids) => {
+                self.ids = ids;
+                None
+            }
+            Err(e) => Some(Msg::NotifyWarn(format!(
+                "Could not generate {kind_sel}: {e}"
+            ))),
+        }
+    }
+
+    fn build_request(&self) -> Result<OtherIdRequest, other_id::OtherIdError> {
+        Ok(OtherIdRequest {
+            kind: self.cur.kind_sel.into(),
+            count: self.cur.count,
+            nanoid: self.nanoid_options(),
+            snowflake: self.snowflake_options()?,
+        })
+    }
+
+    fn nanoid_options(&self) -> NanoIdOptions {
+        let alphabet = self.cur.nanoid_alphabet.value();
+        NanoIdOptions {
+            alphabet: if alphabet.is_empty() {
+                other_id::NANOID_DEFAULT_ALPHABET.into()
+            } else {
+                alphabet.into()
+            },
+            length: self.cur.nanoid_length,
+        }
+    }
+
+    fn snowflake_options(&self) -> Result<SnowflakeOptions, other_id::OtherIdError> {
+        let epoch_ms = if self.cur.kind_sel == KindItemSelect::Snowflake {
+            other_id::parse_snowflake_epoch(self.cur.snowflake_epoch.value())?
+        } else {
+            other_id::SNOWFLAKE_TWITTER_EPOCH_MS
+        };
+        Ok(SnowflakeOptions {
+            epoch_ms,
+            datacenter: self.cur.snowflake_datacenter,
+            worker: self.cur.snowflake_worker,
+        })
+    }
+
+    fn copy_to_clipboard(&self) -> Option<Msg> {
+        if !matches!(self.cur.item, PageItems::Output) {
+            return None;
+        }
+
+        let text = self.ids.join("\n");
+        util::copy_to_clipboard(&text)
+    }
+
+    fn paste_from_clipboard(&mut self) -> Option<Msg> {
+        if !matches!(self.cur.item, PageItems::Output) && !self.cur.item.is_input() {
+            return None;
+        }
+
+        let text = match util::paste_from_clipboard() {
+            Ok(text) => text,
+            Err(msg) => return Some(msg),
+        };
+        if let Some(input) = self.current_input_mut() {
+            *input = input.clone().with_value(text.trim_end().into());
+            return None;
+        }
+        let req = match self.build_request() {
+            Ok(req) => req,
+            Err(e) => {
+                let kind_sel = self.cur.kind_sel.str();
+                return Some(Msg::NotifyWarn(format!("Could not decode {kind_sel}: {e}")));
+            }
+        };
+        let (ids, failures): (Vec<&str>, Vec<&str>) = text
+            .lines()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .partition(|s| other_id::inspect(req.kind, s, &req.nanoid, &req.snowflake).is_ok());
+        self.ids = ids.into_iter().map(String::from).collect();
+        self.cur.output_state = ScrollOutputState::default();
+
+        let failure_count = failures.len();
+        if failure_count > 0 {
+            let kind_sel = self.cur.kind_sel.str();
+            let msg = format!("Could not parse {failure_count} lines of string to {kind_sel}");
+            Some(Msg::NotifyWarn(msg))
+        } else {
+            None
+        }
+    }
+
+    fn render_select(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        theme: &Theme,
+        item: PageItems,
+        items: Vec<String>,
+    ) {
+        let current = match item {
+            PageItems::Kind => self.cur.kind_sel.val(),
+            PageItems::Count => self.cur.count - 1,
+            PageItems::NanoIdLength => self.cur.nanoid_length - 1,
+            PageItems::SnowflakeDatacenter => self.cur.snowflake_datacenter as usize,
+            PageItems::SnowflakeWorker => self.cur.snowflake_worker as usize,
+            PageItems::View => self.cur.view_sel.val(),
+            _ => return,
+        };
+        let select = Select::new(items, current, self.cur.item == item, self.focused, theme);
+        f.render_widget(select, area);
+    }
+
+    fn render_input(&self, f: &mut Frame, area: Rect, theme: &Theme, item: PageItems, title: &str) {
+        let input = match item {
+            PageItems::NanoIdAlphabet => &self.cur.nanoid_alphabet,
+            PageItems::SnowflakeEpoch => &self.cur.snowflake_epoch,
+            _ => return,
+        };
+        let input_style = theme.item_style(self.focused, self.cur.item == item);
+
+        let block = Block::bordered()
+            .style(input_style)
+            .title(title)
+            .padding(Padding::horizontal(1));
+        util::render_input(
+            f,
+            area,
+            input,
+            block,
+            self.cur.edit && self.cur.item == item,
+        );
+    }
+
+    fn render_output(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let lines: Vec<Line> = match self.cur.view_sel {
+            ViewItemSelect::List => self.ids.iter().map(|id| Line::raw(id.clone())).collect(),
+            ViewItemSelect::Decode => self
+                .ids
+                .iter()
+                .flat_map(|id| self.decode_lines(id, theme))
+                .collect(),
+        };
+        let output = ScrollOutput::new(
+            lines,
+            self.focused,
+            self.cur.item == PageItems::Output,
+            theme,
+        );
+        f.render_stateful_widget(output, area, &mut self.cur.output_state);
+    }
+
+    fn decode_lines(&self, id: &str, theme: &Theme) -> Vec<Line<'static>> {
+        let field = |name: &str, value: String| util::field_line(name, value, theme);
+
+        let mut lines = vec![Line::raw(id.to_string())];
+        let info = self
+            .build_request()
+            .and_then(|req| other_id::inspect(req.kind, id, &req.nanoid, &req.snowflake));
+        match info {
+            Ok(info) => {
+                match info.timestamp {
+                    Some(timestamp) => {
+                        let utc = timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true);
+                        let local = timestamp
+                            .with_timezone(&Local)
+                            .to_rfc3339_opts(SecondsFormat::AutoSi, false);
+                        lines.push(field("Time (UTC)", utc));
+                        lines.push(field("Time (Local)", local));
+                    }
+                    None => lines.push(field("Time", "none embedded".into())),
+                }
+                for (name, value) in info.fields {
+                    lines.push(field(name, value));
+                }
+            }
+            Err(e) => lines.push(Line::styled(format!("  {e}"), theme.warn)),
+        }
+        lines.push(Line::default());
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_render_long_multibyte_alphabet() {
+        let mut page = OtherIdPage::new(true, &OtherIdConfig::default());
+        let alphabet = "αβγδεζηθικλμνξοπρστυφχψωΑΒΓΔΕΖΗΘΙΚΛΜΝΞΟΠΡΣΤΥΦΧΨΩ";
+        page.cur.nanoid_alphabet = Input::new(alphabet.into());
+        page.cur.item = PageItems::NanoIdAlphabet;
+        page.cur.edit = true;
+
+        let mut terminal = Terminal::new(TestBackend::new(41, 40)).unwrap();
+        terminal
+            .draw(|f| page.render(f, f.area(), &Theme::default()))
+            .unwrap();
+
+        // the end of the alphabet is shown, with the cursor after it
+        let cursor = terminal.get_cursor_position().unwrap();
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(cursor.x - 1, cursor.y)].symbol(), "Ω");
+    }
+}
@@ -1,15 +1,29 @@
-use ratatui::{layout::Rect, Frame};
+use ratatui::{crossterm::event::KeyEvent, layout::Rect, Frame};
 
-use crate::msg::{Msg, PageMsg};
+use crate::{
+    keymap::{Help, Keymap},
+    msg::{Msg, PageMsg},
+    palette::Command,
+    session::Session,
+    theme::Theme,
+};
 
 pub trait Page {
-    fn handle_key(&self, key: ratatui::crossterm::event::KeyEvent) -> Option<Msg>;
+    fn handle_key(&self, key: KeyEvent, keymap: &Keymap) -> Option<Msg>;
     fn update(&mut self, msg: PageMsg) -> Option<Msg>;
 
-    fn render(&mut self, f: &mut Frame, area: Rect);
+    fn render(&mut self, f: &mut Frame, area: Rect, theme: &Theme);
 
     fn focus(&mut self);
     fn unfocus(&mut self);
 
-    fn helps(&self) -> Vec<&str>;
+    fn helps(&self) -> Vec<Help>;
+
+    /// Returns the actions shown in the command palette.
+    fn commands(&self) -> Vec<Command>;
+
+    /// Writes the state kept across restarts into the session.
+    fn save(&self, session: &mut Session);
+    /// Restores the state written by `save`, if any.
+    fn restore(&mut self, session: &Session);
 }
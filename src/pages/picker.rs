@@ -0,0 +1,159 @@
+use std::cmp::Reverse;
+
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::{Margin, Rect},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListItem},
+    Frame,
+};
+use ratatui_macros::vertical;
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use crate::{
+    theme::Theme,
+    util::{fuzzy_match, FuzzyMatch},
+};
+
+const MAX_VISIBLE_CANDIDATES: usize = 10;
+
+/// A popup to choose one of many values by typing a part of its label.
+pub struct Picker {
+    title: &'static str,
+    labels: Vec<String>,
+    input: Input,
+    /// Index in the filtered labels.
+    selected: usize,
+}
+
+impl Picker {
+    /// Starts with all labels listed and `current` selected.
+    pub fn new(title: &'static str, labels: Vec<String>, current: usize) -> Picker {
+        Picker {
+            title,
+            labels,
+            input: Input::default(),
+            selected: current,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        let event = &ratatui::crossterm::event::Event::Key(key);
+        if self.input.handle_event(event).is_some_and(|c| c.value) {
+            self.selected = 0;
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.filtered_labels().len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Returns the index of the selected label in all labels, `None` if nothing matches.
+    pub fn selected(&self) -> Option<usize> {
+        self.filtered_labels()
+            .into_iter()
+            .nth(self.selected)
+            .map(|(i, _)| i)
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let filtered = self.filtered_labels();
+
+        let list_height = filtered.len().clamp(1, MAX_VISIBLE_CANDIDATES) as u16;
+        let height = (list_height + 3).min(area.height);
+        let picker_area = Rect::new(area.x, area.y, area.width, height);
+
+        let block = Block::bordered()
+            .title(format!(" {} ", self.title))
+            .style(theme.focused);
+        let inner_area = block.inner(picker_area).inner(Margin::new(1, 0));
+        f.render_widget(Clear, picker_area);
+        f.render_widget(block, picker_area);
+
+        let chunks = vertical![==1, >=0].split(inner_area);
+
+        let input_line = Line::from(vec![Span::raw("> "), Span::raw(self.input.value())]);
+        f.render_widget(input_line.style(theme.text), chunks[0]);
+        let max_cursor = chunks[0].width.saturating_sub(3);
+        let cursor_x = chunks[0].x + 2 + (self.input.visual_cursor() as u16).min(max_cursor);
+        f.set_cursor_position((cursor_x, chunks[0].y));
+
+        let visible = chunks[1].height as usize;
+        let offset = (self.selected + 1).saturating_sub(visible);
+        let items = filtered
+            .into_iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible)
+            .map(|(i, (li, m))| {
+                let spans: Vec<Span> = self.labels[li]
+                    .chars()
+                    .enumerate()
+                    .map(|(ci, c)| {
+                        if m.indices.contains(&ci) {
+                            Span::styled(c.to_string(), theme.highlight)
+                        } else {
+                            Span::raw(c.to_string())
+                        }
+                    })
+                    .collect();
+                let style = if i == self.selected {
+                    theme.selected
+                } else {
+                    theme.text
+                };
+                ListItem::new(Line::from(spans)).style(style)
+            });
+        f.render_widget(List::new(items), chunks[1]);
+    }
+
+    /// Returns the indices of the labels matching the input, best match first.
+    fn filtered_labels(&self) -> Vec<(usize, FuzzyMatch)> {
+        let mut labels: Vec<(usize, FuzzyMatch)> = self
+            .labels
+            .iter()
+            .enumerate()
+            .filter_map(|(i, label)| fuzzy_match(self.input.value(), label).map(|m| (i, m)))
+            .collect();
+        labels.sort_by_key(|(_, m)| Reverse(m.score));
+        labels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::crossterm::event::{KeyCode, KeyModifiers};
+
+    fn type_str(picker: &mut Picker, s: &str) {
+        for c in s.chars() {
+            picker.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn test_selected() {
+        let labels = ["MD5", "SHA-256", "SHA3-256"].map(String::from).to_vec();
+        let mut picker = Picker::new("Algorithm", labels, 1);
+        assert_eq!(picker.selected(), Some(1));
+
+        picker.select_next();
+        picker.select_next();
+        assert_eq!(picker.selected(), Some(2));
+
+        type_str(&mut picker, "sha3");
+        assert_eq!(picker.selected(), Some(2));
+
+        picker.select_prev();
+        assert_eq!(picker.selected(), Some(2));
+
+        type_str(&mut picker, "x");
+        assert_eq!(picker.selected(), None);
+    }
+}
@@ -1,19 +1,36 @@
+use ::ulid::Ulid;
+use btox::{
+    id128,
+    ulid::{self, UlidFormat, UlidRequest},
+};
+use chrono::{Local, SecondsFormat};
 use itsuki::zero_indexed_enum;
-use ratatui::{crossterm::event::KeyCode, layout::Rect, text::Line, Frame};
-use ratatui_macros::vertical;
-use ulid::Ulid;
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Padding},
+    Frame,
+};
+use tui_input::{backend::crossterm::EventHandler, Input};
 
 use crate::{
-    fn_next_prev_mut, fn_str_map, key_code, key_code_char,
+    config::UlidConfig,
+    fn_next_prev_mut, fn_str_map,
+    keymap::{Action, Help, Keymap},
     msg::{Msg, PageMsg, UlidMsg},
     pages::{page::Page, util},
+    palette::Command,
+    session::{Session, UlidSession},
+    theme::Theme,
     widget::{
         scroll::{ScrollOutput, ScrollOutputState},
         select::Select,
     },
 };
+use serde::{Deserialize, Serialize};
 
-const COUNT_MAX: usize = 100;
+pub(crate) const COUNT_MAX: usize = 100;
 
 pub struct UlidPage {
     focused: bool,
@@ -26,15 +43,21 @@ struct CurrentStatus {
     item: PageItems,
     case_sel: CaseItemSelect,
     count: usize,
+    gen_sel: GenerationItemSelect,
+    timestamp: Input,
+    view_sel: ViewItemSelect,
     output_state: ScrollOutputState,
+    edit: bool,
 }
 
 impl UlidPage {
-    pub fn new(focused: bool) -> UlidPage {
+    pub fn new(focused: bool, config: &UlidConfig) -> UlidPage {
         UlidPage {
             focused,
             cur: CurrentStatus {
-                count: 1,
+                case_sel: config.case,
+                count: config.count,
+                gen_sel: config.generation,
                 ..Default::default()
             },
             ids: Vec::new(),
@@ -48,10 +71,14 @@ enum PageItems {
     #[default]
     Case,
     Count,
+    Generation,
+    Timestamp,
+    View,
     Output,
 }
 
-#[derive(Default)]
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
 #[zero_indexed_enum]
 enum CaseItemSelect {
     #[default]
@@ -67,18 +94,63 @@ impl CaseItemSelect {
     fn_next_prev_mut! {}
 }
 
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[zero_indexed_enum]
+enum GenerationItemSelect {
+    #[default]
+    Random,
+    Monotonic,
+}
+
+impl GenerationItemSelect {
+    fn_str_map! {
+        GenerationItemSelect::Random => "Random",
+        GenerationItemSelect::Monotonic => "Monotonic",
+    }
+    fn_next_prev_mut! {}
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[zero_indexed_enum]
+enum ViewItemSelect {
+    #[default]
+    List,
+    Inspect,
+    Convert,
+}
+
+impl ViewItemSelect {
+    fn_str_map! {
+        ViewItemSelect::List => "List",
+        ViewItemSelect::Inspect => "Inspect",
+        ViewItemSelect::Convert => "Convert",
+    }
+    fn_next_prev_mut! {}
+}
+
 impl Page for UlidPage {
-    fn handle_key(&self, key: ratatui::crossterm::event::KeyEvent) -> Option<Msg> {
-        let msg = match key {
-            key_code_char!('j') | key_code!(KeyCode::Down) => UlidMsg::SelectNextItem,
-            key_code_char!('k') | key_code!(KeyCode::Up) => UlidMsg::SelectPrevItem,
-            key_code_char!('l') | key_code!(KeyCode::Right) => UlidMsg::CurrentItemSelectNext,
-            key_code_char!('h') | key_code!(KeyCode::Left) => UlidMsg::CurrentItemSelectPrev,
-            key_code_char!('e', Ctrl) => UlidMsg::ScrollDown,
-            key_code_char!('y', Ctrl) => UlidMsg::ScrollUp,
-            key_code_char!('y') => UlidMsg::Copy,
-            key_code_char!('p') => UlidMsg::Paste,
-            key_code!(KeyCode::Enter) => UlidMsg::Generate,
+    fn handle_key(&self, key: KeyEvent, keymap: &Keymap) -> Option<Msg> {
+        if self.cur.edit {
+            let msg = match keymap.resolve_input(key) {
+                Some(Action::Cancel) => UlidMsg::EditEnd,
+                _ => UlidMsg::EditKeyEvent(key),
+            };
+            return Some(Msg::Page(PageMsg::Ulid(msg)));
+        }
+
+        let msg = match keymap.resolve(key)? {
+            Action::SelectNextItem => UlidMsg::SelectNextItem,
+            Action::SelectPrevItem => UlidMsg::SelectPrevItem,
+            Action::SelectNextValue => UlidMsg::CurrentItemSelectNext,
+            Action::SelectPrevValue => UlidMsg::CurrentItemSelectPrev,
+            Action::ScrollDown => UlidMsg::ScrollDown,
+            Action::ScrollUp => UlidMsg::ScrollUp,
+            Action::Copy => UlidMsg::Copy,
+            Action::Paste => UlidMsg::Paste,
+            Action::Generate => UlidMsg::Generate,
+            Action::Edit => UlidMsg::EditStart,
             _ => return None,
         };
         Some(Msg::Page(PageMsg::Ulid(msg)))
@@ -106,7 +178,7 @@ impl Page for UlidPage {
                     self.scroll_up();
                 }
                 UlidMsg::Generate => {
-                    self.generate_ulid();
+                    return self.generate_ulid();
                 }
                 UlidMsg::Copy => {
                     return self.copy_to_clipboard();
@@ -114,19 +186,56 @@ impl Page for UlidPage {
                 UlidMsg::Paste => {
                     return self.paste_from_clipboard();
                 }
+                UlidMsg::SelectItem(item) => {
+                    self.cur.item = item;
+                }
+                UlidMsg::SetCase(case_sel) => {
+                    self.cur.case_sel = case_sel;
+                }
+                UlidMsg::SetCount(count) => {
+                    self.cur.count = count.clamp(1, COUNT_MAX);
+                }
+                UlidMsg::SetGeneration(gen_sel) => {
+                    self.cur.gen_sel = gen_sel;
+                }
+                UlidMsg::SetView(view_sel) => {
+                    self.cur.view_sel = view_sel;
+                    self.cur.output_state = ScrollOutputState::default();
+                }
+                UlidMsg::EditStart => {
+                    if self.cur.item == PageItems::Timestamp {
+                        self.cur.edit = true;
+                    }
+                }
+                UlidMsg::EditEnd => {
+                    self.cur.edit = false;
+                }
+                UlidMsg::EditKeyEvent(key) => {
+                    let event = &ratatui::crossterm::event::Event::Key(key);
+                    self.cur.timestamp.handle_event(event);
+                }
             }
         }
         None
     }
 
-    fn render(&mut self, f: &mut Frame, area: Rect) {
-        let chunks = vertical![==2, ==2, >=0].split(area);
+    fn render(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let chunks = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(3),
+            Constraint::Length(2),
+            Constraint::Min(0),
+        ])
+        .split(area);
 
         let case_sel = Select::new(
             CaseItemSelect::strings_vec(),
             self.cur.case_sel.val(),
             self.cur.item == PageItems::Case,
             self.focused,
+            theme,
         );
         f.render_widget(case_sel, chunks[0]);
 
@@ -135,10 +244,31 @@ impl Page for UlidPage {
             self.cur.count - 1,
             self.cur.item == PageItems::Count,
             self.focused,
+            theme,
         );
         f.render_widget(count_sel, chunks[1]);
 
-        self.render_output(f, chunks[2]);
+        let gen_sel = Select::new(
+            GenerationItemSelect::strings_vec(),
+            self.cur.gen_sel.val(),
+            self.cur.item == PageItems::Generation,
+            self.focused,
+            theme,
+        );
+        f.render_widget(gen_sel, chunks[2]);
+
+        self.render_timestamp(f, chunks[3], theme);
+
+        let view_sel = Select::new(
+            ViewItemSelect::strings_vec(),
+            self.cur.view_sel.val(),
+            self.cur.item == PageItems::View,
+            self.focused,
+            theme,
+        );
+        f.render_widget(view_sel, chunks[4]);
+
+        self.render_output(f, chunks[5], theme);
     }
 
     fn focus(&mut self) {
@@ -149,20 +279,109 @@ impl Page for UlidPage {
         self.focused = false;
     }
 
-    fn helps(&self) -> Vec<&str> {
-        let mut helps: Vec<&str> = Vec::new();
-        helps.push("<j/k> Select item");
-        if !matches!(self.cur.item, PageItems::Output) {
-            helps.push("<h/l> Select current item value");
+    fn helps(&self) -> Vec<Help> {
+        let mut helps: Vec<Help> = Vec::new();
+        if self.cur.edit {
+            helps.push(Help::new(&[Action::Cancel], "End edit"));
+            return helps;
         }
-        helps.push("<Enter> Generate ulid");
+        helps.push(Help::new(
+            &[Action::SelectNextItem, Action::SelectPrevItem],
+            "Select item",
+        ));
+        if matches!(self.cur.item, PageItems::Timestamp) {
+            helps.push(Help::new(&[Action::Edit], "Edit"));
+            helps.push(Help::new(&[Action::Paste], "Paste from clipboard"));
+        } else if !matches!(self.cur.item, PageItems::Output) {
+            helps.push(Help::new(
+                &[Action::SelectPrevValue, Action::SelectNextValue],
+                "Select current item value",
+            ));
+        }
+        helps.push(Help::new(&[Action::Generate], "Generate ulid"));
         if matches!(self.cur.item, PageItems::Output) {
-            helps.push("<C-e/C-y> Scroll down/up");
-            helps.push("<y> Copy to clipboard");
-            helps.push("<p> Paste from clipboard");
+            helps.push(Help::new(
+                &[Action::ScrollDown, Action::ScrollUp],
+                "Scroll down/up",
+            ));
+            helps.push(Help::new(&[Action::Copy], "Copy to clipboard"));
+            helps.push(Help::new(&[Action::Paste], "Paste from clipboard"));
         }
         helps
     }
+
+    fn commands(&self) -> Vec<Command> {
+        let msg = |msg| Msg::Page(PageMsg::Ulid(msg));
+        let mut commands = vec![Command::new("ULID: generate", vec![msg(UlidMsg::Generate)])];
+        for count in [1, 10] {
+            for case_sel in CaseItemSelect::vars_vec() {
+                let label = format!("ULID: generate {count} {}", case_sel.str().to_lowercase());
+                let msgs = vec![
+                    msg(UlidMsg::SetCount(count)),
+                    msg(UlidMsg::SetCase(case_sel)),
+                    msg(UlidMsg::Generate),
+                ];
+                commands.push(Command::new(label, msgs));
+            }
+        }
+        for gen_sel in GenerationItemSelect::vars_vec() {
+            let label = format!("ULID: {} generation", gen_sel.str().to_lowercase());
+            commands.push(Command::new(
+                label,
+                vec![msg(UlidMsg::SetGeneration(gen_sel))],
+            ));
+        }
+        for view_sel in ViewItemSelect::vars_vec() {
+            let label = format!("ULID: view {}", view_sel.str().to_lowercase());
+            commands.push(Command::new(label, vec![msg(UlidMsg::SetView(view_sel))]));
+        }
+        commands.push(Command::new(
+            "ULID: edit timestamp",
+            vec![
+                msg(UlidMsg::SelectItem(PageItems::Timestamp)),
+                msg(UlidMsg::EditStart),
+            ],
+        ));
+        let output = msg(UlidMsg::SelectItem(PageItems::Output));
+        commands.push(Command::new(
+            "ULID: copy output",
+            vec![output.clone(), msg(UlidMsg::Copy)],
+        ));
+        commands.push(Command::new(
+            "ULID: paste from clipboard",
+            vec![output.clone(), msg(UlidMsg::Paste)],
+        ));
+        commands.push(Command::new(
+            "ULID: inspect ULIDs from clipboard",
+            vec![
+                output,
+                msg(UlidMsg::Paste),
+                msg(UlidMsg::SetView(ViewItemSelect::Inspect)),
+            ],
+        ));
+        commands
+    }
+
+    fn save(&self, session: &mut Session) {
+        session.ulid = Some(UlidSession {
+            case: self.cur.case_sel,
+            count: self.cur.count,
+            generation: self.cur.gen_sel,
+            timestamp: self.cur.timestamp.value().into(),
+            view: self.cur.view_sel,
+        });
+    }
+
+    fn restore(&mut self, session: &Session) {
+        let Some(saved) = &session.ulid else {
+            return;
+        };
+        self.cur.case_sel = saved.case;
+        self.cur.count = saved.count.clamp(1, COUNT_MAX);
+        self.cur.gen_sel = saved.generation;
+        self.cur.timestamp = Input::new(saved.timestamp.clone());
+        self.cur.view_sel = saved.view;
+    }
 }
 
 impl UlidPage {
@@ -179,12 +398,19 @@ impl UlidPage {
             PageItems::Case => {
                 self.cur.case_sel.next_mut();
             }
+            PageItems::Generation => {
+                self.cur.gen_sel.next_mut();
+            }
+            PageItems::View => {
+                self.cur.view_sel.next_mut();
+                self.cur.output_state = ScrollOutputState::default();
+            }
             PageItems::Count => {
                 if self.cur.count < COUNT_MAX {
                     self.cur.count += 1;
                 }
             }
-            PageItems::Output => {}
+            PageItems::Timestamp | PageItems::Output => {}
         }
     }
 
@@ -193,12 +419,19 @@ impl UlidPage {
             PageItems::Case => {
                 self.cur.case_sel.prev_mut();
             }
+            PageItems::Generation => {
+                self.cur.gen_sel.prev_mut();
+            }
+            PageItems::View => {
+                self.cur.view_sel.prev_mut();
+                self.cur.output_state = ScrollOutputState::default();
+            }
             PageItems::Count => {
                 if self.cur.count > 1 {
                     self.cur.count -= 1;
                 }
             }
-            PageItems::Output => {}
+            PageItems::Timestamp | PageItems::Output => {}
         }
     }
 
@@ -216,8 +449,28 @@ impl UlidPage {
         self.cur.output_state.scroll_up();
     }
 
-    fn generate_ulid(&mut self) {
-        self.ids = (0..self.cur.count).map(|_| Ulid::new()).collect();
+    fn generate_ulid(&mut self) -> Option<Msg> {
+        let timestamp = self.cur.timestamp.value();
+        let timestamp = if timestamp.trim().is_empty() {
+            None
+        } else {
+            match ulid::parse_timestamp(timestamp) {
+                Ok(timestamp) => Some(timestamp),
+                Err(e) => return Some(Msg::NotifyWarn(format!("Could not generate ULID: {e}"))),
+            }
+        };
+        let req = UlidRequest {
+            count: self.cur.count,
+            monotonic: self.cur.gen_sel == GenerationItemSelect::Monotonic,
+            timestamp,
+        };
+        match ulid::generate(&req) {
+            Ok(ids) => {
+                self.ids = ids;
+                None
+            }
+            Err(e) => Some(Msg::NotifyWarn(format!("Could not generate ULID: {e}"))),
+        }
     }
 
     fn copy_to_clipboard(&self) -> Option<Msg> {
@@ -231,22 +484,24 @@ impl UlidPage {
     }
 
     fn paste_from_clipboard(&mut self) -> Option<Msg> {
-        if !matches!(self.cur.item, PageItems::Output) {
+        if !matches!(self.cur.item, PageItems::Timestamp | PageItems::Output) {
             return None;
         }
 
-        let text = util::paste_from_clipboard().unwrap();
-        let mut ids: Vec<Ulid> = Vec::new();
-        let mut failure_count = 0;
-        for s in text.lines() {
-            if let Ok(id) = Ulid::from_string(s) {
-                ids.push(id);
-            } else {
-                failure_count += 1;
-            }
+        let text = match util::paste_from_clipboard() {
+            Ok(text) => text,
+            Err(msg) => return Some(msg),
+        };
+        if self.cur.item == PageItems::Timestamp {
+            let value = text.trim().to_string();
+            self.cur.timestamp = self.cur.timestamp.clone().with_value(value);
+            return None;
         }
-        self.ids = ids;
+        // either format is accepted so that the other can be converted to this one
+        let parsed = id128::parse_lines(&text);
+        self.ids = parsed.ids.into_iter().map(Ulid).collect();
 
+        let failure_count = parsed.failure_count;
         if failure_count > 0 {
             let msg = format!("Could not parse {failure_count} lines of string to ULID");
             Some(Msg::NotifyWarn(msg))
@@ -256,19 +511,113 @@ impl UlidPage {
     }
 
     fn format_ulid(&self, id: &Ulid) -> String {
-        match self.cur.case_sel {
-            CaseItemSelect::Uppercase => id.to_string(),
-            CaseItemSelect::Lowercase => id.to_string().to_lowercase(),
-        }
+        let format = UlidFormat {
+            uppercase: self.cur.case_sel == CaseItemSelect::Uppercase,
+        };
+        ulid::format(id, format)
     }
 
-    fn render_output(&mut self, f: &mut Frame, area: Rect) {
-        let lines: Vec<Line> = self
-            .ids
-            .iter()
-            .map(|id| Line::raw(self.format_ulid(id)))
-            .collect();
-        let output = ScrollOutput::new(lines, self.focused, self.cur.item == PageItems::Output);
+    fn render_timestamp(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let selected = self.cur.item == PageItems::Timestamp;
+        let input_style = theme.item_style(self.focused, selected);
+
+        let block = Block::bordered()
+            .style(input_style)
+            .title("Timestamp (now if empty)")
+            .padding(Padding::horizontal(1));
+        util::render_input(
+            f,
+            area,
+            &self.cur.timestamp,
+            block,
+            self.cur.edit && selected,
+        );
+    }
+
+    fn render_output(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let lines: Vec<Line> = match self.cur.view_sel {
+            ViewItemSelect::List => self
+                .ids
+                .iter()
+                .map(|id| Line::raw(self.format_ulid(id)))
+                .collect(),
+            ViewItemSelect::Inspect => self
+                .ids
+                .iter()
+                .enumerate()
+                .flat_map(|(i, id)| self.inspect_lines(i, id, theme))
+                .collect(),
+            ViewItemSelect::Convert => self
+                .ids
+                .iter()
+                .flat_map(|id| {
+                    let mut lines = vec![Line::raw(self.format_ulid(id))];
+                    lines.extend(util::conversion_lines(id.0, theme));
+                    lines.push(Line::default());
+                    lines
+                })
+                .collect(),
+        };
+        let output = ScrollOutput::new(
+            lines,
+            self.focused,
+            self.cur.item == PageItems::Output,
+            theme,
+        );
         f.render_stateful_widget(output, area, &mut self.cur.output_state);
     }
+
+    fn inspect_lines(&self, i: usize, id: &Ulid, theme: &Theme) -> Vec<Line<'static>> {
+        let info = ulid::inspect(id);
+        let field = |name: &str, value: String| util::field_line(name, value, theme);
+
+        let utc = info.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true);
+        let local = info
+            .timestamp
+            .with_timezone(&Local)
+            .to_rfc3339_opts(SecondsFormat::Millis, false);
+        let mut lines = vec![
+            Line::raw(self.format_ulid(id)),
+            field("Time (UTC)", utc),
+            field("Time (Local)", local),
+            field("Timestamp", format!("{} ms", info.timestamp_ms)),
+            field("Randomness", info.randomness_hex()),
+        ];
+        if let Some(prev) = i.checked_sub(1).map(|p| &self.ids[p]) {
+            let order = match prev.cmp(id) {
+                std::cmp::Ordering::Less => Span::raw("after the previous ULID"),
+                std::cmp::Ordering::Equal => Span::styled("same as the previous ULID", theme.warn),
+                std::cmp::Ordering::Greater => {
+                    Span::styled("before the previous ULID (out of order)", theme.warn)
+                }
+            };
+            lines.push(Line::from(vec![util::field_label("Order", theme), order]));
+        }
+        lines.push(Line::default());
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_render_long_multibyte_timestamp() {
+        let mut page = UlidPage::new(true, &UlidConfig::default());
+        page.cur.timestamp = Input::new("時刻".repeat(20));
+        page.cur.item = PageItems::Timestamp;
+        page.cur.edit = true;
+
+        let mut terminal = Terminal::new(TestBackend::new(41, 40)).unwrap();
+        terminal
+            .draw(|f| page.render(f, f.area(), &Theme::default()))
+            .unwrap();
+
+        // the end of the timestamp is shown, with the cursor after it
+        let cursor = terminal.get_cursor_position().unwrap();
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(cursor.x - 2, cursor.y)].symbol(), "刻");
+    }
 }
@@ -1,9 +1,9 @@
-use chrono::{DateTime, Local, Utc};
+use btox::unixtime::{self, ParsedInput, TimeZone};
 use itsuki::zero_indexed_enum;
 use ratatui::{
-    crossterm::event::KeyCode,
+    crossterm::event::KeyEvent,
     layout::Rect,
-    style::{Color, Style},
+    style::Style,
     widgets::{Block, Borders, Padding, Paragraph},
     Frame,
 };
@@ -11,13 +11,17 @@ use ratatui_macros::vertical;
 use tui_input::{backend::crossterm::EventHandler, Input};
 
 use crate::{
-    fn_next_prev_mut, fn_str_map, key_code, key_code_char,
+    config::UnixTimeConfig,
+    fn_next_prev_mut, fn_str_map,
+    keymap::{Action, Help, Keymap},
     msg::{Msg, PageMsg, UnixTimeMsg},
     pages::{page::Page, util},
+    palette::Command,
+    session::{Session, UnixTimeSession},
+    theme::Theme,
     widget::select::Select,
 };
-
-const DEFAUT_OUTPUT_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f%:z";
+use serde::{Deserialize, Serialize};
 
 pub struct UnixTimePage {
     focused: bool,
@@ -55,11 +59,12 @@ impl Status {
 }
 
 impl UnixTimePage {
-    pub fn new(focused: bool) -> UnixTimePage {
-        let output_format = Input::new(DEFAUT_OUTPUT_FORMAT.to_string());
+    pub fn new(focused: bool, config: &UnixTimeConfig) -> UnixTimePage {
+        let output_format = Input::new(config.output_format.clone());
         UnixTimePage {
             focused,
             cur: CurrentStatus {
+                tz_sel: config.time_zone,
                 output_format,
                 ..Default::default()
             },
@@ -77,7 +82,8 @@ enum PageItems {
     OutputFormat,
 }
 
-#[derive(Default)]
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
 #[zero_indexed_enum]
 enum TimeZoneItemSelect {
     #[default]
@@ -94,26 +100,31 @@ impl TimeZoneItemSelect {
     fn_next_prev_mut! {}
 }
 
+impl From<TimeZoneItemSelect> for TimeZone {
+    fn from(tz_sel: TimeZoneItemSelect) -> Self {
+        match tz_sel {
+            TimeZoneItemSelect::Utc => TimeZone::Utc,
+            TimeZoneItemSelect::Local => TimeZone::Local,
+        }
+    }
+}
+
 impl Page for UnixTimePage {
-    fn handle_key(&self, key: ratatui::crossterm::event::KeyEvent) -> Option<Msg> {
+    fn handle_key(&self, key: KeyEvent, keymap: &Keymap) -> Option<Msg> {
         let msg = if self.cur.edit {
-            match key {
-                key_code!(KeyCode::Esc) => UnixTimeMsg::EditEnd,
+            match keymap.resolve_input(key) {
+                Some(Action::Cancel) => UnixTimeMsg::EditEnd,
                 _ => UnixTimeMsg::EditKeyEvent(key),
             }
         } else {
-            match key {
-                key_code_char!('j') | key_code!(KeyCode::Down) => UnixTimeMsg::SelectNextItem,
-                key_code_char!('k') | key_code!(KeyCode::Up) => UnixTimeMsg::SelectPrevItem,
-                key_code_char!('l') | key_code!(KeyCode::Right) => {
-                    UnixTimeMsg::CurrentItemSelectNext
-                }
-                key_code_char!('h') | key_code!(KeyCode::Left) => {
-                    UnixTimeMsg::CurrentItemSelectPrev
-                }
-                key_code_char!('y') => UnixTimeMsg::Copy,
-                key_code_char!('p') => UnixTimeMsg::Paste,
-                key_code_char!('e') => UnixTimeMsg::EditStart,
+            match keymap.resolve(key)? {
+                Action::SelectNextItem => UnixTimeMsg::SelectNextItem,
+                Action::SelectPrevItem => UnixTimeMsg::SelectPrevItem,
+                Action::SelectNextValue => UnixTimeMsg::CurrentItemSelectNext,
+                Action::SelectPrevValue => UnixTimeMsg::CurrentItemSelectPrev,
+                Action::Copy => UnixTimeMsg::Copy,
+                Action::Paste => UnixTimeMsg::Paste,
+                Action::Edit => UnixTimeMsg::EditStart,
                 _ => return None,
             }
         };
@@ -139,7 +150,14 @@ impl Page for UnixTimePage {
                     return self.copy_to_clipboard();
                 }
                 UnixTimeMsg::Paste => {
-                    self.paste_from_clipboard();
+                    return self.paste_from_clipboard();
+                }
+                UnixTimeMsg::SelectItem(item) => {
+                    self.cur.item = item;
+                }
+                UnixTimeMsg::SetTimeZone(tz_sel) => {
+                    self.cur.tz_sel = tz_sel;
+                    self.update_output();
                 }
                 UnixTimeMsg::EditStart => {
                     self.edit_start();
@@ -155,24 +173,23 @@ impl Page for UnixTimePage {
         None
     }
 
-    fn render(&mut self, f: &mut Frame, area: Rect) {
+    fn render(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
         let chunks = vertical![==3, ==2, ==3, ==1, ==2, ==3, ==1].split(area);
 
-        self.render_input(f, chunks[0], &self.cur.input, PageItems::Input, "Input");
+        self.render_input(
+            f,
+            chunks[0],
+            theme,
+            &self.cur.input,
+            PageItems::Input,
+            "Input",
+        );
 
         if !matches!(self.cur.input_status, Status::None) {
-            self.render_status(f, chunks[1], &self.cur.input_status);
+            self.render_status(f, chunks[1], theme, &self.cur.input_status);
         }
 
-        let output_style = if self.focused {
-            if self.cur.item == PageItems::Output {
-                Style::default().fg(Color::Blue)
-            } else {
-                Style::default().fg(Color::Reset)
-            }
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
+        let output_style = theme.item_style(self.focused, self.cur.item == PageItems::Output);
 
         let output = Paragraph::new(self.cur.output.clone()).block(
             Block::bordered()
@@ -187,19 +204,21 @@ impl Page for UnixTimePage {
             self.cur.tz_sel.val(),
             self.cur.item == PageItems::TimeZone,
             self.focused,
+            theme,
         );
         f.render_widget(tz_sel, chunks[4]);
 
         self.render_input(
             f,
             chunks[5],
+            theme,
             &self.cur.output_format,
             PageItems::OutputFormat,
             "Output Format",
         );
 
         if !matches!(self.cur.output_format_status, Status::None) {
-            self.render_status(f, chunks[6], &self.cur.output_format_status);
+            self.render_status(f, chunks[6], theme, &self.cur.output_format_status);
         }
     }
 
@@ -211,28 +230,85 @@ impl Page for UnixTimePage {
         self.focused = false;
     }
 
-    fn helps(&self) -> Vec<&str> {
+    fn helps(&self) -> Vec<Help> {
         use PageItems::*;
-        let mut helps: Vec<&str> = Vec::new();
+        let mut helps: Vec<Help> = Vec::new();
         if self.cur.edit {
-            helps.push("<Esc> End edit");
+            helps.push(Help::new(&[Action::Cancel], "End edit"));
         } else {
-            helps.push("<j/k> Select item");
+            helps.push(Help::new(
+                &[Action::SelectNextItem, Action::SelectPrevItem],
+                "Select item",
+            ));
             if matches!(self.cur.item, TimeZone) {
-                helps.push("<h/l> Select current item value");
+                helps.push(Help::new(
+                    &[Action::SelectPrevValue, Action::SelectNextValue],
+                    "Select current item value",
+                ));
             }
             if matches!(self.cur.item, Input | OutputFormat) {
-                helps.push("<e> Edit");
+                helps.push(Help::new(&[Action::Edit], "Edit"));
             }
             if matches!(self.cur.item, Input | Output | OutputFormat) {
-                helps.push("<y> Copy to clipboard");
+                helps.push(Help::new(&[Action::Copy], "Copy to clipboard"));
             }
             if matches!(self.cur.item, Input | OutputFormat) {
-                helps.push("<p> Paste from clipboard");
+                helps.push(Help::new(&[Action::Paste], "Paste from clipboard"));
             }
         }
         helps
     }
+
+    fn commands(&self) -> Vec<Command> {
+        let msg = |msg| Msg::Page(PageMsg::UnixTime(msg));
+        let mut commands: Vec<Command> = TimeZoneItemSelect::vars_vec()
+            .into_iter()
+            .map(|tz_sel| {
+                let label = format!("Unix time: show in {}", tz_sel.str());
+                Command::new(label, vec![msg(UnixTimeMsg::SetTimeZone(tz_sel))])
+            })
+            .collect();
+        let input = msg(UnixTimeMsg::SelectItem(PageItems::Input));
+        let output_format = msg(UnixTimeMsg::SelectItem(PageItems::OutputFormat));
+        commands.push(Command::new(
+            "Unix time: edit input",
+            vec![input.clone(), msg(UnixTimeMsg::EditStart)],
+        ));
+        commands.push(Command::new(
+            "Unix time: paste input",
+            vec![input, msg(UnixTimeMsg::Paste)],
+        ));
+        commands.push(Command::new(
+            "Unix time: copy output",
+            vec![
+                msg(UnixTimeMsg::SelectItem(PageItems::Output)),
+                msg(UnixTimeMsg::Copy),
+            ],
+        ));
+        commands.push(Command::new(
+            "Unix time: edit output format",
+            vec![output_format, msg(UnixTimeMsg::EditStart)],
+        ));
+        commands
+    }
+
+    fn save(&self, session: &mut Session) {
+        session.unixtime = Some(UnixTimeSession {
+            time_zone: self.cur.tz_sel,
+            output_format: self.cur.output_format.value().into(),
+            input: self.cur.input.value().into(),
+        });
+    }
+
+    fn restore(&mut self, session: &Session) {
+        let Some(saved) = &session.unixtime else {
+            return;
+        };
+        self.cur.tz_sel = saved.time_zone;
+        self.cur.output_format = Input::new(saved.output_format.clone());
+        self.cur.input = Input::new(saved.input.clone());
+        self.update_output();
+    }
 }
 
 impl UnixTimePage {
@@ -315,13 +391,16 @@ impl UnixTimePage {
         util::copy_to_clipboard(text)
     }
 
-    fn paste_from_clipboard(&mut self) {
+    fn paste_from_clipboard(&mut self) -> Option<Msg> {
         use PageItems::*;
         if !matches!(self.cur.item, Input | OutputFormat) {
-            return;
+            return None;
         }
 
-        let text = util::paste_from_clipboard().unwrap();
+        let text = match util::paste_from_clipboard() {
+            Ok(text) => text,
+            Err(msg) => return Some(msg),
+        };
         match self.cur.item {
             Input => self.cur.input = self.cur.input.clone().with_value(text),
             OutputFormat => {
@@ -331,6 +410,7 @@ impl UnixTimePage {
         }
 
         self.update_output();
+        None
     }
 
     fn update_output(&mut self) {
@@ -338,68 +418,63 @@ impl UnixTimePage {
         if s.is_empty() {
             self.cur.output = String::new();
             self.cur.input_status = Status::None;
-        } else if let Some(dt) = parse_as_unix_timestamp(s) {
-            let f = self.cur.output_format.value();
-            if is_valid_datetime_format(f) {
-                self.cur.output = match self.cur.tz_sel {
-                    TimeZoneItemSelect::Utc => {
-                        dt.datetime.with_timezone(&Utc).format(f).to_string()
+            return;
+        }
+        match unixtime::parse(s) {
+            Ok(ParsedInput::UnixTimestamp(dt)) => {
+                let f = self.cur.output_format.value();
+                match unixtime::format(&dt.datetime, self.cur.tz_sel.into(), f) {
+                    Ok(output) => {
+                        self.cur.output = output;
+                        self.cur.output_format_status = Status::None;
                     }
-                    TimeZoneItemSelect::Local => {
-                        dt.datetime.with_timezone(&Local).format(f).to_string()
+                    Err(e) => {
+                        self.cur.output = String::new();
+                        self.cur.output_format_status = Status::Warn(e.to_string());
                     }
-                };
-                self.cur.output_format_status = Status::None;
-            } else {
+                }
+                let msg = format!("valid unix timestamp ({:?})", dt.resolution);
+                self.cur.input_status = Status::Info(msg);
+            }
+            Ok(ParsedInput::Datetime(dt)) => {
+                self.cur.output = dt.timestamp().to_string();
+                self.cur.input_status = Status::Info("valid datetime".into());
+            }
+            Err(e) => {
                 self.cur.output = String::new();
-                self.cur.output_format_status = Status::Warn("invalid format".into());
+                self.cur.input_status = Status::Warn(e.to_string());
             }
-            let msg = format!("valid unix timestamp ({:?})", dt.resolution);
-            self.cur.input_status = Status::Info(msg);
-        } else if let Some(dt) = parse_as_datetime(s) {
-            self.cur.output = dt.timestamp().to_string();
-            self.cur.input_status = Status::Info("valid datetime".into());
-        } else {
-            self.cur.output = String::new();
-            self.cur.input_status = Status::Warn("invalid input".into());
         }
     }
 
-    fn render_input(&self, f: &mut Frame, area: Rect, input: &Input, item: PageItems, title: &str) {
-        let input_style = if self.focused {
-            if self.cur.item == item {
-                Style::default().fg(Color::Blue)
-            } else {
-                Style::default().fg(Color::Reset)
-            }
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
-
-        let input_max_width = area.width - 4;
-        let input_value = input.value();
-        let input_start = input_value.len().saturating_sub(input_max_width as usize);
-        let input_content = &input_value[input_start..];
-        let input_widget = Paragraph::new(input_content).block(
-            Block::bordered()
-                .style(input_style)
-                .title(title)
-                .padding(Padding::horizontal(1)),
+    fn render_input(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        theme: &Theme,
+        input: &Input,
+        item: PageItems,
+        title: &str,
+    ) {
+        let input_style = theme.item_style(self.focused, self.cur.item == item);
+
+        let block = Block::bordered()
+            .style(input_style)
+            .title(title)
+            .padding(Padding::horizontal(1));
+        util::render_input(
+            f,
+            area,
+            input,
+            block,
+            self.cur.edit && self.cur.item == item,
         );
-        f.render_widget(input_widget, area);
-
-        if self.cur.edit && self.cur.item == item {
-            let visual_cursor = input.visual_cursor() as u16;
-            let x = area.x + 2 + visual_cursor.min(input_max_width);
-            let y = area.y + 1;
-            f.set_cursor_position((x, y));
-        }
     }
 
-    fn render_status(&self, f: &mut Frame, area: Rect, status: &Status) {
+    fn render_status(&self, f: &mut Frame, area: Rect, theme: &Theme, status: &Status) {
         let status_style = match status {
-            Status::Info(_) => Style::default().fg(Color::Green),
-            Status::Warn(_) => Style::default().fg(Color::Yellow),
+            Status::Info(_) => theme.info,
+            Status::Warn(_) => theme.warn,
             _ => Style::default(),
         };
         let status = Paragraph::new(status.str().to_string()).block(
@@ -411,115 +486,3 @@ impl UnixTimePage {
         f.render_widget(status, area)
     }
 }
-
-struct DateTimeWithResolution {
-    datetime: DateTime<Utc>,
-    resolution: Resolution,
-}
-
-#[derive(Debug)]
-enum Resolution {
-    Second,
-    Milli,
-    Micro,
-    Nano,
-}
-
-fn parse_as_unix_timestamp(s: &str) -> Option<DateTimeWithResolution> {
-    s.parse::<u128>().ok().and_then(to_timestamp)
-}
-
-fn to_timestamp(t: u128) -> Option<DateTimeWithResolution> {
-    if t < 1_000_000_000_000 {
-        // seconds
-        DateTime::from_timestamp(t as i64, 0).map(|datetime| DateTimeWithResolution {
-            datetime,
-            resolution: Resolution::Second,
-        })
-    } else if t < 1_000_000_000_000_000 {
-        // millis
-        let sec = t / 1_000;
-        let millis = t % 1_000;
-        DateTime::from_timestamp(sec as i64, millis as u32 * 1_000_000).map(|datetime| {
-            DateTimeWithResolution {
-                datetime,
-                resolution: Resolution::Milli,
-            }
-        })
-    } else if t < 1_000_000_000_000_000_000 {
-        // micros
-        let sec = t / 1_000_000;
-        let micros = t % 1_000_000;
-        DateTime::from_timestamp(sec as i64, micros as u32 * 1_000).map(|datetime| {
-            DateTimeWithResolution {
-                datetime,
-                resolution: Resolution::Micro,
-            }
-        })
-    } else if t < 1_000_000_000_000_000_000_000 {
-        // nanos
-        let sec = t / 1_000_000_000;
-        let nanos = t % 1_000_000_000;
-        DateTime::from_timestamp(sec as i64, nanos as u32).map(|datetime| DateTimeWithResolution {
-            datetime,
-            resolution: Resolution::Nano,
-        })
-    } else {
-        // too large
-        None
-    }
-}
-
-fn parse_as_datetime(s: &str) -> Option<DateTime<Utc>> {
-    s.parse::<DateTime<Utc>>().ok()
-}
-
-fn is_valid_datetime_format(s: &str) -> bool {
-    // https://github.com/chronotope/chrono/issues/47#issuecomment-320471394
-    chrono::format::StrftimeItems::new(s).all(|item| item != chrono::format::Item::Error)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_to_timestamp() {
-        assert_eq!(
-            to_timestamp(1_634_567_890).map(|d| d.datetime),
-            parse_from_rfc3339("2021-10-18T14:38:10Z")
-        );
-        assert_eq!(
-            to_timestamp(1_634_567_890_123).map(|d| d.datetime),
-            parse_from_rfc3339("2021-10-18T14:38:10.123Z")
-        );
-        assert_eq!(
-            to_timestamp(1_634_567_890_123_456).map(|d| d.datetime),
-            parse_from_rfc3339("2021-10-18T14:38:10.123456Z")
-        );
-        assert_eq!(
-            to_timestamp(1_634_567_890_123_456_789).map(|d| d.datetime),
-            parse_from_rfc3339("2021-10-18T14:38:10.123456789Z")
-        );
-        assert_eq!(
-            to_timestamp(1_634_567).map(|d| d.datetime),
-            parse_from_rfc3339("1970-01-19T22:02:47.000Z")
-        );
-        assert_eq!(
-            to_timestamp(1_634_567_890_123_456_789_000).map(|d| d.datetime),
-            None
-        );
-    }
-
-    fn parse_from_rfc3339(s: &str) -> Option<DateTime<Utc>> {
-        Some(DateTime::parse_from_rfc3339(s).unwrap().to_utc())
-    }
-
-    #[test]
-    fn test_is_valid_datetime_format() {
-        assert!(is_valid_datetime_format("%Y-%m-%dT%H:%M:%S%.f%:z"));
-        assert!(is_valid_datetime_format("%Y-%m-%d"));
-        assert!(!is_valid_datetime_format("%Y-%m-%dT%H:%M:%S%.f%:z%"));
-        assert!(!is_valid_datetime_format("%"));
-    }
-}
@@ -1,23 +1,32 @@
+use btox::url::{self, Charset, Mode, UrlRequest};
 use itsuki::zero_indexed_enum;
-use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
 use ratatui::{
-    crossterm::event::KeyCode,
+    crossterm::event::KeyEvent,
     layout::Rect,
-    style::{Color, Style},
     widgets::{Block, Borders, Padding, Paragraph, Wrap},
     Frame,
 };
 use ratatui_macros::vertical;
 
 use crate::{
-    fn_next_prev_mut, fn_str_map, key_code, key_code_char,
+    config::UrlConfig,
+    fn_next_prev_mut, fn_str_map,
+    keymap::{Action, Help, Keymap},
     msg::{Msg, PageMsg, UrlMsg},
-    pages::{page::Page, util},
+    pages::{
+        file::{self, FileLoad, LoadState, PathPrompt, PromptResult, FILE_SIZE_MAX},
+        page::Page,
+        util,
+    },
+    palette::Command,
+    session::{Session, UrlSession},
+    theme::Theme,
     widget::{
         scroll::{ScrollOutput, ScrollOutputState},
         select::Select,
     },
 };
+use serde::{Deserialize, Serialize};
 
 pub struct UrlPage {
     focused: bool,
@@ -33,13 +42,20 @@ struct CurrentStatus {
     input_state: ScrollOutputState,
     output: String,
     status: InputStatus,
+    /// The file used instead of the input, if loaded.
+    file: Option<FileLoad>,
+    prompt: Option<PathPrompt>,
 }
 
 impl UrlPage {
-    pub fn new(focused: bool) -> UrlPage {
+    pub fn new(focused: bool, config: &UrlConfig) -> UrlPage {
         UrlPage {
             focused,
-            cur: CurrentStatus::default(),
+            cur: CurrentStatus {
+                eod_sel: config.mode,
+                charset_sel: config.charset,
+                ..Default::default()
+            },
         }
     }
 }
@@ -61,7 +77,8 @@ enum PageItems {
     Output,
 }
 
-#[derive(Default)]
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
 #[zero_indexed_enum]
 enum EncodeOrDecodeSelect {
     #[default]
@@ -78,7 +95,17 @@ impl EncodeOrDecodeSelect {
     fn_next_prev_mut! {}
 }
 
-#[derive(Default)]
+impl From<EncodeOrDecodeSelect> for Mode {
+    fn from(eod_sel: EncodeOrDecodeSelect) -> Self {
+        match eod_sel {
+            EncodeOrDecodeSelect::Encode => Mode::Encode,
+            EncodeOrDecodeSelect::Decode => Mode::Decode,
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
 #[zero_indexed_enum]
 enum CharsetSelect {
     #[default]
@@ -93,17 +120,37 @@ impl CharsetSelect {
     fn_next_prev_mut! {}
 }
 
+impl From<CharsetSelect> for Charset {
+    fn from(charset_sel: CharsetSelect) -> Self {
+        match charset_sel {
+            CharsetSelect::Utf8 => Charset::Utf8,
+        }
+    }
+}
+
 impl Page for UrlPage {
-    fn handle_key(&self, key: ratatui::crossterm::event::KeyEvent) -> Option<Msg> {
-        let msg = match key {
-            key_code_char!('j') | key_code!(KeyCode::Down) => UrlMsg::SelectNextItem,
-            key_code_char!('k') | key_code!(KeyCode::Up) => UrlMsg::SelectPrevItem,
-            key_code_char!('l') | key_code!(KeyCode::Right) => UrlMsg::CurrentItemSelectNext,
-            key_code_char!('h') | key_code!(KeyCode::Left) => UrlMsg::CurrentItemSelectPrev,
-            key_code_char!('e', Ctrl) => UrlMsg::ScrollDown,
-            key_code_char!('y', Ctrl) => UrlMsg::ScrollUp,
-            key_code_char!('y') => UrlMsg::Copy,
-            key_code_char!('p') => UrlMsg::Paste,
+    fn handle_key(&self, key: KeyEvent, keymap: &Keymap) -> Option<Msg> {
+        if self.cur.prompt.is_some() {
+            let msg = match keymap.resolve_input(key) {
+                Some(Action::Confirm) => UrlMsg::LoadConfirm,
+                Some(Action::Cancel) => UrlMsg::LoadEnd,
+                Some(Action::SelectNextCandidate) => UrlMsg::LoadSelectNextCandidate,
+                Some(Action::SelectPrevCandidate) => UrlMsg::LoadSelectPrevCandidate,
+                _ => UrlMsg::LoadKeyEvent(key),
+            };
+            return Some(Msg::Page(PageMsg::Url(msg)));
+        }
+
+        let msg = match keymap.resolve(key)? {
+            Action::SelectNextItem => UrlMsg::SelectNextItem,
+            Action::SelectPrevItem => UrlMsg::SelectPrevItem,
+            Action::SelectNextValue => UrlMsg::CurrentItemSelectNext,
+            Action::SelectPrevValue => UrlMsg::CurrentItemSelectPrev,
+            Action::ScrollDown => UrlMsg::ScrollDown,
+            Action::ScrollUp => UrlMsg::ScrollUp,
+            Action::Copy => UrlMsg::Copy,
+            Action::Paste => UrlMsg::Paste,
+            Action::LoadFile => UrlMsg::LoadStart,
             _ => return None,
         };
         Some(Msg::Page(PageMsg::Url(msg)))
@@ -134,14 +181,52 @@ impl Page for UrlPage {
                     return self.copy_to_clipboard();
                 }
                 UrlMsg::Paste => {
-                    self.paste_from_clipboard();
+                    return self.paste_from_clipboard();
+                }
+                UrlMsg::SelectItem(item) => {
+                    self.cur.item = item;
+                }
+                UrlMsg::SetMode(eod_sel) => {
+                    self.cur.eod_sel = eod_sel;
+                    self.update_output();
+                }
+                UrlMsg::SetCharset(charset_sel) => {
+                    self.cur.charset_sel = charset_sel;
+                    self.update_output();
+                }
+                UrlMsg::LoadStart => {
+                    self.cur.prompt = Some(PathPrompt::new());
+                }
+                UrlMsg::LoadEnd => {
+                    self.cur.prompt = None;
+                }
+                UrlMsg::LoadConfirm => {
+                    return self.load_confirm();
+                }
+                UrlMsg::LoadSelectNextCandidate => {
+                    if let Some(prompt) = &mut self.cur.prompt {
+                        prompt.select_next();
+                    }
+                }
+                UrlMsg::LoadSelectPrevCandidate => {
+                    if let Some(prompt) = &mut self.cur.prompt {
+                        prompt.select_prev();
+                    }
+                }
+                UrlMsg::LoadKeyEvent(key) => {
+                    if let Some(prompt) = &mut self.cur.prompt {
+                        prompt.handle_key(key);
+                    }
+                }
+                UrlMsg::LoadProgress => {
+                    return self.load_progress();
                 }
             }
         }
         None
     }
 
-    fn render(&mut self, f: &mut Frame, area: Rect) {
+    fn render(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
         let chunks = vertical![==2, ==2, >=0, ==1, >=0].split(area);
 
         let eod_sel = Select::new(
@@ -149,6 +234,7 @@ impl Page for UrlPage {
             self.cur.eod_sel.val(),
             self.cur.item == PageItems::EncodeOrDecode,
             self.focused,
+            theme,
         );
         f.render_widget(eod_sel, chunks[0]);
 
@@ -157,16 +243,33 @@ impl Page for UrlPage {
             self.cur.charset_sel.val(),
             self.cur.item == PageItems::Charset,
             self.focused,
+            theme,
         );
         f.render_widget(charset_sel, chunks[1]);
 
-        let input_text = self.cur.input.clone();
-        let input = ScrollOutput::new(input_text, self.focused, self.cur.item == PageItems::Input)
+        if let Some(file) = &self.cur.file {
+            let input_style = theme.item_style(self.focused, self.cur.item == PageItems::Input);
+            let input = Paragraph::new(file.info_lines(theme)).block(
+                Block::bordered()
+                    .style(input_style)
+                    .title("Input (file)")
+                    .padding(Padding::horizontal(1)),
+            );
+            f.render_widget(input, chunks[2]);
+        } else {
+            let input_text = self.cur.input.clone();
+            let input = ScrollOutput::new(
+                input_text,
+                self.focused,
+                self.cur.item == PageItems::Input,
+                theme,
+            )
             .title("Input");
-        f.render_stateful_widget(input, chunks[2], &mut self.cur.input_state);
+            f.render_stateful_widget(input, chunks[2], &mut self.cur.input_state);
+        }
 
         if let InputStatus::Warn(status) = &self.cur.status {
-            let status_style = Style::default().fg(Color::Red);
+            let status_style = theme.error;
             let status = Paragraph::new(status.as_str()).block(
                 Block::default()
                     .borders(Borders::empty())
@@ -176,15 +279,7 @@ impl Page for UrlPage {
             f.render_widget(status, chunks[3]);
         }
 
-        let output_style = if self.focused {
-            if self.cur.item == PageItems::Output {
-                Style::default().fg(Color::Blue)
-            } else {
-                Style::default().fg(Color::Reset)
-            }
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
+        let output_style = theme.item_style(self.focused, self.cur.item == PageItems::Output);
 
         let output_text = self.cur.output.clone();
         let output = Paragraph::new(output_text)
@@ -196,6 +291,10 @@ impl Page for UrlPage {
             )
             .wrap(Wrap { trim: false });
         f.render_widget(output, chunks[4]);
+
+        if let Some(prompt) = &self.cur.prompt {
+            prompt.render(f, chunks[2].union(chunks[4]), theme);
+        }
     }
 
     fn focus(&mut self) {
@@ -204,26 +303,98 @@ impl Page for UrlPage {
 
     fn unfocus(&mut self) {
         self.focused = false;
+        self.cur.prompt = None;
     }
 
-    fn helps(&self) -> Vec<&str> {
-        let mut helps: Vec<&str> = Vec::new();
-        helps.push("<j/k> Select item");
+    fn helps(&self) -> Vec<Help> {
+        let mut helps: Vec<Help> = Vec::new();
+        if self.cur.prompt.is_some() {
+            helps.push(Help::new(
+                &[Action::SelectNextCandidate, Action::SelectPrevCandidate],
+                "Select candidate",
+            ));
+            helps.push(Help::new(&[Action::Confirm], "Load"));
+            helps.push(Help::new(&[Action::Cancel], "Cancel"));
+            return helps;
+        }
+        helps.push(Help::new(
+            &[Action::SelectNextItem, Action::SelectPrevItem],
+            "Select item",
+        ));
         if matches!(
             self.cur.item,
             PageItems::EncodeOrDecode | PageItems::Charset
         ) {
-            helps.push("<h/l> Select current item value");
+            helps.push(Help::new(
+                &[Action::SelectPrevValue, Action::SelectNextValue],
+                "Select current item value",
+            ));
         }
         if matches!(self.cur.item, PageItems::Output) {
-            helps.push("<y> Copy to clipboard");
+            helps.push(Help::new(&[Action::Copy], "Copy to clipboard"));
         }
         if matches!(self.cur.item, PageItems::Input) {
-            helps.push("<C-e/C-y> Scroll down/up");
-            helps.push("<p> Paste from clipboard");
+            helps.push(Help::new(
+                &[Action::ScrollDown, Action::ScrollUp],
+                "Scroll down/up",
+            ));
+            helps.push(Help::new(&[Action::Paste], "Paste from clipboard"));
+            helps.push(Help::new(&[Action::LoadFile], "Load from file"));
         }
         helps
     }
+
+    fn commands(&self) -> Vec<Command> {
+        let msg = |msg| Msg::Page(PageMsg::Url(msg));
+        let mut commands: Vec<Command> = EncodeOrDecodeSelect::vars_vec()
+            .into_iter()
+            .map(|eod_sel| {
+                let label = format!("URL: {}", eod_sel.str().to_lowercase());
+                Command::new(label, vec![msg(UrlMsg::SetMode(eod_sel))])
+            })
+            .collect();
+        for charset_sel in CharsetSelect::vars_vec() {
+            let label = format!("URL: charset {}", charset_sel.str());
+            commands.push(Command::new(
+                label,
+                vec![msg(UrlMsg::SetCharset(charset_sel))],
+            ));
+        }
+        commands.push(Command::new(
+            "URL: paste input",
+            vec![
+                msg(UrlMsg::SelectItem(PageItems::Input)),
+                msg(UrlMsg::Paste),
+            ],
+        ));
+        commands.push(Command::new("URL: load file", vec![msg(UrlMsg::LoadStart)]));
+        commands.push(Command::new(
+            "URL: copy output",
+            vec![
+                msg(UrlMsg::SelectItem(PageItems::Output)),
+                msg(UrlMsg::Copy),
+            ],
+        ));
+        commands
+    }
+
+    fn save(&self, session: &mut Session) {
+        session.url = Some(UrlSession {
+            mode: self.cur.eod_sel,
+            charset: self.cur.charset_sel,
+            input: self.cur.input.clone(),
+        });
+    }
+
+    fn restore(&mut self, session: &Session) {
+        let Some(saved) = &session.url else {
+            return;
+        };
+        self.cur.eod_sel = saved.mode;
+        self.cur.charset_sel = saved.charset;
+        self.cur.input = saved.input.clone();
+        self.update_output();
+    }
 }
 
 impl UrlPage {
@@ -288,40 +459,83 @@ impl UrlPage {
         util::copy_to_clipboard(text)
     }
 
-    fn paste_from_clipboard(&mut self) {
-        if matches!(self.cur.item, PageItems::Input) {
-            self.cur.input = util::paste_from_clipboard().unwrap();
-            self.update_output();
+    fn paste_from_clipboard(&mut self) -> Option<Msg> {
+        if !matches!(self.cur.item, PageItems::Input) {
+            return None;
         }
-    }
 
-    fn update_output(&mut self) {
-        (self.cur.output, self.cur.status) =
-            calculate_url(&self.cur.input, self.cur.eod_sel, self.cur.charset_sel);
+        self.cur.input = match util::paste_from_clipboard() {
+            Ok(text) => text,
+            Err(msg) => return Some(msg),
+        };
+        self.cur.file = None;
+        self.update_output();
+        None
     }
-}
 
-fn calculate_url(
-    input: &str,
-    eod_sel: EncodeOrDecodeSelect,
-    charset_sel: CharsetSelect,
-) -> (String, InputStatus) {
-    match eod_sel {
-        EncodeOrDecodeSelect::Encode => {
-            let output = match charset_sel {
-                CharsetSelect::Utf8 => utf8_percent_encode(input, NON_ALPHANUMERIC).to_string(),
-            };
-            (output, InputStatus::None)
+    fn load_confirm(&mut self) -> Option<Msg> {
+        let PromptResult::Open(path) = self.cur.prompt.as_mut()?.confirm() else {
+            return None;
+        };
+        self.cur.prompt = None;
+        if path.is_empty() {
+            return None;
         }
-        EncodeOrDecodeSelect::Decode => {
-            let output = percent_decode_str(input).decode_utf8();
-            match output {
-                Ok(decoded) => (decoded.to_string(), InputStatus::None),
-                Err(_) => (
-                    String::new(),
-                    InputStatus::Warn("invalid UTF-8 sequence".into()),
-                ),
+
+        let msg = Msg::Page(PageMsg::Url(UrlMsg::LoadProgress));
+        match FileLoad::start(&path, Some(FILE_SIZE_MAX), msg, file::read_all) {
+            Ok(file) => {
+                self.cur.file = Some(file);
+                self.cur.input.clear();
+                self.cur.input_state = ScrollOutputState::default();
+                self.update_output();
+                None
             }
+            Err(e) => Some(Msg::NotifyError(e)),
+        }
+    }
+
+    fn load_progress(&mut self) -> Option<Msg> {
+        let file = self.cur.file.as_mut()?;
+        if !file.poll() {
+            return None;
+        }
+        if let LoadState::Failed(e) = &file.state {
+            return Some(Msg::NotifyError(e.clone()));
+        }
+        self.update_output();
+        None
+    }
+
+    /// The loaded file is encoded as is, it is decoded only if it is text.
+    fn update_output(&mut self) {
+        let mode: Mode = self.cur.eod_sel.into();
+        let result = match &self.cur.file {
+            Some(file) => match (file.bytes(), mode) {
+                (None, _) => Ok(String::new()),
+                (Some(bytes), Mode::Encode) => Ok(url::encode(bytes)),
+                (Some(bytes), Mode::Decode) => match std::str::from_utf8(bytes) {
+                    Ok(input) => Self::calculate(input, mode, self.cur.charset_sel),
+                    Err(_) => Err("File is not valid UTF-8 text".to_string()),
+                },
+            },
+            None => Self::calculate(&self.cur.input, mode, self.cur.charset_sel),
+        };
+        (self.cur.output, self.cur.status) = match result {
+            Ok(output) => (output, InputStatus::None),
+            Err(e) => (String::new(), InputStatus::Warn(e)),
+        };
+    }
+
+    fn calculate(input: &str, mode: Mode, charset_sel: CharsetSelect) -> Result<String, String> {
+        let req = UrlRequest {
+            input,
+            mode,
+            charset: charset_sel.into(),
+        };
+        match url::calculate(&req) {
+            Ok(res) => Ok(res.output),
+            Err(e) => Err(e.to_string()),
         }
     }
 }
@@ -1,15 +1,119 @@
-use arboard::Clipboard;
+use std::{
+    fs::OpenOptions,
+    io::{self, Write},
+    path::PathBuf,
+};
 
-use crate::msg::Msg;
+use btox::id128;
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+use tui_input::Input;
+
+use crate::{clipboard, msg::Msg, theme::Theme};
 
 pub fn copy_to_clipboard(text: &str) -> Option<Msg> {
-    let result = Clipboard::new().and_then(|mut c| c.set_text(text));
-    match result {
+    match clipboard::get().copy(text) {
         Ok(_) => Some(Msg::NotifyInfo("Copy to clipboard succeeded".into())),
-        Err(_) => Some(Msg::NotifyError("Copy to clipboard failed".into())),
+        Err(e) => Some(Msg::NotifyError(format!("Copy to clipboard failed: {e}"))),
+    }
+}
+
+pub fn paste_from_clipboard() -> Result<String, Msg> {
+    clipboard::get()
+        .paste()
+        .map_err(|e| Msg::NotifyError(format!("Paste from clipboard failed: {e}")))
+}
+
+/// Returns the path with `~/` expanded to the home directory.
+pub fn expand_path(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
     }
 }
 
-pub fn paste_from_clipboard() -> Option<String> {
-    Clipboard::new().and_then(|mut c| c.get_text()).ok()
+/// Writes the bytes to a new file at the path, `~/` is expanded to the home directory.
+/// An existing file is never overwritten, so that a mistyped path does not destroy it.
+pub fn save_to_file(path: &str, bytes: &[u8]) -> Msg {
+    let result = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(expand_path(path))
+        .and_then(|mut file| file.write_all(bytes));
+    match result {
+        Ok(_) => Msg::NotifyInfo(format!("Saved {} bytes to {path}", bytes.len())),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            Msg::NotifyWarn(format!("{path} already exists, choose another path"))
+        }
+        Err(e) => Msg::NotifyError(format!("Could not save to {path}: {e}")),
+    }
+}
+
+/// Renders a single line input in the block, which is bordered with a horizontal padding of 1.
+/// The value is scrolled by the display width to keep the cursor in sight, shown while editing.
+pub fn render_input(f: &mut Frame, area: Rect, input: &Input, block: Block, editing: bool) {
+    let max_width = area.width.saturating_sub(4);
+    let scroll = input.visual_scroll(max_width as usize) as u16;
+    let paragraph = Paragraph::new(input.value())
+        .scroll((0, scroll))
+        .block(block);
+    f.render_widget(paragraph, area);
+
+    if editing {
+        let visual_cursor = (input.visual_cursor() as u16).saturating_sub(scroll);
+        let x = area.x + 2 + visual_cursor.min(max_width);
+        f.set_cursor_position((x, area.y + 1));
+    }
+}
+
+/// Returns the label of a field in the inspect and convert views, padded to align the values.
+pub fn field_label(name: &str, theme: &Theme) -> Span<'static> {
+    Span::styled(format!("  {name:<14}"), theme.disabled)
+}
+
+pub fn field_line(name: &str, value: String, theme: &Theme) -> Line<'static> {
+    Line::from(vec![field_label(name, theme), Span::raw(value)])
+}
+
+/// Returns the lines of the convert view of UUID and ULID pages, without the ID itself.
+pub fn conversion_lines(value: u128, theme: &Theme) -> Vec<Line<'static>> {
+    let reprs = id128::convert(value);
+    vec![
+        field_line("UUID", reprs.hyphenated, theme),
+        field_line("Simple", reprs.simple, theme),
+        field_line("Braced", reprs.braced, theme),
+        field_line("URN", reprs.urn, theme),
+        field_line("ULID", reprs.ulid, theme),
+        field_line("Base62", reprs.base62, theme),
+        field_line("Base58", reprs.base58, theme),
+        field_line("Decimal", reprs.decimal, theme),
+        field_line("Rust", reprs.rust_bytes, theme),
+        field_line("Java", reprs.java_bytes, theme),
+        field_line("C# GUID", reprs.csharp_bytes, theme),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_save_to_file() {
+        let dir = std::env::temp_dir().join(format!("btox_save_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = format!("{}/out.bin", dir.display());
+
+        let msg = save_to_file(&path, b"\x00\xff");
+        assert!(matches!(msg, Msg::NotifyInfo(_)));
+        let msg = save_to_file(&path, b"new");
+        assert!(matches!(msg, Msg::NotifyWarn(_)));
+        assert_eq!(fs::read(&path).unwrap(), b"\x00\xff");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
 }
@@ -1,19 +1,36 @@
+use ::uuid::Uuid;
+use btox::{
+    id128,
+    uuid::{self, Namespace, UuidFormat, UuidRequest, UuidVersion},
+};
+use chrono::{Local, SecondsFormat};
 use itsuki::zero_indexed_enum;
-use ratatui::{crossterm::event::KeyCode, layout::Rect, text::Line, Frame};
-use ratatui_macros::vertical;
-use uuid::Uuid;
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Padding},
+    Frame,
+};
+use tui_input::{backend::crossterm::EventHandler, Input};
 
 use crate::{
-    fn_next_prev_mut, fn_str_map, key_code, key_code_char,
+    config::UuidConfig,
+    fn_next_prev_mut, fn_str_map,
+    keymap::{Action, Help, Keymap},
     msg::{Msg, PageMsg, UuidMsg},
     pages::{page::Page, util},
+    palette::Command,
+    session::{Session, UuidSession},
+    theme::Theme,
     widget::{
         scroll::{ScrollOutput, ScrollOutputState},
         select::Select,
     },
 };
+use serde::{Deserialize, Serialize};
 
-const COUNT_MAX: usize = 100;
+pub(crate) const COUNT_MAX: usize = 100;
 
 pub struct UuidPage {
     focused: bool,
@@ -29,15 +46,25 @@ struct CurrentStatus {
     case_sel: CaseItemSelect,
     ver_sel: VersionItemSelect,
     count: usize,
+    namespace_sel: NamespaceItemSelect,
+    custom_namespace: Input,
+    name: Input,
+    node_id: Input,
+    payload: Input,
+    view_sel: ViewItemSelect,
     output_state: ScrollOutputState,
+    edit: bool,
 }
 
 impl UuidPage {
-    pub fn new(focused: bool) -> UuidPage {
+    pub fn new(focused: bool, config: &UuidConfig) -> UuidPage {
         UuidPage {
             focused,
             cur: CurrentStatus {
-                count: 1,
+                hyphen_sel: config.hyphen,
+                case_sel: config.case,
+                ver_sel: config.version,
+                count: config.count,
                 ..Default::default()
             },
             ids: Vec::new(),
@@ -53,10 +80,26 @@ enum PageItems {
     Case,
     Version,
     Count,
+    Namespace,
+    CustomNamespace,
+    Name,
+    NodeId,
+    Payload,
+    View,
     Output,
 }
 
-#[derive(Default)]
+impl PageItems {
+    fn is_input(&self) -> bool {
+        matches!(
+            self,
+            PageItems::CustomNamespace | PageItems::Name | PageItems::NodeId | PageItems::Payload
+        )
+    }
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
 #[zero_indexed_enum]
 enum HyphenItemSelect {
     #[default]
@@ -73,7 +116,8 @@ impl HyphenItemSelect {
     fn_next_prev_mut! {}
 }
 
-#[derive(Default)]
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
 #[zero_indexed_enum]
 enum CaseItemSelect {
     #[default]
@@ -90,33 +134,113 @@ impl CaseItemSelect {
     fn_next_prev_mut! {}
 }
 
-#[derive(Default)]
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
 #[zero_indexed_enum]
 enum VersionItemSelect {
+    V1,
+    V3,
     #[default]
     V4,
+    V5,
+    V6,
+    V7,
+    V8,
 }
 
 impl VersionItemSelect {
     fn_str_map! {
+        VersionItemSelect::V1 => "Version 1",
+        VersionItemSelect::V3 => "Version 3",
         VersionItemSelect::V4 => "Version 4",
+        VersionItemSelect::V5 => "Version 5",
+        VersionItemSelect::V6 => "Version 6",
+        VersionItemSelect::V7 => "Version 7",
+        VersionItemSelect::V8 => "Version 8",
+    }
+
+    fn_next_prev_mut! {}
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[zero_indexed_enum]
+enum ViewItemSelect {
+    #[default]
+    List,
+    Inspect,
+    Convert,
+}
+
+impl ViewItemSelect {
+    fn_str_map! {
+        ViewItemSelect::List => "List",
+        ViewItemSelect::Inspect => "Inspect",
+        ViewItemSelect::Convert => "Convert",
+    }
+
+    fn_next_prev_mut! {}
+}
+
+impl From<VersionItemSelect> for UuidVersion {
+    fn from(ver_sel: VersionItemSelect) -> Self {
+        match ver_sel {
+            VersionItemSelect::V1 => UuidVersion::V1,
+            VersionItemSelect::V3 => UuidVersion::V3,
+            VersionItemSelect::V4 => UuidVersion::V4,
+            VersionItemSelect::V5 => UuidVersion::V5,
+            VersionItemSelect::V6 => UuidVersion::V6,
+            VersionItemSelect::V7 => UuidVersion::V7,
+            VersionItemSelect::V8 => UuidVersion::V8,
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[zero_indexed_enum]
+enum NamespaceItemSelect {
+    #[default]
+    Dns,
+    Url,
+    Oid,
+    X500,
+    Custom,
+}
+
+impl NamespaceItemSelect {
+    fn_str_map! {
+        NamespaceItemSelect::Dns => "Namespace DNS",
+        NamespaceItemSelect::Url => "Namespace URL",
+        NamespaceItemSelect::Oid => "Namespace OID",
+        NamespaceItemSelect::X500 => "Namespace X.500",
+        NamespaceItemSelect::Custom => "Custom namespace",
     }
 
     fn_next_prev_mut! {}
 }
 
 impl Page for UuidPage {
-    fn handle_key(&self, key: ratatui::crossterm::event::KeyEvent) -> Option<Msg> {
-        let msg = match key {
-            key_code_char!('j') | key_code!(KeyCode::Down) => UuidMsg::SelectNextItem,
-            key_code_char!('k') | key_code!(KeyCode::Up) => UuidMsg::SelectPrevItem,
-            key_code_char!('l') | key_code!(KeyCode::Right) => UuidMsg::CurrentItemSelectNext,
-            key_code_char!('h') | key_code!(KeyCode::Left) => UuidMsg::CurrentItemSelectPrev,
-            key_code_char!('e', Ctrl) => UuidMsg::ScrollDown,
-            key_code_char!('y', Ctrl) => UuidMsg::ScrollUp,
-            key_code_char!('y') => UuidMsg::Copy,
-            key_code_char!('p') => UuidMsg::Paste,
-            key_code!(KeyCode::Enter) => UuidMsg::Generate,
+    fn handle_key(&self, key: KeyEvent, keymap: &Keymap) -> Option<Msg> {
+        if self.cur.edit {
+            let msg = match keymap.resolve_input(key) {
+                Some(Action::Cancel) => UuidMsg::EditEnd,
+                _ => UuidMsg::EditKeyEvent(key),
+            };
+            return Some(Msg::Page(PageMsg::Uuid(msg)));
+        }
+
+        let msg = match keymap.resolve(key)? {
+            Action::SelectNextItem => UuidMsg::SelectNextItem,
+            Action::SelectPrevItem => UuidMsg::SelectPrevItem,
+            Action::SelectNextValue => UuidMsg::CurrentItemSelectNext,
+            Action::SelectPrevValue => UuidMsg::CurrentItemSelectPrev,
+            Action::ScrollDown => UuidMsg::ScrollDown,
+            Action::ScrollUp => UuidMsg::ScrollUp,
+            Action::Copy => UuidMsg::Copy,
+            Action::Paste => UuidMsg::Paste,
+            Action::Generate => UuidMsg::Generate,
+            Action::Edit => UuidMsg::EditStart,
             _ => return None,
         };
         Some(Msg::Page(PageMsg::Uuid(msg)))
@@ -144,7 +268,7 @@ impl Page for UuidPage {
                     self.scroll_up();
                 }
                 UuidMsg::Generate => {
-                    self.generate_uuid();
+                    return self.generate_uuid();
                 }
                 UuidMsg::Copy => {
                     return self.copy_to_clipboard();
@@ -152,47 +276,92 @@ impl Page for UuidPage {
                 UuidMsg::Paste => {
                     return self.paste_from_clipboard();
                 }
+                UuidMsg::SelectItem(item) => {
+                    if self.is_visible(item) {
+                        self.cur.item = item;
+                    }
+                }
+                UuidMsg::SetHyphen(hyphen_sel) => {
+                    self.cur.hyphen_sel = hyphen_sel;
+                }
+                UuidMsg::SetCase(case_sel) => {
+                    self.cur.case_sel = case_sel;
+                }
+                UuidMsg::SetVersion(ver_sel) => {
+                    self.cur.ver_sel = ver_sel;
+                    self.ensure_item_visible();
+                }
+                UuidMsg::SetCount(count) => {
+                    self.cur.count = count.clamp(1, COUNT_MAX);
+                }
+                UuidMsg::SetView(view_sel) => {
+                    self.cur.view_sel = view_sel;
+                    self.cur.output_state = ScrollOutputState::default();
+                }
+                UuidMsg::SetNamespace(namespace_sel) => {
+                    self.cur.namespace_sel = namespace_sel;
+                    self.ensure_item_visible();
+                }
+                UuidMsg::EditStart => {
+                    self.edit_start();
+                }
+                UuidMsg::EditEnd => {
+                    self.edit_end();
+                }
+                UuidMsg::EditKeyEvent(key) => {
+                    self.edit(key);
+                }
             }
         }
         None
     }
 
-    fn render(&mut self, f: &mut Frame, area: Rect) {
-        let chunks = vertical![==2, ==2, ==2, ==2, >=0].split(area);
-
-        let hyphen_sel = Select::new(
-            HyphenItemSelect::strings_vec(),
-            self.cur.hyphen_sel.val(),
-            self.cur.item == PageItems::Hyphen,
-            self.focused,
-        );
-        f.render_widget(hyphen_sel, chunks[0]);
-
-        let case_sel = Select::new(
-            CaseItemSelect::strings_vec(),
-            self.cur.case_sel.val(),
-            self.cur.item == PageItems::Case,
-            self.focused,
-        );
-        f.render_widget(case_sel, chunks[1]);
-
-        let version_sel = Select::new(
-            VersionItemSelect::strings_vec(),
-            self.cur.ver_sel.val(),
-            self.cur.item == PageItems::Version,
-            self.focused,
-        );
-        f.render_widget(version_sel, chunks[2]);
-
-        let count_sel = Select::new(
-            (1..=COUNT_MAX).map(|i| format!("{i}")).collect(),
-            self.cur.count - 1,
-            self.cur.item == PageItems::Count,
-            self.focused,
-        );
-        f.render_widget(count_sel, chunks[3]);
-
-        self.render_output(f, chunks[4]);
+    fn render(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let items: Vec<PageItems> = PageItems::vars_vec()
+            .into_iter()
+            .filter(|item| self.is_visible(*item))
+            .collect();
+        let constraints = items.iter().map(|item| match item {
+            PageItems::Output => Constraint::Min(0),
+            item if item.is_input() => Constraint::Length(3),
+            _ => Constraint::Length(2),
+        });
+        let chunks = Layout::vertical(constraints).split(area);
+
+        for (item, chunk) in items.into_iter().zip(chunks.iter()) {
+            match item {
+                PageItems::Hyphen => {
+                    self.render_select(f, *chunk, theme, item, HyphenItemSelect::strings_vec())
+                }
+                PageItems::Case => {
+                    self.render_select(f, *chunk, theme, item, CaseItemSelect::strings_vec())
+                }
+                PageItems::Version => {
+                    self.render_select(f, *chunk, theme, item, VersionItemSelect::strings_vec())
+                }
+                PageItems::Count => {
+                    let counts = (1..=COUNT_MAX).map(|i| format!("{i}")).collect();
+                    self.render_select(f, *chunk, theme, item, counts)
+                }
+                PageItems::Namespace => {
+                    self.render_select(f, *chunk, theme, item, NamespaceItemSelect::strings_vec())
+                }
+                PageItems::CustomNamespace => {
+                    self.render_input(f, *chunk, theme, item, "Namespace UUID")
+                }
+                PageItems::Name => self.render_input(f, *chunk, theme, item, "Name"),
+                PageItems::NodeId => {
+                    self.render_input(f, *chunk, theme, item, "Node ID (random if empty)")
+                }
+                PageItems::Payload => {
+                    self.render_input(f, *chunk, theme, item, "Custom payload (hex)")
+                }
+                PageItems::View => {
+                    self.render_select(f, *chunk, theme, item, ViewItemSelect::strings_vec())
+                }
+                PageItems::Output => self.render_output(f, *chunk, theme),
+            }
+        }
     }
 
     fn focus(&mut self) {
@@ -203,29 +372,169 @@ impl Page for UuidPage {
         self.focused = false;
     }
 
-    fn helps(&self) -> Vec<&str> {
-        let mut helps: Vec<&str> = Vec::new();
-        helps.push("<j/k> Select item");
-        if !matches!(self.cur.item, PageItems::Output) {
-            helps.push("<h/l> Select current item value");
+    fn helps(&self) -> Vec<Help> {
+        let mut helps: Vec<Help> = Vec::new();
+        if self.cur.edit {
+            helps.push(Help::new(&[Action::Cancel], "End edit"));
+            return helps;
+        }
+        helps.push(Help::new(
+            &[Action::SelectNextItem, Action::SelectPrevItem],
+            "Select item",
+        ));
+        if self.cur.item.is_input() {
+            helps.push(Help::new(&[Action::Edit], "Edit"));
+            helps.push(Help::new(&[Action::Paste], "Paste from clipboard"));
+        } else if !matches!(self.cur.item, PageItems::Output) {
+            helps.push(Help::new(
+                &[Action::SelectPrevValue, Action::SelectNextValue],
+                "Select current item value",
+            ));
         }
-        helps.push("<Enter> Generate uuid");
+        helps.push(Help::new(&[Action::Generate], "Generate uuid"));
         if matches!(self.cur.item, PageItems::Output) {
-            helps.push("<C-e/C-y> Scroll down/up");
-            helps.push("<y> Copy to clipboard");
-            helps.push("<p> Paste from clipboard");
+            helps.push(Help::new(
+                &[Action::ScrollDown, Action::ScrollUp],
+                "Scroll down/up",
+            ));
+            helps.push(Help::new(&[Action::Copy], "Copy to clipboard"));
+            helps.push(Help::new(&[Action::Paste], "Paste from clipboard"));
         }
         helps
     }
+
+    fn commands(&self) -> Vec<Command> {
+        let msg = |msg| Msg::Page(PageMsg::Uuid(msg));
+        let mut commands = vec![Command::new("UUID: generate", vec![msg(UuidMsg::Generate)])];
+        for count in [1, 10] {
+            for case_sel in CaseItemSelect::vars_vec() {
+                let label = format!("UUID: generate {count} {}", case_sel.str().to_lowercase());
+                let msgs = vec![
+                    msg(UuidMsg::SetCount(count)),
+                    msg(UuidMsg::SetCase(case_sel)),
+                    msg(UuidMsg::Generate),
+                ];
+                commands.push(Command::new(label, msgs));
+            }
+        }
+        for hyphen_sel in HyphenItemSelect::vars_vec() {
+            let label = format!("UUID: {}", hyphen_sel.str().to_lowercase());
+            commands.push(Command::new(
+                label,
+                vec![msg(UuidMsg::SetHyphen(hyphen_sel))],
+            ));
+        }
+        for ver_sel in VersionItemSelect::vars_vec() {
+            let label = format!("UUID: {}", ver_sel.str().to_lowercase());
+            commands.push(Command::new(label, vec![msg(UuidMsg::SetVersion(ver_sel))]));
+        }
+        if self.is_visible(PageItems::Namespace) {
+            for namespace_sel in NamespaceItemSelect::vars_vec() {
+                let label = format!("UUID: {}", namespace_sel.str());
+                let msgs = vec![msg(UuidMsg::SetNamespace(namespace_sel))];
+                commands.push(Command::new(label, msgs));
+            }
+            let msgs = vec![
+                msg(UuidMsg::SelectItem(PageItems::Name)),
+                msg(UuidMsg::EditStart),
+            ];
+            commands.push(Command::new("UUID: edit name", msgs));
+        }
+        for view_sel in ViewItemSelect::vars_vec() {
+            let label = format!("UUID: view {}", view_sel.str().to_lowercase());
+            commands.push(Command::new(label, vec![msg(UuidMsg::SetView(view_sel))]));
+        }
+        let output = msg(UuidMsg::SelectItem(PageItems::Output));
+        commands.push(Command::new(
+            "UUID: copy output",
+            vec![output.clone(), msg(UuidMsg::Copy)],
+        ));
+        commands.push(Command::new(
+            "UUID: paste from clipboard",
+            vec![output.clone(), msg(UuidMsg::Paste)],
+        ));
+        commands.push(Command::new(
+            "UUID: inspect UUIDs from clipboard",
+            vec![
+                output,
+                msg(UuidMsg::Paste),
+                msg(UuidMsg::SetView(ViewItemSelect::Inspect)),
+            ],
+        ));
+        commands
+    }
+
+    fn save(&self, session: &mut Session) {
+        session.uuid = Some(UuidSession {
+            hyphen: self.cur.hyphen_sel,
+            case: self.cur.case_sel,
+            version: self.cur.ver_sel,
+            count: self.cur.count,
+            namespace: self.cur.namespace_sel,
+            custom_namespace: self.cur.custom_namespace.value().into(),
+            name: self.cur.name.value().into(),
+            node_id: self.cur.node_id.value().into(),
+            payload: self.cur.payload.value().into(),
+            view: self.cur.view_sel,
+        });
+    }
+
+    fn restore(&mut self, session: &Session) {
+        let Some(saved) = &session.uuid else {
+            return;
+        };
+        self.cur.hyphen_sel = saved.hyphen;
+        self.cur.case_sel = saved.case;
+        self.cur.ver_sel = saved.version;
+        self.cur.count = saved.count.clamp(1, COUNT_MAX);
+        self.cur.namespace_sel = saved.namespace;
+        self.cur.custom_namespace = Input::new(saved.custom_namespace.clone());
+        self.cur.name = Input::new(saved.name.clone());
+        self.cur.node_id = Input::new(saved.node_id.clone());
+        self.cur.payload = Input::new(saved.payload.clone());
+        self.cur.view_sel = saved.view;
+    }
 }
 
 impl UuidPage {
     fn select_next_item(&mut self) {
         self.cur.item = self.cur.item.next();
+        while !self.is_visible(self.cur.item) {
+            self.cur.item = self.cur.item.next();
+        }
     }
 
     fn select_prev_item(&mut self) {
         self.cur.item = self.cur.item.prev();
+        while !self.is_visible(self.cur.item) {
+            self.cur.item = self.cur.item.prev();
+        }
+    }
+
+    fn ensure_item_visible(&mut self) {
+        if !self.is_visible(self.cur.item) {
+            self.cur.item = PageItems::Version;
+        }
+    }
+
+    /// Returns whether the item is used by the selected version.
+    fn is_visible(&self, item: PageItems) -> bool {
+        use VersionItemSelect::*;
+        let ver_sel = self.cur.ver_sel;
+        match item {
+            PageItems::Count => !UuidVersion::from(ver_sel).is_deterministic(),
+            PageItems::Namespace | PageItems::Name => matches!(ver_sel, V3 | V5),
+            PageItems::CustomNamespace => {
+                matches!(ver_sel, V3 | V5) && self.cur.namespace_sel == NamespaceItemSelect::Custom
+            }
+            PageItems::NodeId => matches!(ver_sel, V1 | V6),
+            PageItems::Payload => matches!(ver_sel, V8),
+            PageItems::Hyphen
+            | PageItems::Case
+            | PageItems::Version
+            | PageItems::View
+            | PageItems::Output => true,
+        }
     }
 
     fn current_item_select_next(&mut self) {
@@ -239,12 +548,23 @@ impl UuidPage {
             PageItems::Version => {
                 self.cur.ver_sel.next_mut();
             }
+            PageItems::Namespace => {
+                self.cur.namespace_sel.next_mut();
+            }
+            PageItems::View => {
+                self.cur.view_sel.next_mut();
+                self.cur.output_state = ScrollOutputState::default();
+            }
             PageItems::Count => {
                 if self.cur.count < COUNT_MAX {
                     self.cur.count += 1;
                 }
             }
-            PageItems::Output => {}
+            PageItems::CustomNamespace
+            | PageItems::Name
+            | PageItems::NodeId
+            | PageItems::Payload
+            | PageItems::Output => {}
         }
     }
 
@@ -259,12 +579,23 @@ impl UuidPage {
             PageItems::Version => {
                 self.cur.ver_sel.prev_mut();
             }
+            PageItems::Namespace => {
+                self.cur.namespace_sel.prev_mut();
+            }
+            PageItems::View => {
+                self.cur.view_sel.prev_mut();
+                self.cur.output_state = ScrollOutputState::default();
+            }
             PageItems::Count => {
                 if self.cur.count > 1 {
                     self.cur.count -= 1;
                 }
             }
-            PageItems::Output => {}
+            PageItems::CustomNamespace
+            | PageItems::Name
+            | PageItems::NodeId
+            | PageItems::Payload
+            | PageItems::Output => {}
         }
     }
 
@@ -282,8 +613,74 @@ impl UuidPage {
         self.cur.output_state.scroll_up();
     }
 
-    fn generate_uuid(&mut self) {
-        self.ids = (0..self.cur.count).map(|_| Uuid::new_v4()).collect();
+    fn edit_start(&mut self) {
+        if self.cur.item.is_input() {
+            self.cur.edit = true;
+        }
+    }
+
+    fn edit_end(&mut self) {
+        self.cur.edit = false;
+    }
+
+    fn edit(&mut self, key: KeyEvent) {
+        let event = &ratatui::crossterm::event::Event::Key(key);
+        if let Some(input) = self.current_input_mut() {
+            input.handle_event(event);
+        }
+    }
+
+    fn current_input_mut(&mut self) -> Option<&mut Input> {
+        match self.cur.item {
+            PageItems::CustomNamespace => Some(&mut self.cur.custom_namespace),
+            PageItems::Name => Some(&mut self.cur.name),
+            PageItems::NodeId => Some(&mut self.cur.node_id),
+            PageItems::Payload => Some(&mut self.cur.payload),
+            _ => None,
+        }
+    }
+
+    fn generate_uuid(&mut self) -> Option<Msg> {
+        let req = match self.build_request() {
+            Ok(req) => req,
+            Err(e) => return Some(Msg::NotifyWarn(format!("Could not generate UUID: {e}"))),
+        };
+        self.ids = uuid::generate(&req);
+        None
+    }
+
+    fn build_request(&self) -> Result<UuidRequest, uuid::UuidError> {
+        let version: UuidVersion = self.cur.ver_sel.into();
+        let mut req = UuidRequest {
+            version,
+            count: self.cur.count,
+            ..Default::default()
+        };
+        match version {
+            UuidVersion::V3 | UuidVersion::V5 => {
+                req.namespace = match self.cur.namespace_sel {
+                    NamespaceItemSelect::Dns => Namespace::Dns,
+                    NamespaceItemSelect::Url => Namespace::Url,
+                    NamespaceItemSelect::Oid => Namespace::Oid,
+                    NamespaceItemSelect::X500 => Namespace::X500,
+                    NamespaceItemSelect::Custom => {
+                        uuid::parse_namespace(self.cur.custom_namespace.value())?
+                    }
+                };
+                req.name = self.cur.name.value().into();
+            }
+            UuidVersion::V1 | UuidVersion::V6 => {
+                let node_id = self.cur.node_id.value();
+                if !node_id.trim().is_empty() {
+                    req.node_id = Some(uuid::parse_node_id(node_id)?);
+                }
+            }
+            UuidVersion::V8 => {
+                req.payload = uuid::parse_payload(self.cur.payload.value())?;
+            }
+            UuidVersion::V4 | UuidVersion::V7 => {}
+        }
+        Ok(req)
     }
 
     fn copy_to_clipboard(&self) -> Option<Msg> {
@@ -297,22 +694,23 @@ impl UuidPage {
     }
 
     fn paste_from_clipboard(&mut self) -> Option<Msg> {
-        if !matches!(self.cur.item, PageItems::Output) {
+        if !matches!(self.cur.item, PageItems::Output) && !self.cur.item.is_input() {
             return None;
         }
 
-        let text = util::paste_from_clipboard().unwrap();
-        let mut ids: Vec<Uuid> = Vec::new();
-        let mut failure_count = 0;
-        for s in text.lines() {
-            if let Ok(id) = Uuid::parse_str(s) {
-                ids.push(id);
-            } else {
-                failure_count += 1;
-            }
+        let text = match util::paste_from_clipboard() {
+            Ok(text) => text,
+            Err(msg) => return Some(msg),
+        };
+        if let Some(input) = self.current_input_mut() {
+            *input = input.clone().with_value(text.trim_end().into());
+            return None;
         }
-        self.ids = ids;
+        // either format is accepted so that the other can be converted to this one
+        let parsed = id128::parse_lines(&text);
+        self.ids = parsed.ids.into_iter().map(Uuid::from_u128).collect();
 
+        let failure_count = parsed.failure_count;
         if failure_count > 0 {
             let msg = format!("Could not parse {failure_count} lines of string to UUID");
             Some(Msg::NotifyWarn(msg))
@@ -322,31 +720,158 @@ impl UuidPage {
     }
 
     fn format_uuid(&self, id: &Uuid) -> String {
-        let mut buf = Uuid::encode_buffer();
-        let s = match (self.cur.hyphen_sel, self.cur.case_sel) {
-            (HyphenItemSelect::WithHyphen, CaseItemSelect::Lowercase) => {
-                id.hyphenated().encode_lower(&mut buf)
-            }
-            (HyphenItemSelect::WithHyphen, CaseItemSelect::Uppercase) => {
-                id.hyphenated().encode_upper(&mut buf)
-            }
-            (HyphenItemSelect::WithoutHyphen, CaseItemSelect::Lowercase) => {
-                id.simple().encode_lower(&mut buf)
-            }
-            (HyphenItemSelect::WithoutHyphen, CaseItemSelect::Uppercase) => {
-                id.simple().encode_upper(&mut buf)
-            }
+        let format = UuidFormat {
+            hyphenated: self.cur.hyphen_sel == HyphenItemSelect::WithHyphen,
+            uppercase: self.cur.case_sel == CaseItemSelect::Uppercase,
         };
-        s.to_string()
+        uuid::format(id, format)
     }
 
-    fn render_output(&mut self, f: &mut Frame, area: Rect) {
-        let lines: Vec<Line> = self
-            .ids
-            .iter()
-            .map(|id| Line::raw(self.format_uuid(id)))
-            .collect();
-        let output = ScrollOutput::new(lines, self.focused, self.cur.item == PageItems::Output);
+    fn render_select(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        theme: &Theme,
+        item: PageItems,
+        items: Vec<String>,
+    ) {
+        let current = match item {
+            PageItems::Hyphen => self.cur.hyphen_sel.val(),
+            PageItems::Case => self.cur.case_sel.val(),
+            PageItems::Version => self.cur.ver_sel.val(),
+            PageItems::Count => self.cur.count - 1,
+            PageItems::Namespace => self.cur.namespace_sel.val(),
+            PageItems::View => self.cur.view_sel.val(),
+            _ => return,
+        };
+        let select = Select::new(items, current, self.cur.item == item, self.focused, theme);
+        f.render_widget(select, area);
+    }
+
+    fn render_input(&self, f: &mut Frame, area: Rect, theme: &Theme, item: PageItems, title: &str) {
+        let input = match item {
+            PageItems::CustomNamespace => &self.cur.custom_namespace,
+            PageItems::Name => &self.cur.name,
+            PageItems::NodeId => &self.cur.node_id,
+            PageItems::Payload => &self.cur.payload,
+            _ => return,
+        };
+        let input_style = theme.item_style(self.focused, self.cur.item == item);
+
+        let block = Block::bordered()
+            .style(input_style)
+            .title(title)
+            .padding(Padding::horizontal(1));
+        util::render_input(
+            f,
+            area,
+            input,
+            block,
+            self.cur.edit && self.cur.item == item,
+        );
+    }
+
+    fn render_output(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let lines: Vec<Line> = match self.cur.view_sel {
+            ViewItemSelect::List => self
+                .ids
+                .iter()
+                .map(|id| {
+                    Line::from(vec![
+                        Span::raw(self.format_uuid(id)),
+                        Span::styled(format!("  v{}", id.get_version_num()), theme.disabled),
+                    ])
+                })
+                .collect(),
+            ViewItemSelect::Inspect => self
+                .ids
+                .iter()
+                .flat_map(|id| self.inspect_lines(id, theme))
+                .collect(),
+            ViewItemSelect::Convert => self
+                .ids
+                .iter()
+                .flat_map(|id| {
+                    let mut lines = vec![Line::raw(self.format_uuid(id))];
+                    lines.extend(util::conversion_lines(id.as_u128(), theme));
+                    lines.push(Line::default());
+                    lines
+                })
+                .collect(),
+        };
+        let output = ScrollOutput::new(
+            lines,
+            self.focused,
+            self.cur.item == PageItems::Output,
+            theme,
+        );
         f.render_stateful_widget(output, area, &mut self.cur.output_state);
     }
+
+    fn inspect_lines(&self, id: &Uuid, theme: &Theme) -> Vec<Line<'static>> {
+        let info = uuid::inspect(id);
+        let field = |name: &str, value: String| util::field_line(name, value, theme);
+
+        let mut lines = vec![Line::raw(self.format_uuid(id))];
+        if info.is_nil {
+            lines.push(field("Special", "nil UUID".into()));
+        } else if info.is_max {
+            lines.push(field("Special", "max UUID".into()));
+        }
+        let version = info.version;
+        let version_desc = uuid::version_description(version);
+        lines.push(field("Version", format!("{version} ({version_desc})")));
+        lines.push(field(
+            "Variant",
+            uuid::variant_description(info.variant).into(),
+        ));
+        if let Some(timestamp) = info.timestamp {
+            let utc = timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true);
+            let local = timestamp
+                .with_timezone(&Local)
+                .to_rfc3339_opts(SecondsFormat::AutoSi, false);
+            lines.push(field("Time (UTC)", utc));
+            lines.push(field("Time (Local)", local));
+        }
+        if let Some(clock_seq) = info.clock_seq {
+            lines.push(field(
+                "Clock seq",
+                format!("{clock_seq} (0x{clock_seq:04x})"),
+            ));
+        }
+        if let Some(node_id) = info.node_id {
+            let node_id: Vec<String> = node_id.iter().map(|b| format!("{b:02x}")).collect();
+            lines.push(field("Node ID", node_id.join(":")));
+        }
+        lines.push(field("Hex", info.hex));
+        lines.push(field("Decimal", info.decimal));
+        lines.push(field("Base64", info.base64));
+        lines.push(Line::default());
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_render_long_multibyte_name() {
+        let mut page = UuidPage::new(true, &UuidConfig::default());
+        page.cur.ver_sel = VersionItemSelect::V5;
+        page.cur.name = Input::new("日本語".repeat(20));
+        page.cur.item = PageItems::Name;
+        page.cur.edit = true;
+
+        let mut terminal = Terminal::new(TestBackend::new(41, 40)).unwrap();
+        terminal
+            .draw(|f| page.render(f, f.area(), &Theme::default()))
+            .unwrap();
+
+        // the end of the name is shown, with the cursor after it
+        let cursor = terminal.get_cursor_position().unwrap();
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(cursor.x - 2, cursor.y)].symbol(), "語");
+    }
 }
@@ -0,0 +1,209 @@
+use std::cmp::Reverse;
+
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::{Margin, Rect},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListItem},
+    Frame,
+};
+use ratatui_macros::vertical;
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use crate::{
+    keymap::{Action, Help, Keymap},
+    msg::Msg,
+    theme::Theme,
+    util::{fuzzy_match, FuzzyMatch},
+};
+
+const MAX_WIDTH: u16 = 60;
+const MAX_VISIBLE_COMMANDS: usize = 15;
+
+/// An entry of the command palette, executing it processes `msgs` in order.
+#[derive(Debug, Clone)]
+pub struct Command {
+    label: String,
+    msgs: Vec<Msg>,
+}
+
+impl Command {
+    pub fn new(label: impl Into<String>, msgs: Vec<Msg>) -> Command {
+        Command {
+            label: label.into(),
+            msgs,
+        }
+    }
+}
+
+pub struct Palette {
+    commands: Vec<Command>,
+    input: Input,
+    selected: usize,
+}
+
+impl Palette {
+    pub fn new(commands: Vec<Command>) -> Palette {
+        Palette {
+            commands,
+            input: Input::default(),
+            selected: 0,
+        }
+    }
+
+    pub fn handle_key(&self, key: KeyEvent, keymap: &Keymap) -> Option<Msg> {
+        let msg = match keymap.resolve_input(key) {
+            Some(Action::Confirm) => Msg::PaletteExecute,
+            Some(Action::Cancel) => Msg::PaletteClose,
+            Some(Action::SelectNextCandidate) => Msg::PaletteSelectNext,
+            Some(Action::SelectPrevCandidate) => Msg::PaletteSelectPrev,
+            _ => Msg::PaletteKeyEvent(key),
+        };
+        Some(msg)
+    }
+
+    pub fn update(&mut self, msg: Msg) {
+        match msg {
+            Msg::PaletteSelectNext => {
+                let len = self.filtered_commands().len();
+                if self.selected + 1 < len {
+                    self.selected += 1;
+                }
+            }
+            Msg::PaletteSelectPrev => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            Msg::PaletteKeyEvent(key) => {
+                let event = &ratatui::crossterm::event::Event::Key(key);
+                if self.input.handle_event(event).is_some_and(|c| c.value) {
+                    self.selected = 0;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the messages of the selected command.
+    pub fn selected_msgs(&self) -> Option<Vec<Msg>> {
+        self.filtered_commands()
+            .into_iter()
+            .nth(self.selected)
+            .map(|(command, _)| command.msgs.clone())
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let filtered = self.filtered_commands();
+
+        let width = area.width.saturating_sub(4).min(MAX_WIDTH);
+        let list_height = filtered.len().clamp(1, MAX_VISIBLE_COMMANDS) as u16;
+        let height = (list_height + 3).min(area.height.saturating_sub(2));
+        let x = area.x + (area.width - width) / 2;
+        let palette_area = Rect::new(x, area.y + 1, width, height);
+
+        let block = Block::bordered().title(" Commands ").style(theme.focused);
+        let inner_area = block.inner(palette_area).inner(Margin::new(1, 0));
+        f.render_widget(Clear, palette_area);
+        f.render_widget(block, palette_area);
+
+        let chunks = vertical![==1, >=0].split(inner_area);
+
+        let input_line = Line::from(vec![Span::raw("> "), Span::raw(self.input.value())]);
+        f.render_widget(input_line.style(theme.text), chunks[0]);
+        let max_cursor = chunks[0].width.saturating_sub(3);
+        let cursor_x = chunks[0].x + 2 + (self.input.visual_cursor() as u16).min(max_cursor);
+        f.set_cursor_position((cursor_x, chunks[0].y));
+
+        let visible = chunks[1].height as usize;
+        let offset = (self.selected + 1).saturating_sub(visible);
+        let items = filtered
+            .into_iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible)
+            .map(|(i, (command, m))| {
+                let spans: Vec<Span> = command
+                    .label
+                    .chars()
+                    .enumerate()
+                    .map(|(ci, c)| {
+                        if m.indices.contains(&ci) {
+                            Span::styled(c.to_string(), theme.highlight)
+                        } else {
+                            Span::raw(c.to_string())
+                        }
+                    })
+                    .collect();
+                let style = if i == self.selected {
+                    theme.selected
+                } else {
+                    theme.text
+                };
+                ListItem::new(Line::from(spans)).style(style)
+            });
+        f.render_widget(List::new(items), chunks[1]);
+    }
+
+    pub fn helps(&self) -> Vec<Help> {
+        vec![
+            Help::new(
+                &[Action::SelectNextCandidate, Action::SelectPrevCandidate],
+                "Select command",
+            ),
+            Help::new(&[Action::Confirm], "Execute"),
+            Help::new(&[Action::Cancel], "Close"),
+        ]
+    }
+}
+
+impl Palette {
+    /// Returns the commands matching the input, best match first.
+    fn filtered_commands(&self) -> Vec<(&Command, FuzzyMatch)> {
+        let mut commands: Vec<(&Command, FuzzyMatch)> = self
+            .commands
+            .iter()
+            .filter_map(|command| {
+                fuzzy_match(self.input.value(), &command.label).map(|m| (command, m))
+            })
+            .collect();
+        commands.sort_by_key(|(_, m)| Reverse(m.score));
+        commands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::crossterm::event::{KeyCode, KeyModifiers};
+
+    fn type_str(palette: &mut Palette, s: &str) {
+        for c in s.chars() {
+            let key = KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+            palette.update(Msg::PaletteKeyEvent(key));
+        }
+    }
+
+    #[test]
+    fn test_selected_msgs() {
+        let mut palette = Palette::new(vec![
+            Command::new("Switch pane", vec![Msg::SwitchPane]),
+            Command::new("Quit", vec![Msg::Quit]),
+        ]);
+        assert!(matches!(
+            palette.selected_msgs().unwrap()[..],
+            [Msg::SwitchPane]
+        ));
+
+        palette.update(Msg::PaletteSelectNext);
+        palette.update(Msg::PaletteSelectNext);
+        assert!(matches!(palette.selected_msgs().unwrap()[..], [Msg::Quit]));
+
+        type_str(&mut palette, "pa");
+        assert!(matches!(
+            palette.selected_msgs().unwrap()[..],
+            [Msg::SwitchPane]
+        ));
+
+        type_str(&mut palette, "x");
+        assert!(palette.selected_msgs().is_none());
+    }
+}
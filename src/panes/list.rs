@@ -1,37 +1,68 @@
-use std::vec;
-
 use itsuki::zero_indexed_enum;
 use ratatui::{
-    crossterm::event::KeyCode,
+    crossterm::event::{KeyCode, KeyEvent},
     layout::Rect,
-    style::{Color, Style},
+    text::{Line, Span},
     widgets::{Block, BorderType, Borders, List, ListItem},
     Frame,
 };
+use ratatui_macros::vertical;
+use serde::{Deserialize, Serialize};
+use tui_input::{backend::crossterm::EventHandler, Input};
 
-use crate::{fn_str_map, key_code, key_code_char, msg::Msg, panes::pane::Pane};
+use crate::{
+    fn_str_map,
+    keymap::{Action, Help, Keymap},
+    msg::Msg,
+    palette::Command,
+    panes::pane::Pane,
+    theme::Theme,
+    util::{fuzzy_match, FuzzyMatch},
+};
 
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
 #[zero_indexed_enum]
 enum PageType {
+    #[default]
     Uuid,
     Ulid,
     Base64,
+    Encoding,
     Url,
     Hash,
     UnixTime,
     NumberBase,
+    OtherId,
 }
 
 impl PageType {
-    fn select_msg(&self) -> Msg {
+    pub fn select_msg(&self) -> Msg {
         match self {
             PageType::Uuid => Msg::ToolPaneSelectUuidPage,
             PageType::Ulid => Msg::ToolPaneSelectUlidPage,
             PageType::Base64 => Msg::ToolPaneSelectBase64Page,
+            PageType::Encoding => Msg::ToolPaneSelectEncodingPage,
             PageType::Url => Msg::ToolPaneSelectUrlPage,
             PageType::Hash => Msg::ToolPaneSelectHashPage,
             PageType::UnixTime => Msg::ToolPaneSelectUnixTimePage,
             PageType::NumberBase => Msg::ToolPaneSelectNumberBasePage,
+            PageType::OtherId => Msg::ToolPaneSelectOtherIdPage,
+        }
+    }
+
+    /// Other names the page can be found by in the filter.
+    fn aliases(&self) -> &[&str] {
+        match self {
+            PageType::Uuid => &["guid"],
+            PageType::Ulid => &[],
+            PageType::Base64 => &["b64"],
+            PageType::Encoding => &["hex", "base32", "base58", "base85", "ascii85", "z85"],
+            PageType::Url => &["uri", "percent"],
+            PageType::Hash => &["md5", "sha", "blake", "crc", "xxhash", "digest", "checksum"],
+            PageType::UnixTime => &["epoch", "timestamp", "date"],
+            PageType::NumberBase => &["bin", "oct", "dec", "hex", "radix"],
+            PageType::OtherId => &["nanoid", "ksuid", "cuid", "objectid", "snowflake"],
         }
     }
 
@@ -39,33 +70,66 @@ impl PageType {
         PageType::Uuid => "UUID",
         PageType::Ulid => "ULID",
         PageType::Base64 => "Base64",
+        PageType::Encoding => "Encoding",
         PageType::Url => "URL",
         PageType::Hash => "Hash",
         PageType::UnixTime => "Unix time",
         PageType::NumberBase => "Number base",
+        PageType::OtherId => "Other IDs",
     }
 }
 
 pub struct ListPane {
     selected: PageType,
     focused: bool,
+    filter: Option<Input>,
+}
+
+/// A page matching the filter, `label_match` is `None` if it matched by an alias.
+struct FilteredPage {
+    page: PageType,
+    label_match: Option<FuzzyMatch>,
 }
 
 impl ListPane {
-    pub fn new(focused: bool) -> ListPane {
+    pub fn new(focused: bool, selected: PageType) -> ListPane {
         ListPane {
-            selected: PageType::Uuid,
+            selected,
             focused,
+            filter: None,
         }
     }
 }
 
+impl ListPane {
+    pub fn commands(&self) -> Vec<Command> {
+        PageType::vars_vec()
+            .into_iter()
+            .map(|page| {
+                let label = format!("Go to {}", page.str());
+                Command::new(label, vec![Msg::ListPaneSelectPage(page)])
+            })
+            .collect()
+    }
+}
+
 impl Pane for ListPane {
-    fn handle_key(&self, key: ratatui::crossterm::event::KeyEvent) -> Option<Msg> {
-        match key {
-            key_code_char!('j') | key_code!(KeyCode::Down) => Some(Msg::ListPaneSelectNext),
-            key_code_char!('k') | key_code!(KeyCode::Up) => Some(Msg::ListPaneSelectPrev),
-            _ => None,
+    fn handle_key(&self, key: KeyEvent, keymap: &Keymap) -> Option<Msg> {
+        if self.filter.is_some() {
+            let msg = match keymap.resolve_input(key) {
+                Some(Action::Confirm) => Msg::ListPaneFilterConfirm,
+                Some(Action::Cancel) => Msg::ListPaneFilterEnd,
+                _ => Msg::ListPaneFilterKeyEvent(key),
+            };
+            return Some(msg);
+        }
+
+        match keymap.resolve(key) {
+            Some(Action::SelectNextItem) => Some(Msg::ListPaneSelectNext),
+            Some(Action::SelectPrevItem) => Some(Msg::ListPaneSelectPrev),
+            Some(Action::Filter) => Some(Msg::ListPaneFilterStart),
+            Some(_) => None,
+            None => page_by_number(key).map(Msg::ListPaneSelectPage),
         }
     }
 
@@ -79,42 +143,88 @@ impl Pane for ListPane {
                 self.selected = self.selected.prev();
                 return Some(self.selected.select_msg());
             }
+            Msg::ListPaneSelectPage(page) => {
+                self.selected = page;
+                return Some(self.selected.select_msg());
+            }
+            Msg::ListPaneFilterStart => {
+                self.filter = Some(Input::default());
+            }
+            Msg::ListPaneFilterEnd => {
+                self.filter = None;
+            }
+            Msg::ListPaneFilterConfirm => {
+                let first = self.filtered_pages().into_iter().next();
+                self.filter = None;
+                if let Some(FilteredPage { page, .. }) = first {
+                    return Some(Msg::ListPaneSelectPage(page));
+                }
+            }
+            Msg::ListPaneFilterKeyEvent(key) => {
+                if let Some(filter) = &mut self.filter {
+                    filter.handle_event(&ratatui::crossterm::event::Event::Key(key));
+                }
+            }
             _ => {}
         }
         None
     }
 
-    fn render(&mut self, f: &mut Frame, area: Rect) {
-        let items = PageType::strings_vec()
-            .into_iter()
-            .enumerate()
-            .map(|(i, label)| {
-                let item = ListItem::new(format!(" {label} "));
-                if i == self.selected as usize {
-                    let selected_color = if self.focused {
-                        Color::Blue
+    fn render(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .style(theme.pane_style(self.focused));
+        let inner_area = block.inner(area);
+        f.render_widget(block, area);
+
+        let Some(filter) = &self.filter else {
+            let items = PageType::vars_vec().into_iter().map(|page| {
+                let item = ListItem::new(format!(" {} ", page.str()));
+                if page == self.selected {
+                    if self.focused {
+                        item.style(theme.selected)
                     } else {
-                        Color::DarkGray
-                    };
-                    item.style(Style::default().fg(Color::Reset).bg(selected_color))
+                        item.style(theme.selected_unfocused)
+                    }
                 } else {
-                    item.style(Style::default().fg(Color::Reset))
+                    item.style(theme.text)
                 }
             });
-
-        let (border_type, block_style) = if self.focused {
-            (BorderType::Rounded, Style::default().fg(Color::Blue))
-        } else {
-            (BorderType::Rounded, Style::default().fg(Color::DarkGray))
+            f.render_widget(List::new(items), inner_area);
+            return;
         };
-        let list = List::new(items).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(border_type)
-                .style(block_style),
-        );
 
-        f.render_widget(list, area);
+        let chunks = vertical![>=0, ==1].split(inner_area);
+
+        let items = self
+            .filtered_pages()
+            .into_iter()
+            .enumerate()
+            .map(|(i, filtered)| {
+                let label = filtered.page.str();
+                let indices = filtered.label_match.map(|m| m.indices).unwrap_or_default();
+                let mut spans = vec![Span::raw(" ")];
+                spans.extend(label.chars().enumerate().map(|(ci, c)| {
+                    if indices.contains(&ci) {
+                        Span::styled(c.to_string(), theme.highlight)
+                    } else {
+                        Span::raw(c.to_string())
+                    }
+                }));
+                spans.push(Span::raw(" "));
+                // the first item is the one to jump to with Enter
+                let style = if i == 0 { theme.selected } else { theme.text };
+                ListItem::new(Line::from(spans)).style(style)
+            });
+        f.render_widget(List::new(items), chunks[0]);
+
+        let filter_line = Line::from(vec![Span::raw("/"), Span::raw(filter.value())]);
+        f.render_widget(filter_line.style(theme.text), chunks[1]);
+
+        let max_cursor = chunks[1].width.saturating_sub(1);
+        let x = chunks[1].x + 1 + (filter.visual_cursor() as u16).min(max_cursor);
+        f.set_cursor_position((x, chunks[1].y));
     }
 
     fn focus(&mut self) {
@@ -123,9 +233,114 @@ impl Pane for ListPane {
 
     fn unfocus(&mut self) {
         self.focused = false;
+        self.filter = None;
+    }
+
+    fn helps(&self) -> Vec<Help> {
+        if self.filter.is_some() {
+            return vec![
+                Help::new(&[Action::Confirm], "Jump to first match"),
+                Help::new(&[Action::Cancel], "Cancel filter"),
+            ];
+        }
+        vec![
+            Help::new(
+                &[Action::SelectNextItem, Action::SelectPrevItem],
+                "Select item",
+            ),
+            Help::new(&[Action::Filter], "Filter"),
+            Help::new(&[Action::SwitchPane], "Switch pane"),
+        ]
+    }
+}
+
+impl ListPane {
+    /// Returns the pages matching the filter, best match first.
+    fn filtered_pages(&self) -> Vec<FilteredPage> {
+        let pattern = self.filter.as_ref().map(|f| f.value()).unwrap_or_default();
+        filter_pages(pattern)
     }
+}
+
+fn filter_pages(pattern: &str) -> Vec<FilteredPage> {
+    let mut pages: Vec<(i32, FilteredPage)> = PageType::vars_vec()
+        .into_iter()
+        .filter_map(|page| {
+            if let Some(m) = fuzzy_match(pattern, page.str()) {
+                let filtered = FilteredPage {
+                    page,
+                    label_match: Some(m),
+                };
+                let score = filtered.label_match.as_ref().unwrap().score;
+                return Some((score, filtered));
+            }
+            // aliases are matched by prefix, otherwise short patterns match too many pages
+            page.aliases()
+                .iter()
+                .filter(|alias| alias.starts_with(&pattern.to_ascii_lowercase()))
+                .filter_map(|alias| fuzzy_match(pattern, alias))
+                .map(|m| m.score)
+                .max()
+                .map(|score| {
+                    let filtered = FilteredPage {
+                        page,
+                        label_match: None,
+                    };
+                    (score, filtered)
+                })
+        })
+        .collect();
+    pages.sort_by(|(s1, _), (s2, _)| s2.cmp(s1));
+    pages.into_iter().map(|(_, page)| page).collect()
+}
+
+/// Returns the page for the number keys, `1` is the first page.
+fn page_by_number(key: KeyEvent) -> Option<PageType> {
+    if !key.modifiers.is_empty() {
+        return None;
+    }
+    match key.code {
+        KeyCode::Char(c @ '1'..='9') => {
+            let n = c.to_digit(10).unwrap() as usize;
+            PageType::try_from(n - 1).ok()
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
 
-    fn helps(&self) -> Vec<&str> {
-        vec!["<j/k> Select item", "<Tab> Switch pane"]
+    #[rstest]
+    #[case("", vec![PageType::Uuid, PageType::Ulid, PageType::Base64, PageType::Encoding, PageType::Url, PageType::Hash, PageType::UnixTime, PageType::NumberBase, PageType::OtherId])]
+    #[case("sha", vec![PageType::Hash])]
+    #[case("u", vec![PageType::Uuid, PageType::Ulid, PageType::Url, PageType::UnixTime, PageType::NumberBase])]
+    #[case("time", vec![PageType::UnixTime])]
+    #[case("hex", vec![PageType::Encoding, PageType::NumberBase])]
+    #[case("base32", vec![PageType::Encoding])]
+    #[case("ksuid", vec![PageType::OtherId])]
+    #[case("xyz", vec![])]
+    fn test_filter_pages(#[case] pattern: &str, #[case] expected: Vec<PageType>) {
+        let actual: Vec<PageType> = filter_pages(pattern).into_iter().map(|p| p.page).collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_filter_confirm_selects_page() {
+        let mut pane = ListPane::new(true, PageType::Uuid);
+        pane.update(Msg::ListPaneFilterStart);
+        for c in "sha".chars() {
+            let key = KeyEvent::new(
+                KeyCode::Char(c),
+                ratatui::crossterm::event::KeyModifiers::NONE,
+            );
+            pane.update(Msg::ListPaneFilterKeyEvent(key));
+        }
+        let msg = pane.update(Msg::ListPaneFilterConfirm).unwrap();
+        let msg = pane.update(msg).unwrap();
+        assert!(matches!(msg, Msg::ToolPaneSelectHashPage));
+        assert!(pane.filter.is_none());
     }
 }
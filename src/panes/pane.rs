@@ -1,15 +1,19 @@
-use ratatui::{layout::Rect, Frame};
+use ratatui::{crossterm::event::KeyEvent, layout::Rect, Frame};
 
-use crate::msg::Msg;
+use crate::{
+    keymap::{Help, Keymap},
+    msg::Msg,
+    theme::Theme,
+};
 
 pub trait Pane {
-    fn handle_key(&self, key: ratatui::crossterm::event::KeyEvent) -> Option<Msg>;
+    fn handle_key(&self, key: KeyEvent, keymap: &Keymap) -> Option<Msg>;
     fn update(&mut self, msg: Msg) -> Option<Msg>;
 
-    fn render(&mut self, f: &mut Frame, area: Rect);
+    fn render(&mut self, f: &mut Frame, area: Rect, theme: &Theme);
 
     fn focus(&mut self);
     fn unfocus(&mut self);
 
-    fn helps(&self) -> Vec<&str>;
+    fn helps(&self) -> Vec<Help>;
 }
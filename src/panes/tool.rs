@@ -1,97 +1,189 @@
 use ratatui::{
+    crossterm::event::KeyEvent,
     layout::{Margin, Rect},
-    style::{Color, Style},
     widgets::{Block, BorderType, Borders},
     Frame,
 };
 
 use crate::{
+    config::Config,
+    keymap::{Action, Help, Keymap},
     msg::Msg,
     pages::{
-        base64::Base64Page, hash::HashPage, number::NumberBasePage, page::Page, ulid::UlidPage,
-        unixtime::UnixTimePage, url::UrlPage, uuid::UuidPage,
+        base64::Base64Page, encoding::EncodingPage, hash::HashPage, number::NumberBasePage,
+        other_id::OtherIdPage, page::Page, ulid::UlidPage, unixtime::UnixTimePage, url::UrlPage,
+        uuid::UuidPage,
     },
-    panes::pane::Pane,
+    palette::Command,
+    panes::{list::PageType, pane::Pane},
+    session::Session,
+    theme::Theme,
 };
 
 pub struct ToolPane {
-    page: Box<dyn Page>,
+    /// Pages are created on first selection and kept while switching, indexed by `PageType`.
+    pages: Vec<Option<Box<dyn Page>>>,
+    current: PageType,
     focused: bool,
+    config: Config,
+    /// The session restored on launch, applied to each page when it is first opened.
+    session: Session,
 }
 
 impl ToolPane {
-    pub fn new(focused: bool) -> ToolPane {
-        ToolPane {
-            page: Box::new(UuidPage::new(focused)),
+    pub fn new(
+        focused: bool,
+        initial_page: PageType,
+        config: &Config,
+        session: Session,
+    ) -> ToolPane {
+        let mut pane = ToolPane {
+            pages: PageType::vars_vec().iter().map(|_| None).collect(),
+            current: initial_page,
             focused,
+            config: config.clone(),
+            session,
+        };
+        pane.update(initial_page.select_msg());
+        pane
+    }
+}
+
+impl ToolPane {
+    pub fn commands(&self) -> Vec<Command> {
+        let mut commands = self.page().commands();
+        commands.push(Command::new("Reset page", vec![Msg::ToolPaneResetPage]));
+        commands
+    }
+
+    /// Returns the state of the opened pages, pages not opened keep the restored state.
+    pub fn session(&self) -> Session {
+        let mut session = self.session.clone();
+        for page in self.pages.iter().flatten() {
+            page.save(&mut session);
+        }
+        session.page = Some(self.current);
+        session
+    }
+
+    fn page(&self) -> &dyn Page {
+        self.pages[self.current.val()].as_deref().unwrap()
+    }
+
+    fn page_mut(&mut self) -> &mut dyn Page {
+        self.pages[self.current.val()].as_deref_mut().unwrap()
+    }
+
+    fn select_page(&mut self, page_type: PageType) {
+        self.current = page_type;
+        if self.pages[page_type.val()].is_none() {
+            self.reset_page();
+            if let Some(page) = self.pages[page_type.val()].as_deref_mut() {
+                page.restore(&self.session);
+            }
+            return;
+        }
+        // the page may have been hidden while the focus moved
+        let focused = self.focused;
+        let page = self.page_mut();
+        if focused {
+            page.focus();
+        } else {
+            page.unfocus();
         }
     }
+
+    fn reset_page(&mut self) {
+        let config = &self.config;
+        let page: Box<dyn Page> = match self.current {
+            PageType::Uuid => Box::new(UuidPage::new(self.focused, &config.uuid)),
+            PageType::Ulid => Box::new(UlidPage::new(self.focused, &config.ulid)),
+            PageType::Base64 => Box::new(Base64Page::new(self.focused, &config.base64)),
+            PageType::Encoding => Box::new(EncodingPage::new(self.focused, &config.encoding)),
+            PageType::Url => Box::new(UrlPage::new(self.focused, &config.url)),
+            PageType::Hash => Box::new(HashPage::new(self.focused, &config.hash)),
+            PageType::UnixTime => Box::new(UnixTimePage::new(self.focused, &config.unixtime)),
+            PageType::NumberBase => Box::new(NumberBasePage::new(self.focused, &config.number)),
+            PageType::OtherId => Box::new(OtherIdPage::new(self.focused, &config.other_id)),
+        };
+        self.pages[self.current.val()] = Some(page);
+    }
 }
 
 impl Pane for ToolPane {
-    fn handle_key(&self, key: ratatui::crossterm::event::KeyEvent) -> Option<Msg> {
-        self.page.handle_key(key)
+    fn handle_key(&self, key: KeyEvent, keymap: &Keymap) -> Option<Msg> {
+        let msg = self.page().handle_key(key, keymap);
+        match (msg, keymap.resolve(key)) {
+            (None, Some(Action::ResetPage)) => Some(Msg::ToolPaneResetPage),
+            (msg, _) => msg,
+        }
     }
 
     fn update(&mut self, msg: Msg) -> Option<Msg> {
         match msg {
             Msg::ToolPaneSelectUuidPage => {
-                self.page = Box::new(UuidPage::new(self.focused));
+                self.select_page(PageType::Uuid);
             }
             Msg::ToolPaneSelectUlidPage => {
-                self.page = Box::new(UlidPage::new(self.focused));
+                self.select_page(PageType::Ulid);
             }
             Msg::ToolPaneSelectBase64Page => {
-                self.page = Box::new(Base64Page::new(self.focused));
+                self.select_page(PageType::Base64);
+            }
+            Msg::ToolPaneSelectEncodingPage => {
+                self.select_page(PageType::Encoding);
             }
             Msg::ToolPaneSelectUrlPage => {
-                self.page = Box::new(UrlPage::new(self.focused));
+                self.select_page(PageType::Url);
             }
             Msg::ToolPaneSelectHashPage => {
-                self.page = Box::new(HashPage::new(self.focused));
+                self.select_page(PageType::Hash);
             }
             Msg::ToolPaneSelectUnixTimePage => {
-                self.page = Box::new(UnixTimePage::new(self.focused));
+                self.select_page(PageType::UnixTime);
             }
             Msg::ToolPaneSelectNumberBasePage => {
-                self.page = Box::new(NumberBasePage::new(self.focused));
+                self.select_page(PageType::NumberBase);
+            }
+            Msg::ToolPaneSelectOtherIdPage => {
+                self.select_page(PageType::OtherId);
+            }
+            Msg::ToolPaneResetPage => {
+                self.reset_page();
             }
             Msg::Page(page_msg) => {
-                return self.page.update(page_msg);
+                // a background task of a page may finish after switching to another page
+                let page = self.pages[page_msg.page_type().val()].as_deref_mut()?;
+                return page.update(page_msg);
             }
             _ => {}
         }
         None
     }
 
-    fn render(&mut self, f: &mut Frame, area: Rect) {
-        let (border_type, block_style) = if self.focused {
-            (BorderType::Rounded, Style::default().fg(Color::Blue))
-        } else {
-            (BorderType::Rounded, Style::default().fg(Color::DarkGray))
-        };
+    fn render(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
         let page_block = Block::default()
             .borders(Borders::ALL)
-            .border_type(border_type)
-            .style(block_style);
+            .border_type(BorderType::Rounded)
+            .style(theme.pane_style(self.focused));
 
         f.render_widget(page_block, area);
 
         let page_content_area = area.inner(Margin::new(2, 1));
-        self.page.render(f, page_content_area);
+        self.page_mut().render(f, page_content_area, theme);
     }
 
     fn focus(&mut self) {
         self.focused = true;
-        self.page.focus();
+        self.page_mut().focus();
     }
 
     fn unfocus(&mut self) {
         self.focused = false;
-        self.page.unfocus();
+        self.page_mut().unfocus();
     }
 
-    fn helps(&self) -> Vec<&str> {
-        self.page.helps()
+    fn helps(&self) -> Vec<Help> {
+        self.page().helps()
     }
 }
@@ -0,0 +1,245 @@
+use std::{fmt, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    pages::{base64, encoding, hash, number, other_id, ulid, unixtime, url, uuid},
+    panes::list::PageType,
+};
+
+const APP_DIR_NAME: &str = "btox";
+const SESSION_FILE_NAME: &str = "session.toml";
+
+/// State saved on quit and restored on the next launch.
+/// A page is `None` if it was never opened or is excluded by the config.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub page: Option<PageType>,
+    pub uuid: Option<UuidSession>,
+    pub ulid: Option<UlidSession>,
+    pub base64: Option<Base64Session>,
+    pub encoding: Option<EncodingSession>,
+    pub url: Option<UrlSession>,
+    pub hash: Option<HashSession>,
+    pub unixtime: Option<UnixTimeSession>,
+    pub number: Option<NumberSession>,
+    pub other_id: Option<OtherIdSession>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UuidSession {
+    pub hyphen: uuid::HyphenItemSelect,
+    pub case: uuid::CaseItemSelect,
+    pub version: uuid::VersionItemSelect,
+    pub count: usize,
+    pub namespace: uuid::NamespaceItemSelect,
+    pub custom_namespace: String,
+    pub name: String,
+    pub node_id: String,
+    pub payload: String,
+    pub view: uuid::ViewItemSelect,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UlidSession {
+    pub case: ulid::CaseItemSelect,
+    pub count: usize,
+    pub generation: ulid::GenerationItemSelect,
+    pub timestamp: String,
+    pub view: ulid::ViewItemSelect,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Base64Session {
+    pub mode: base64::EncodeOrDecodeSelect,
+    pub variant: base64::VariantSelect,
+    pub padding: base64::PaddingSelect,
+    pub view: base64::OutputViewSelect,
+    pub input: String,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EncodingSession {
+    pub mode: encoding::EncodeOrDecodeSelect,
+    pub codec: encoding::CodecSelect,
+    pub view: encoding::OutputViewSelect,
+    pub input: String,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UrlSession {
+    pub mode: url::EncodeOrDecodeSelect,
+    pub charset: url::CharsetSelect,
+    pub input: String,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HashSession {
+    pub view: hash::ViewItemSelect,
+    pub algorithm: hash::AlgoItemSelect,
+    pub output_len: usize,
+    pub mode: hash::ModeItemSelect,
+    pub key_encoding: hash::KeyEncodingItemSelect,
+    pub encoding: hash::EncodeItemSelect,
+    pub newline: hash::NewlineItemSelect,
+    pub format: hash::FormatItemSelect,
+    pub input: String,
+    pub expected: String,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UnixTimeSession {
+    pub time_zone: unixtime::TimeZoneItemSelect,
+    pub output_format: String,
+    pub input: String,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NumberSession {
+    pub case: number::CaseItemSelect,
+    /// The number in decimal, the other bases are derived from it.
+    pub input: String,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OtherIdSession {
+    pub kind: other_id::KindItemSelect,
+    pub count: usize,
+    pub nanoid_length: usize,
+    pub nanoid_alphabet: String,
+    pub snowflake_epoch: String,
+    pub snowflake_datacenter: u8,
+    pub snowflake_worker: u8,
+    pub view: other_id::ViewItemSelect,
+}
+
+#[derive(Debug)]
+pub enum SessionError {
+    Read(PathBuf, std::io::Error),
+    Parse(PathBuf, toml::de::Error),
+    Serialize(toml::ser::Error),
+    Write(PathBuf, std::io::Error),
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionError::Read(path, e) => {
+                write!(f, "failed to read session file {}: {e}", path.display())
+            }
+            SessionError::Parse(path, e) => {
+                write!(f, "failed to parse session file {}: {e}", path.display())
+            }
+            SessionError::Serialize(e) => {
+                write!(f, "failed to serialize session: {e}")
+            }
+            SessionError::Write(path, e) => {
+                write!(f, "failed to write session file {}: {e}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+impl Session {
+    /// Loads the session file from the XDG state directory.
+    /// Returns an empty session if the file does not exist.
+    pub fn load() -> Result<Session, SessionError> {
+        let Some(path) = session_file_path() else {
+            return Ok(Session::default());
+        };
+        if !path.exists() {
+            return Ok(Session::default());
+        }
+        let content =
+            std::fs::read_to_string(&path).map_err(|e| SessionError::Read(path.clone(), e))?;
+        toml::from_str(&content).map_err(|e| SessionError::Parse(path, e))
+    }
+
+    pub fn save(&self) -> Result<(), SessionError> {
+        let Some(path) = session_file_path() else {
+            return Ok(());
+        };
+        let content = toml::to_string(self).map_err(SessionError::Serialize)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| SessionError::Write(path.clone(), e))?;
+        }
+        std::fs::write(&path, content).map_err(|e| SessionError::Write(path, e))
+    }
+
+    /// Drops the state of the given pages so that it is neither restored nor saved.
+    pub fn exclude(&mut self, pages: &[PageType]) {
+        for page in pages {
+            match page {
+                PageType::Uuid => self.uuid = None,
+                PageType::Ulid => self.ulid = None,
+                PageType::Base64 => self.base64 = None,
+                PageType::Encoding => self.encoding = None,
+                PageType::Url => self.url = None,
+                PageType::Hash => self.hash = None,
+                PageType::UnixTime => self.unixtime = None,
+                PageType::NumberBase => self.number = None,
+                PageType::OtherId => self.other_id = None,
+            }
+        }
+    }
+}
+
+fn session_file_path() -> Option<PathBuf> {
+    let state_dir = std::env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| dirs::home_dir().map(|home| home.join(".local").join("state")))?;
+    Some(state_dir.join(APP_DIR_NAME).join(SESSION_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_session() {
+        let mut session = Session {
+            page: Some(PageType::UnixTime),
+            hash: Some(HashSession {
+                algorithm: hash::AlgoItemSelect::Sha256,
+                input: "secret".into(),
+                ..Default::default()
+            }),
+            unixtime: Some(UnixTimeSession {
+                output_format: "%Y-%m-%d".into(),
+                input: "1634567890".into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        session.exclude(&[PageType::Hash]);
+
+        let content = toml::to_string(&session).unwrap();
+        let restored: Session = toml::from_str(&content).unwrap();
+        assert_eq!(restored.page, Some(PageType::UnixTime));
+        assert!(restored.hash.is_none());
+        let unixtime = restored.unixtime.unwrap();
+        assert_eq!(unixtime.output_format, "%Y-%m-%d");
+        assert_eq!(unixtime.input, "1634567890");
+    }
+
+    #[test]
+    fn test_deserialize_partial_session() {
+        let session: Session = toml::from_str("page = \"hash\"\n[uuid]\ncount = 5").unwrap();
+        assert_eq!(session.page, Some(PageType::Hash));
+        assert_eq!(session.uuid.unwrap().count, 5);
+        assert!(session.ulid.is_none());
+    }
+}
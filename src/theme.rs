@@ -0,0 +1,191 @@
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+
+use crate::config::ThemeConfig;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Items that are not selected in the focused pane.
+    pub text: Style,
+    /// Borders of the focused pane and the selected item in it.
+    pub focused: Style,
+    /// Unfocused panes and unavailable select values.
+    pub disabled: Style,
+    /// The selected page in the list pane when it is focused.
+    pub selected: Style,
+    /// The selected page in the list pane when it is not focused.
+    pub selected_unfocused: Style,
+    /// Characters matching the filter in the list pane.
+    pub highlight: Style,
+    pub help: Style,
+    pub info: Style,
+    pub warn: Style,
+    pub error: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+impl Theme {
+    /// Builds the theme from the config, or a monochrome theme if `NO_COLOR` is set.
+    pub fn load(config: &ThemeConfig) -> Theme {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        if no_color {
+            Theme::monochrome()
+        } else {
+            Theme::from_config(config)
+        }
+    }
+
+    fn from_config(config: &ThemeConfig) -> Theme {
+        let mut theme = match config.name {
+            ThemeName::Dark => Theme::dark(),
+            ThemeName::Light => Theme::light(),
+            ThemeName::HighContrast => Theme::high_contrast(),
+        };
+        let fg_overrides = [
+            (&mut theme.text, config.text),
+            (&mut theme.focused, config.focused),
+            (&mut theme.disabled, config.disabled),
+            (&mut theme.highlight, config.highlight),
+            (&mut theme.help, config.help),
+            (&mut theme.info, config.info),
+            (&mut theme.warn, config.warn),
+            (&mut theme.error, config.error),
+        ];
+        for (style, color) in fg_overrides {
+            if let Some(color) = color {
+                *style = style.fg(color);
+            }
+        }
+        if let Some(color) = config.selected {
+            theme.selected = theme.selected.bg(color);
+        }
+        theme
+    }
+
+    pub fn dark() -> Theme {
+        Theme {
+            text: Style::default().fg(Color::Reset),
+            focused: Style::default().fg(Color::Blue),
+            disabled: Style::default().fg(Color::DarkGray),
+            selected: Style::default().fg(Color::Reset).bg(Color::Blue),
+            selected_unfocused: Style::default().fg(Color::Reset).bg(Color::DarkGray),
+            highlight: Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            help: Style::default().fg(Color::DarkGray),
+            info: Style::default().fg(Color::Green),
+            warn: Style::default().fg(Color::Yellow),
+            error: Style::default().fg(Color::Red),
+        }
+    }
+
+    pub fn light() -> Theme {
+        Theme {
+            text: Style::default().fg(Color::Reset),
+            focused: Style::default().fg(Color::Indexed(25)),
+            disabled: Style::default().fg(Color::Indexed(245)),
+            selected: Style::default().fg(Color::Black).bg(Color::Indexed(153)),
+            selected_unfocused: Style::default().fg(Color::Black).bg(Color::Indexed(252)),
+            highlight: Style::default()
+                .fg(Color::Indexed(166))
+                .add_modifier(Modifier::BOLD),
+            help: Style::default().fg(Color::Indexed(242)),
+            info: Style::default().fg(Color::Indexed(28)),
+            warn: Style::default().fg(Color::Indexed(130)),
+            error: Style::default().fg(Color::Indexed(160)),
+        }
+    }
+
+    pub fn high_contrast() -> Theme {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        Theme {
+            text: Style::default().fg(Color::White),
+            focused: bold.fg(Color::LightYellow),
+            disabled: Style::default().fg(Color::Gray),
+            selected: bold.fg(Color::Black).bg(Color::LightYellow),
+            selected_unfocused: Style::default().fg(Color::Black).bg(Color::Gray),
+            highlight: bold.fg(Color::LightCyan).add_modifier(Modifier::UNDERLINED),
+            help: Style::default().fg(Color::White),
+            info: bold.fg(Color::LightGreen),
+            warn: bold.fg(Color::LightYellow),
+            error: bold.fg(Color::LightRed),
+        }
+    }
+
+    /// Distinguishes states only by text attributes, see https://no-color.org
+    pub fn monochrome() -> Theme {
+        let plain = Style::default();
+        Theme {
+            text: plain,
+            focused: plain.add_modifier(Modifier::BOLD),
+            disabled: plain.add_modifier(Modifier::DIM),
+            selected: plain.add_modifier(Modifier::REVERSED),
+            selected_unfocused: plain.add_modifier(Modifier::UNDERLINED),
+            highlight: plain.add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            help: plain.add_modifier(Modifier::DIM),
+            info: plain,
+            warn: plain,
+            error: plain,
+        }
+    }
+
+    /// Returns the style of a pane border.
+    pub fn pane_style(&self, focused: bool) -> Style {
+        if focused {
+            self.focused
+        } else {
+            self.disabled
+        }
+    }
+
+    /// Returns the style of an item in a page, `selected` is whether the cursor is on the item.
+    pub fn item_style(&self, focused: bool, selected: bool) -> Style {
+        if !focused {
+            self.disabled
+        } else if selected {
+            self.focused
+        } else {
+            self.text
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_config() {
+        let config: ThemeConfig = toml::from_str(
+            r##"
+            name = "light"
+            focused = "magenta"
+            selected = "#ffd700"
+            "##,
+        )
+        .unwrap();
+        let theme = Theme::from_config(&config);
+        let light = Theme::light();
+        assert_eq!(theme.focused, light.focused.fg(Color::Magenta));
+        assert_eq!(
+            theme.selected,
+            light.selected.bg(Color::Rgb(0xff, 0xd7, 0x00))
+        );
+        assert_eq!(theme.text, light.text);
+        assert_eq!(theme.error, light.error);
+    }
+}
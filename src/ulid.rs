@@ -0,0 +1,174 @@
+use std::{fmt, time::SystemTime};
+
+use chrono::{DateTime, Utc};
+use ulid::{Generator, Ulid};
+
+use crate::{
+    unixtime::{self, ParsedInput},
+    ParsedLines,
+};
+
+/// Largest timestamp a ULID can hold, 48 bits of milliseconds.
+const MAX_TIMESTAMP_MS: i64 = (1 << 48) - 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UlidFormat {
+    pub uppercase: bool,
+}
+
+impl Default for UlidFormat {
+    fn default() -> Self {
+        UlidFormat { uppercase: true }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UlidError {
+    InvalidTimestamp,
+    /// The random part overflowed while generating monotonic ULIDs in the same millisecond.
+    Overflow,
+}
+
+impl fmt::Display for UlidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UlidError::InvalidTimestamp => write!(f, "invalid timestamp"),
+            UlidError::Overflow => write!(f, "random part overflowed in the same millisecond"),
+        }
+    }
+}
+
+impl std::error::Error for UlidError {}
+
+#[derive(Debug, Default, Clone)]
+pub struct UlidRequest {
+    pub count: usize,
+    /// Increments the random part within the same millisecond so that the IDs are strictly ordered.
+    pub monotonic: bool,
+    /// Timestamp of the IDs, the current time if `None`.
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+pub fn generate(req: &UlidRequest) -> Result<Vec<Ulid>, UlidError> {
+    if let Some(timestamp) = req.timestamp {
+        if !(0..=MAX_TIMESTAMP_MS).contains(&timestamp.timestamp_millis()) {
+            return Err(UlidError::InvalidTimestamp);
+        }
+    }
+    let datetime = || req.timestamp.map_or_else(SystemTime::now, SystemTime::from);
+    if req.monotonic {
+        let mut generator = Generator::new();
+        (0..req.count)
+            .map(|_| {
+                generator
+                    .generate_from_datetime(datetime())
+                    .map_err(|_| UlidError::Overflow)
+            })
+            .collect()
+    } else {
+        Ok((0..req.count)
+            .map(|_| Ulid::from_datetime(datetime()))
+            .collect())
+    }
+}
+
+/// Parses a Unix timestamp (seconds to nanoseconds) or an RFC 3339 datetime.
+pub fn parse_timestamp(s: &str) -> Result<DateTime<Utc>, UlidError> {
+    match unixtime::parse(s.trim()) {
+        Ok(ParsedInput::UnixTimestamp(dt)) => Ok(dt.datetime),
+        Ok(ParsedInput::Datetime(dt)) => Ok(dt),
+        Err(_) => Err(UlidError::InvalidTimestamp),
+    }
+}
+
+/// Fields decoded from a ULID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UlidInfo {
+    pub timestamp: DateTime<Utc>,
+    pub timestamp_ms: u64,
+    /// The 80-bit random part.
+    pub randomness: u128,
+}
+
+impl UlidInfo {
+    pub fn randomness_hex(&self) -> String {
+        format!("{:020x}", self.randomness)
+    }
+}
+
+pub fn inspect(id: &Ulid) -> UlidInfo {
+    let timestamp_ms = id.timestamp_ms();
+    UlidInfo {
+        timestamp: DateTime::from_timestamp_millis(timestamp_ms as i64).unwrap_or_default(),
+        timestamp_ms,
+        randomness: id.random(),
+    }
+}
+
+pub fn format(id: &Ulid, format: UlidFormat) -> String {
+    if format.uppercase {
+        id.to_string()
+    } else {
+        id.to_string().to_lowercase()
+    }
+}
+
+pub fn parse_lines(text: &str) -> ParsedLines<Ulid> {
+    let mut ids = Vec::new();
+    let mut failure_count = 0;
+    for s in text.lines() {
+        if let Ok(id) = Ulid::from_string(s) {
+            ids.push(id);
+        } else {
+            failure_count += 1;
+        }
+    }
+    ParsedLines { ids, failure_count }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_monotonic() {
+        let timestamp = DateTime::from_timestamp_millis(1_700_000_000_000);
+        let req = UlidRequest {
+            count: 100,
+            monotonic: true,
+            timestamp,
+        };
+        let ids = generate(&req).unwrap();
+        assert_eq!(ids.len(), 100);
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+        assert!(ids.iter().all(|id| id.timestamp_ms() == 1_700_000_000_000));
+    }
+
+    #[test]
+    fn test_generate_invalid_timestamp() {
+        let req = UlidRequest {
+            count: 1,
+            timestamp: DateTime::from_timestamp(-1, 0),
+            ..Default::default()
+        };
+        assert_eq!(generate(&req), Err(UlidError::InvalidTimestamp));
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        let expected = DateTime::from_timestamp_millis(1_634_567_890_123).unwrap();
+        assert_eq!(parse_timestamp("1634567890123"), Ok(expected));
+        assert_eq!(parse_timestamp("2021-10-18T14:38:10.123Z"), Ok(expected));
+        assert_eq!(parse_timestamp("foo"), Err(UlidError::InvalidTimestamp));
+    }
+
+    #[test]
+    fn test_inspect() {
+        let id = Ulid::from_string("01ARZ3NDEKTSV4RRFFQ69G5FAV").unwrap();
+        let info = inspect(&id);
+        assert_eq!(info.timestamp_ms, 1_469_922_850_259);
+        assert_eq!(info.timestamp.to_rfc3339(), "2016-07-30T23:54:10.259+00:00");
+        assert_eq!(info.randomness, id.random());
+        assert_eq!(info.randomness_hex().len(), 20);
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt;
+
+use chrono::{DateTime, Local, Utc};
+
+pub const DEFAULT_OUTPUT_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f%:z";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TimeZone {
+    #[default]
+    Utc,
+    Local,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Second,
+    Milli,
+    Micro,
+    Nano,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateTimeWithResolution {
+    pub datetime: DateTime<Utc>,
+    pub resolution: Resolution,
+}
+
+/// Interpretation of the input string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParsedInput {
+    UnixTimestamp(DateTimeWithResolution),
+    Datetime(DateTime<Utc>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnixTimeError {
+    InvalidInput,
+    InvalidFormat,
+}
+
+impl fmt::Display for UnixTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnixTimeError::InvalidInput => write!(f, "invalid input"),
+            UnixTimeError::InvalidFormat => write!(f, "invalid format"),
+        }
+    }
+}
+
+impl std::error::Error for UnixTimeError {}
+
+#[derive(Debug, Clone)]
+pub struct UnixTimeRequest<'a> {
+    pub input: &'a str,
+    pub time_zone: TimeZone,
+    pub output_format: &'a str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnixTimeResponse {
+    pub input: ParsedInput,
+    pub output: String,
+}
+
+/// Converts a Unix timestamp to a formatted datetime, or a datetime to a Unix timestamp (seconds).
+pub fn convert(req: &UnixTimeRequest) -> Result<UnixTimeResponse, UnixTimeError> {
+    let input = parse(req.input)?;
+    let output = match &input {
+        ParsedInput::UnixTimestamp(dt) => format(&dt.datetime, req.time_zone, req.output_format)?,
+        ParsedInput::Datetime(dt) => dt.timestamp().to_string(),
+    };
+    Ok(UnixTimeResponse { input, output })
+}
+
+pub fn parse(s: &str) -> Result<ParsedInput, UnixTimeError> {
+    if let Some(dt) = parse_as_unix_timestamp(s) {
+        Ok(ParsedInput::UnixTimestamp(dt))
+    } else if let Some(dt) = parse_as_datetime(s) {
+        Ok(ParsedInput::Datetime(dt))
+    } else {
+        Err(UnixTimeError::InvalidInput)
+    }
+}
+
+pub fn format(
+    datetime: &DateTime<Utc>,
+    time_zone: TimeZone,
+    output_format: &str,
+) -> Result<String, UnixTimeError> {
+    if !is_valid_datetime_format(output_format) {
+        return Err(UnixTimeError::InvalidFormat);
+    }
+    let s = match time_zone {
+        TimeZone::Utc => datetime.with_timezone(&Utc).format(output_format),
+        TimeZone::Local => datetime.with_timezone(&Local).format(output_format),
+    };
+    Ok(s.to_string())
+}
+
+pub fn parse_as_unix_timestamp(s: &str) -> Option<DateTimeWithResolution> {
+    s.parse::<u128>().ok().and_then(to_timestamp)
+}
+
+pub fn to_timestamp(t: u128) -> Option<DateTimeWithResolution> {
+    if t < 1_000_000_000_000 {
+        // seconds
+        DateTime::from_timestamp(t as i64, 0).map(|datetime| DateTimeWithResolution {
+            datetime,
+            resolution: Resolution::Second,
+        })
+    } else if t < 1_000_000_000_000_000 {
+        // millis
+        let sec = t / 1_000;
+        let millis = t % 1_000;
+        DateTime::from_timestamp(sec as i64, millis as u32 * 1_000_000).map(|datetime| {
+            DateTimeWithResolution {
+                datetime,
+                resolution: Resolution::Milli,
+            }
+        })
+    } else if t < 1_000_000_000_000_000_000 {
+        // micros
+        let sec = t / 1_000_000;
+        let micros = t % 1_000_000;
+        DateTime::from_timestamp(sec as i64, micros as u32 * 1_000).map(|datetime| {
+            DateTimeWithResolution {
+                datetime,
+                resolution: Resolution::Micro,
+            }
+        })
+    } else if t < 1_000_000_000_000_000_000_000 {
+        // nanos
+        let sec = t / 1_000_000_000;
+        let nanos = t % 1_000_000_000;
+        DateTime::from_timestamp(sec as i64, nanos as u32).map(|datetime| DateTimeWithResolution {
+            datetime,
+            resolution: Resolution::Nano,
+        })
+    } else {
+        // too large
+        None
+    }
+}
+
+pub fn parse_as_datetime(s: &str) -> Option<DateTime<Utc>> {
+    s.parse::<DateTime<Utc>>().ok()
+}
+
+pub fn is_valid_datetime_format(s: &str) -> bool {
+    // https://github.com/chronotope/chrono/issues/47#issuecomment-320471394
+    chrono::format::StrftimeItems::new(s).all(|item| item != chrono::format::Item::Error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_timestamp() {
+        assert_eq!(
+            to_timestamp(1_634_567_890).map(|d| d.datetime),
+            parse_from_rfc3339("2021-10-18T14:38:10Z")
+        );
+        assert_eq!(
+            to_timestamp(1_634_567_890_123).map(|d| d.datetime),
+            parse_from_rfc3339("2021-10-18T14:38:10.123Z")
+        );
+        assert_eq!(
+            to_timestamp(1_634_567_890_123_456).map(|d| d.datetime),
+            parse_from_rfc3339("2021-10-18T14:38:10.123456Z")
+        );
+        assert_eq!(
+            to_timestamp(1_634_567_890_123_456_789).map(|d| d.datetime),
+            parse_from_rfc3339("2021-10-18T14:38:10.123456789Z")
+        );
+        assert_eq!(
+            to_timestamp(1_634_567).map(|d| d.datetime),
+            parse_from_rfc3339("1970-01-19T22:02:47.000Z")
+        );
+        assert_eq!(
+            to_timestamp(1_634_567_890_123_456_789_000).map(|d| d.datetime),
+            None
+        );
+    }
+
+    fn parse_from_rfc3339(s: &str) -> Option<DateTime<Utc>> {
+        Some(DateTime::parse_from_rfc3339(s).unwrap().to_utc())
+    }
+
+    #[test]
+    fn test_is_valid_datetime_format() {
+        assert!(is_valid_datetime_format("%Y-%m-%dT%H:%M:%S%.f%:z"));
+        assert!(is_valid_datetime_format("%Y-%m-%d"));
+        assert!(!is_valid_datetime_format("%Y-%m-%dT%H:%M:%S%.f%:z%"));
+        assert!(!is_valid_datetime_format("%"));
+    }
+
+    #[test]
+    fn test_convert() {
+        let req = UnixTimeRequest {
+            input: "1634567890123",
+            time_zone: TimeZone::Utc,
+            output_format: DEFAULT_OUTPUT_FORMAT,
+        };
+        let res = convert(&req).unwrap();
+        assert_eq!(res.output, "2021-10-18T14:38:10.123+00:00");
+
+        let req = UnixTimeRequest {
+            input: "2021-10-18T14:38:10Z",
+            ..req
+        };
+        assert_eq!(convert(&req).unwrap().output, "1634567890");
+
+        let req = UnixTimeRequest {
+            input: "1634567890",
+            output_format: "%",
+            ..req
+        };
+        assert_eq!(convert(&req), Err(UnixTimeError::InvalidFormat));
+
+        let req = UnixTimeRequest { input: "x", ..req };
+        assert_eq!(convert(&req), Err(UnixTimeError::InvalidInput));
+    }
+}
@@ -0,0 +1,86 @@
+use std::fmt;
+
+use percent_encoding::{percent_decode_str, percent_encode, NON_ALPHANUMERIC};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Encode,
+    Decode,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+    #[default]
+    Utf8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlError {
+    InvalidUtf8,
+}
+
+impl fmt::Display for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrlError::InvalidUtf8 => write!(f, "invalid UTF-8 sequence"),
+        }
+    }
+}
+
+impl std::error::Error for UrlError {}
+
+#[derive(Debug, Clone)]
+pub struct UrlRequest<'a> {
+    pub input: &'a str,
+    pub mode: Mode,
+    pub charset: Charset,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlResponse {
+    pub output: String,
+}
+
+pub fn calculate(req: &UrlRequest) -> Result<UrlResponse, UrlError> {
+    let output = match req.mode {
+        Mode::Encode => match req.charset {
+            Charset::Utf8 => encode(req.input.as_bytes()),
+        },
+        Mode::Decode => percent_decode_str(req.input)
+            .decode_utf8()
+            .map_err(|_| UrlError::InvalidUtf8)?
+            .to_string(),
+    };
+    Ok(UrlResponse { output })
+}
+
+/// Percent-encodes bytes that are not necessarily text, e.g. the contents of a file.
+pub fn encode(bytes: &[u8]) -> String {
+    percent_encode(bytes, NON_ALPHANUMERIC).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case("a b/c", Mode::Encode, Ok("a%20b%2Fc"))]
+    #[case("%E3%81%82", Mode::Decode, Ok("あ"))]
+    #[case("%FF", Mode::Decode, Err(UrlError::InvalidUtf8))]
+    #[case("\u{ff}", Mode::Encode, Ok("%C3%BF"))]
+    fn test_calculate(
+        #[case] input: &str,
+        #[case] mode: Mode,
+        #[case] expected: Result<&str, UrlError>,
+    ) {
+        let req = UrlRequest {
+            input,
+            mode,
+            charset: Charset::Utf8,
+        };
+        let actual = calculate(&req).map(|res| res.output);
+        assert_eq!(actual, expected.map(String::from));
+    }
+}
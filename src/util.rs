@@ -20,3 +20,61 @@ pub fn group_strs_to_fit_width<'a>(
     groups.push(current_group);
     groups
 }
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i32,
+    /// Char indices of `text` that matched the pattern.
+    pub indices: Vec<usize>,
+}
+
+/// Matches `pattern` as a case-insensitive subsequence of `text`.
+/// Consecutive matches and matches at the start of a word score higher.
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<FuzzyMatch> {
+    let text: Vec<char> = text.chars().collect();
+    let mut score = 0;
+    let mut indices: Vec<usize> = Vec::new();
+    let mut start = 0;
+    for p in pattern.chars() {
+        let i = (start..text.len()).find(|&i| text[i].eq_ignore_ascii_case(&p))?;
+        score += 1;
+        if indices.last().is_some_and(|&last| last + 1 == i) {
+            score += 2;
+        }
+        if i == 0 || matches!(text[i - 1], ' ' | '-' | '_') {
+            score += 3;
+        }
+        indices.push(i);
+        start = i + 1;
+    }
+    Some(FuzzyMatch { score, indices })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case("", "Hash", Some(vec![]))]
+    #[case("h", "Hash", Some(vec![0]))]
+    #[case("ut", "Unix time", Some(vec![0, 5]))]
+    #[case("B64", "Base64", Some(vec![0, 4, 5]))]
+    #[case("sha", "Hash", None)]
+    #[case("urll", "URL", None)]
+    fn test_fuzzy_match(
+        #[case] pattern: &str,
+        #[case] text: &str,
+        #[case] expected: Option<Vec<usize>>,
+    ) {
+        let actual = fuzzy_match(pattern, text).map(|m| m.indices);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_fuzzy_match_score() {
+        let prefix = fuzzy_match("num", "Number base").unwrap();
+        let scattered = fuzzy_match("nbe", "Number base").unwrap();
+        assert!(prefix.score > scattered.score);
+    }
+}
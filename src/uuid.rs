@@ -0,0 +1,397 @@
+use std::fmt;
+
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{DateTime, Utc};
+use uuid::{Uuid, Variant};
+
+use crate::ParsedLines;
+
+/// UUID versions of RFC 9562, v2 (DCE security) is not supported.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum UuidVersion {
+    /// Gregorian timestamp and node ID.
+    V1,
+    /// MD5 of a namespace and a name.
+    V3,
+    #[default]
+    V4,
+    /// SHA-1 of a namespace and a name.
+    V5,
+    /// Reordered v1 so that IDs sort by time.
+    V6,
+    /// Unix timestamp in milliseconds and random bits.
+    V7,
+    /// Custom payload.
+    V8,
+}
+
+impl UuidVersion {
+    /// Returns whether the same request always generates the same UUID.
+    pub fn is_deterministic(&self) -> bool {
+        matches!(self, UuidVersion::V3 | UuidVersion::V5 | UuidVersion::V8)
+    }
+}
+
+/// Namespace of v3 and v5.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Namespace {
+    #[default]
+    Dns,
+    Url,
+    Oid,
+    X500,
+    Custom(Uuid),
+}
+
+impl Namespace {
+    pub fn uuid(&self) -> Uuid {
+        match self {
+            Namespace::Dns => Uuid::NAMESPACE_DNS,
+            Namespace::Url => Uuid::NAMESPACE_URL,
+            Namespace::Oid => Uuid::NAMESPACE_OID,
+            Namespace::X500 => Uuid::NAMESPACE_X500,
+            Namespace::Custom(id) => *id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UuidError {
+    InvalidNamespace,
+    InvalidNodeId,
+    InvalidPayload,
+}
+
+impl fmt::Display for UuidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UuidError::InvalidNamespace => write!(f, "invalid namespace UUID"),
+            UuidError::InvalidNodeId => write!(f, "node ID must be 6 bytes of hex"),
+            UuidError::InvalidPayload => write!(f, "payload must be 16 bytes of hex"),
+        }
+    }
+}
+
+impl std::error::Error for UuidError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UuidFormat {
+    pub hyphenated: bool,
+    pub uppercase: bool,
+}
+
+impl Default for UuidFormat {
+    fn default() -> Self {
+        UuidFormat {
+            hyphenated: true,
+            uppercase: false,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct UuidRequest {
+    pub version: UuidVersion,
+    /// Number of UUIDs, deterministic versions generate only one.
+    pub count: usize,
+    /// Namespace of v3 and v5.
+    pub namespace: Namespace,
+    /// Name of v3 and v5.
+    pub name: String,
+    /// Node ID of v1 and v6, random for each UUID if `None`.
+    pub node_id: Option<[u8; 6]>,
+    /// Payload of v8, the version and variant bits are overwritten.
+    pub payload: [u8; 16],
+}
+
+pub fn generate(req: &UuidRequest) -> Vec<Uuid> {
+    let count = if req.version.is_deterministic() {
+        req.count.min(1)
+    } else {
+        req.count
+    };
+    (0..count)
+        .map(|_| match req.version {
+            UuidVersion::V1 => Uuid::now_v1(&req.node_id.unwrap_or_else(random_node_id)),
+            UuidVersion::V3 => Uuid::new_v3(&req.namespace.uuid(), req.name.as_bytes()),
+            UuidVersion::V4 => Uuid::new_v4(),
+            UuidVersion::V5 => Uuid::new_v5(&req.namespace.uuid(), req.name.as_bytes()),
+            UuidVersion::V6 => Uuid::now_v6(&req.node_id.unwrap_or_else(random_node_id)),
+            UuidVersion::V7 => Uuid::now_v7(),
+            UuidVersion::V8 => Uuid::new_v8(req.payload),
+        })
+        .collect()
+}
+
+/// Returns a random node ID with the multicast bit set, as RFC 9562 recommends
+/// instead of a MAC address.
+fn random_node_id() -> [u8; 6] {
+    let mut node_id = [0; 6];
+    node_id.copy_from_slice(&Uuid::new_v4().as_bytes()[..6]);
+    node_id[0] |= 0x01;
+    node_id
+}
+
+pub fn parse_namespace(s: &str) -> Result<Namespace, UuidError> {
+    Uuid::parse_str(s.trim())
+        .map(Namespace::Custom)
+        .map_err(|_| UuidError::InvalidNamespace)
+}
+
+/// Parses a node ID such as `01:23:45:67:89:ab`, `01-23-45-67-89-ab` or `0123456789ab`.
+pub fn parse_node_id(s: &str) -> Result<[u8; 6], UuidError> {
+    parse_hex_bytes(s).ok_or(UuidError::InvalidNodeId)
+}
+
+/// Parses a v8 payload of 32 hex digits, hyphens are allowed as in a UUID.
+pub fn parse_payload(s: &str) -> Result<[u8; 16], UuidError> {
+    parse_hex_bytes(s).ok_or(UuidError::InvalidPayload)
+}
+
+fn parse_hex_bytes<const N: usize>(s: &str) -> Option<[u8; N]> {
+    let hex: String = s
+        .trim()
+        .chars()
+        .filter(|c| !matches!(c, ':' | '-'))
+        .collect();
+    let mut buf = [0; N];
+    let decoded = base16ct::mixed::decode(&hex, &mut buf).ok()?;
+    (decoded.len() == N).then_some(buf)
+}
+
+pub fn format(id: &Uuid, format: UuidFormat) -> String {
+    let mut buf = Uuid::encode_buffer();
+    let s = match (format.hyphenated, format.uppercase) {
+        (true, false) => id.hyphenated().encode_lower(&mut buf),
+        (true, true) => id.hyphenated().encode_upper(&mut buf),
+        (false, false) => id.simple().encode_lower(&mut buf),
+        (false, true) => id.simple().encode_upper(&mut buf),
+    };
+    s.to_string()
+}
+
+/// Fields decoded from a UUID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UuidInfo {
+    /// Version number, only meaningful if `variant` is RFC 9562.
+    pub version: usize,
+    pub variant: Variant,
+    /// Embedded timestamp of v1, v6 and v7.
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Clock sequence of v1 and v6.
+    pub clock_seq: Option<u16>,
+    /// Node ID of v1 and v6.
+    pub node_id: Option<[u8; 6]>,
+    pub hex: String,
+    pub decimal: String,
+    pub base64: String,
+    pub is_nil: bool,
+    pub is_max: bool,
+}
+
+pub fn inspect(id: &Uuid) -> UuidInfo {
+    let timestamp = id.get_timestamp();
+    let clock_seq = id
+        .get_node_id()
+        .and(timestamp)
+        .map(|ts| ts.to_gregorian().1);
+    let timestamp = timestamp.and_then(|ts| {
+        let (secs, nanos) = ts.to_unix();
+        DateTime::from_timestamp(secs as i64, nanos)
+    });
+    UuidInfo {
+        version: id.get_version_num(),
+        variant: id.get_variant(),
+        timestamp,
+        clock_seq,
+        node_id: id.get_node_id(),
+        hex: format!("{:032x}", id.as_u128()),
+        decimal: id.as_u128().to_string(),
+        base64: general_purpose::STANDARD.encode(id.as_bytes()),
+        is_nil: id.is_nil(),
+        is_max: id.is_max(),
+    }
+}
+
+/// Returns a short description of the version, such as `random` for v4.
+pub fn version_description(version: usize) -> &'static str {
+    match version {
+        1 => "Gregorian time-based",
+        2 => "DCE security",
+        3 => "name-based, MD5",
+        4 => "random",
+        5 => "name-based, SHA-1",
+        6 => "reordered Gregorian time-based",
+        7 => "Unix time-based",
+        8 => "custom",
+        _ => "unknown",
+    }
+}
+
+pub fn variant_description(variant: Variant) -> &'static str {
+    match variant {
+        Variant::NCS => "NCS (reserved)",
+        Variant::RFC4122 => "RFC 9562",
+        Variant::Microsoft => "Microsoft (reserved)",
+        _ => "future (reserved)",
+    }
+}
+
+pub fn parse_lines(text: &str) -> ParsedLines<Uuid> {
+    let mut ids = Vec::new();
+    let mut failure_count = 0;
+    for s in text.lines() {
+        if let Ok(id) = Uuid::parse_str(s) {
+            ids.push(id);
+        } else {
+            failure_count += 1;
+        }
+    }
+    ParsedLines { ids, failure_count }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(true, false, "67e55044-10b1-426f-9247-bb680e5fe0c8")]
+    #[case(true, true, "67E55044-10B1-426F-9247-BB680E5FE0C8")]
+    #[case(false, false, "67e5504410b1426f9247bb680e5fe0c8")]
+    #[case(false, true, "67E5504410B1426F9247BB680E5FE0C8")]
+    fn test_format(#[case] hyphenated: bool, #[case] uppercase: bool, #[case] expected: &str) {
+        let id = Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+        let fmt = UuidFormat {
+            hyphenated,
+            uppercase,
+        };
+        assert_eq!(format(&id, fmt), expected);
+    }
+
+    #[rstest]
+    #[case(UuidVersion::V1, 1)]
+    #[case(UuidVersion::V3, 3)]
+    #[case(UuidVersion::V4, 4)]
+    #[case(UuidVersion::V5, 5)]
+    #[case(UuidVersion::V6, 6)]
+    #[case(UuidVersion::V7, 7)]
+    #[case(UuidVersion::V8, 8)]
+    fn test_generate_version(#[case] version: UuidVersion, #[case] expected: usize) {
+        let req = UuidRequest {
+            version,
+            count: 3,
+            ..Default::default()
+        };
+        let ids = generate(&req);
+        let expected_count = if version.is_deterministic() { 1 } else { 3 };
+        assert_eq!(ids.len(), expected_count);
+        assert!(ids.iter().all(|id| id.get_version_num() == expected));
+    }
+
+    #[test]
+    fn test_generate_name_based() {
+        let req = UuidRequest {
+            version: UuidVersion::V5,
+            count: 1,
+            namespace: Namespace::Dns,
+            name: "example.com".into(),
+            ..Default::default()
+        };
+        let id = generate(&req)[0];
+        assert_eq!(id.to_string(), "cfbff0d1-9375-5685-968c-48ce8b15ae17");
+
+        let req = UuidRequest {
+            version: UuidVersion::V3,
+            ..req
+        };
+        let id = generate(&req)[0];
+        assert_eq!(id.to_string(), "9073926b-929f-31c2-abc9-fad77ae3e8eb");
+    }
+
+    #[test]
+    fn test_generate_with_node_id() {
+        let node_id = parse_node_id("01:23:45:67:89:ab").unwrap();
+        let req = UuidRequest {
+            version: UuidVersion::V6,
+            count: 1,
+            node_id: Some(node_id),
+            ..Default::default()
+        };
+        let id = generate(&req)[0];
+        assert_eq!(&id.as_bytes()[10..], &node_id);
+    }
+
+    #[rstest]
+    #[case("01:23:45:67:89:ab", Ok([0x01, 0x23, 0x45, 0x67, 0x89, 0xab]))]
+    #[case("01-23-45-67-89-AB", Ok([0x01, 0x23, 0x45, 0x67, 0x89, 0xab]))]
+    #[case("0123456789ab", Ok([0x01, 0x23, 0x45, 0x67, 0x89, 0xab]))]
+    #[case("0123456789", Err(UuidError::InvalidNodeId))]
+    #[case("0123456789abcd", Err(UuidError::InvalidNodeId))]
+    #[case("xx23456789ab", Err(UuidError::InvalidNodeId))]
+    fn test_parse_node_id(#[case] input: &str, #[case] expected: Result<[u8; 6], UuidError>) {
+        assert_eq!(parse_node_id(input), expected);
+    }
+
+    #[test]
+    fn test_parse_payload() {
+        let payload = parse_payload("00112233-4455-6677-8899-aabbccddeeff").unwrap();
+        let id = Uuid::new_v8(payload);
+        assert_eq!(id.to_string(), "00112233-4455-8677-8899-aabbccddeeff");
+        assert_eq!(parse_payload("0011"), Err(UuidError::InvalidPayload));
+    }
+
+    #[test]
+    fn test_inspect_v7() {
+        let id = Uuid::parse_str("01920c8e-5f4b-7c2a-9d3e-0123456789ab").unwrap();
+        let info = inspect(&id);
+        assert_eq!(info.version, 7);
+        assert_eq!(info.variant, Variant::RFC4122);
+        let timestamp = info.timestamp.unwrap();
+        assert_eq!(timestamp.timestamp_millis(), 0x01920c8e5f4b);
+        assert_eq!(info.clock_seq, None);
+        assert_eq!(info.node_id, None);
+        assert_eq!(info.hex, "01920c8e5f4b7c2a9d3e0123456789ab");
+        assert_eq!(info.base64, "AZIMjl9LfCqdPgEjRWeJqw==");
+        assert!(!info.is_nil);
+    }
+
+    #[test]
+    fn test_inspect_v1() {
+        // RFC 9562 A.1
+        let id = Uuid::parse_str("c232ab00-9414-11ec-b3c8-9f6bdeced846").unwrap();
+        let info = inspect(&id);
+        assert_eq!(info.version, 1);
+        assert_eq!(
+            info.timestamp.unwrap().to_rfc3339(),
+            "2022-02-22T19:22:22+00:00"
+        );
+        assert_eq!(info.clock_seq, Some(0x33c8));
+        assert_eq!(info.node_id, Some([0x9f, 0x6b, 0xde, 0xce, 0xd8, 0x46]));
+    }
+
+    #[rstest]
+    #[case(Uuid::nil(), true, false, "0")]
+    #[case(Uuid::max(), false, true, "340282366920938463463374607431768211455")]
+    fn test_inspect_nil_max(
+        #[case] id: Uuid,
+        #[case] is_nil: bool,
+        #[case] is_max: bool,
+        #[case] decimal: &str,
+    ) {
+        let info = inspect(&id);
+        assert_eq!(info.is_nil, is_nil);
+        assert_eq!(info.is_max, is_max);
+        assert_eq!(info.decimal, decimal);
+        assert_eq!(info.timestamp, None);
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let text = "67e55044-10b1-426f-9247-bb680e5fe0c8\nfoo\n67E5504410B1426F9247BB680E5FE0C8";
+        let parsed = parse_lines(text);
+        assert_eq!(parsed.ids.len(), 2);
+        assert_eq!(parsed.ids[0], parsed.ids[1]);
+        assert_eq!(parsed.failure_count, 1);
+    }
+}
@@ -3,11 +3,12 @@ use std::ops::Range;
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Style},
     text::{Line, Text},
     widgets::{Block, Padding, Paragraph, StatefulWidget, Widget},
 };
 
+use crate::theme::Theme;
+
 struct BarCharSet {
     full: char,
     upper_half: char,
@@ -106,10 +107,11 @@ pub struct ScrollOutput<'a> {
     focused: bool,
     selected: bool,
     title: &'a str,
+    theme: &'a Theme,
 }
 
 impl<'a> ScrollOutput<'a> {
-    pub fn new<T>(text: T, focused: bool, selected: bool) -> ScrollOutput<'a>
+    pub fn new<T>(text: T, focused: bool, selected: bool, theme: &'a Theme) -> ScrollOutput<'a>
     where
         T: Into<Text<'a>>,
     {
@@ -118,6 +120,7 @@ impl<'a> ScrollOutput<'a> {
             focused,
             selected,
             title: "",
+            theme,
         }
     }
 
@@ -156,17 +159,9 @@ impl<'a> StatefulWidget for ScrollOutput<'a> {
     type State = ScrollOutputState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let style = if self.focused {
-            if self.selected {
-                Style::default().fg(Color::Blue)
-            } else {
-                Style::default().fg(Color::Reset)
-            }
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
+        let style = self.theme.item_style(self.focused, self.selected);
 
-        let max_content_height = area.height as usize - 2;
+        let max_content_height = (area.height as usize).saturating_sub(2);
 
         let max_offset = self.text.height().saturating_sub(max_content_height);
         if state.offset > max_offset {
@@ -189,7 +184,7 @@ impl<'a> StatefulWidget for ScrollOutput<'a> {
         );
         output.render(area, buf);
 
-        if self.text.height() > max_content_height {
+        if self.text.height() > max_content_height && max_content_height > 0 {
             let scrollbar_area = Rect::new(area.right() - 2, area.top() + 1, 1, area.height - 2);
             let scrollbar = ScrollBar::new(self.text.height(), state.offset);
             scrollbar.render(scrollbar_area, buf);
@@ -1,24 +1,29 @@
-use ratatui::{
-    buffer::Buffer,
-    layout::Rect,
-    style::{Color, Style},
-    widgets::Widget,
-};
+use ratatui::{buffer::Buffer, layout::Rect, style::Style, widgets::Widget};
+
+use crate::theme::Theme;
 
 pub struct Select {
     items: Vec<String>,
     current: usize,
     focused: bool,
     enabled: bool,
+    theme: Theme,
 }
 
 impl Select {
-    pub fn new(items: Vec<String>, current: usize, focused: bool, enabled: bool) -> Select {
+    pub fn new(
+        items: Vec<String>,
+        current: usize,
+        focused: bool,
+        enabled: bool,
+        theme: &Theme,
+    ) -> Select {
         Select {
             items,
             current,
             focused,
             enabled,
+            theme: *theme,
         }
     }
 }
@@ -40,16 +45,10 @@ impl Widget for Select {
 
 impl Select {
     fn parts_style(&self, disabled_cond: bool) -> Style {
-        if self.enabled {
-            if disabled_cond {
-                Style::default().fg(Color::DarkGray)
-            } else if self.focused {
-                Style::default().fg(Color::Blue)
-            } else {
-                Style::default().fg(Color::Reset)
-            }
+        if disabled_cond {
+            self.theme.disabled
         } else {
-            Style::default().fg(Color::DarkGray)
+            self.theme.item_style(self.enabled, self.focused)
         }
     }
 }